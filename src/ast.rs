@@ -18,7 +18,7 @@ impl Dimension {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Pou {
     pub name: String,
     pub variable_blocks: Vec<VariableBlock>,
@@ -38,11 +38,12 @@ impl Debug for Pou {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Implementation {
     pub name: String,
     pub type_name: String,
     pub linkage: LinkageType,
+    pub calling_convention: CallingConvention,
     pub pou_type: PouType,
     pub statements: Vec<Statement>,
     pub location: SourceRange,
@@ -54,6 +55,16 @@ pub enum LinkageType {
     External,
 }
 
+/// how the parameters of a callable are passed
+#[derive(Debug, Copy, PartialEq, Clone)]
+pub enum CallingConvention {
+    /// all parameters are passed in a single instance-struct
+    Default,
+    /// VAR_INPUTs are passed as individual native arguments, VAR_IN_OUTs and VAR_OUTPUTs as pointers
+    /// (selected by `{attribute 'calling_convention' := 'C'}`)
+    C,
+}
+
 #[derive(Debug, Copy, PartialEq, Clone)]
 pub enum PouType {
     Program,
@@ -105,10 +116,17 @@ pub enum VariableBlockType {
     InOut,
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct VariableBlock {
     pub variables: Vec<Variable>,
     pub variable_block_type: VariableBlockType,
+    /// the name of a global variable list (`VAR_GLOBAL Gvl_Io`), its variables can be accessed as `Gvl_Io.x`
+    pub name: Option<String>,
+    /// whether the variables of a global variable list can only be accessed qualified with the list's name
+    pub qualified_only: bool,
+    /// whether the block's inputs are read-only (`VAR_INPUT CONSTANT` or `VAR_INPUT {attribute 'by_ref'}`),
+    /// constant inputs are passed by reference instead of being copied into the callee
+    pub constant: bool,
 }
 
 impl Debug for VariableBlock {
@@ -218,6 +236,14 @@ pub enum DataType {
     StructType {
         name: Option<String>, //maybe None for inline structs
         variables: Vec<Variable>,
+        /// the maximum alignment of the struct's members in bytes
+        /// (selected by `{attribute 'pack_mode' := '...'}`), None for the natural alignment
+        pack_mode: Option<u32>,
+    },
+    /// a type whose members overlay the same storage
+    UnionType {
+        name: Option<String>,
+        variables: Vec<Variable>,
     },
     EnumType {
         name: Option<String>, //maybe empty for inline enums
@@ -238,6 +264,13 @@ pub enum DataType {
         is_wide: bool, //WSTRING
         size: Option<Statement>,
     },
+    /// a POINTER TO the referenced type or a REFERENCE TO it (`auto_deref`), which is
+    /// dereferenced implicitly on every use
+    PointerType {
+        name: Option<String>,
+        referenced_type: Box<DataTypeDeclaration>,
+        auto_deref: bool,
+    },
     VarArgs {
         referenced_type: Option<Box<DataTypeDeclaration>>,
     },
@@ -246,8 +279,22 @@ pub enum DataType {
 impl Debug for DataType {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
-            DataType::StructType { name, variables } => f
-                .debug_struct("StructType")
+            DataType::StructType {
+                name,
+                variables,
+                pack_mode,
+            } => {
+                let mut debug_struct = f.debug_struct("StructType");
+                debug_struct
+                    .field("name", name)
+                    .field("variables", variables);
+                if let Some(pack_mode) = pack_mode {
+                    debug_struct.field("pack_mode", pack_mode);
+                }
+                debug_struct.finish()
+            }
+            DataType::UnionType { name, variables } => f
+                .debug_struct("UnionType")
                 .field("name", name)
                 .field("variables", variables)
                 .finish(),
@@ -286,6 +333,20 @@ impl Debug for DataType {
                 .field("is_wide", is_wide)
                 .field("size", size)
                 .finish(),
+            DataType::PointerType {
+                name,
+                referenced_type,
+                auto_deref,
+            } => {
+                let mut debug_struct = f.debug_struct("PointerType");
+                debug_struct
+                    .field("name", name)
+                    .field("referenced_type", referenced_type);
+                if *auto_deref {
+                    debug_struct.field("auto_deref", auto_deref);
+                }
+                debug_struct.finish()
+            }
             DataType::VarArgs { referenced_type } => f
                 .debug_struct("VarArgs")
                 .field("referenced_type", referenced_type)
@@ -297,22 +358,26 @@ impl Debug for DataType {
 impl DataType {
     pub fn set_name(&mut self, new_name: String) {
         match self {
-            DataType::StructType { name, variables: _ } => *name = Some(new_name),
+            DataType::StructType { name, .. } => *name = Some(new_name),
+            DataType::UnionType { name, .. } => *name = Some(new_name),
             DataType::EnumType { name, elements: _ } => *name = Some(new_name),
             DataType::SubRangeType { name, .. } => *name = Some(new_name),
             DataType::ArrayType { name, .. } => *name = Some(new_name),
             DataType::StringType { name, .. } => *name = Some(new_name),
+            DataType::PointerType { name, .. } => *name = Some(new_name),
             DataType::VarArgs { .. } => {} //No names on varargs
         }
     }
 
     pub fn get_name(&self) -> Option<&str> {
         match self {
-            DataType::StructType { name, variables: _ } => name.as_ref().map(|x| x.as_str()),
+            DataType::StructType { name, .. } => name.as_ref().map(|x| x.as_str()),
+            DataType::UnionType { name, .. } => name.as_ref().map(|x| x.as_str()),
             DataType::EnumType { name, elements: _ } => name.as_ref().map(|x| x.as_str()),
             DataType::ArrayType { name, .. } => name.as_ref().map(|x| x.as_str()),
             DataType::StringType { name, .. } => name.as_ref().map(|x| x.as_str()),
             DataType::SubRangeType { name, .. } => name.as_ref().map(|x| x.as_str()),
+            DataType::PointerType { name, .. } => name.as_ref().map(|x| x.as_str()),
             DataType::VarArgs { .. } => None,
         }
     }
@@ -324,6 +389,9 @@ impl DataType {
     ) -> Option<DataTypeDeclaration> {
         if let DataType::ArrayType {
             referenced_type, ..
+        }
+        | DataType::PointerType {
+            referenced_type, ..
         } = self
         {
             if let DataTypeDeclaration::DataTypeReference { .. } = **referenced_type {
@@ -369,6 +437,7 @@ pub enum Statement {
         year: i32,
         month: u32,
         day: u32,
+        is_long: bool, //LDATE
         location: SourceRange,
     },
     LiteralDateAndTime {
@@ -378,14 +447,16 @@ pub enum Statement {
         hour: u32,
         min: u32,
         sec: u32,
-        milli: u32,
+        nano: u32,
+        is_long: bool, //LDATE_AND_TIME
         location: SourceRange,
     },
     LiteralTimeOfDay {
         hour: u32,
         min: u32,
         sec: u32,
-        milli: u32,
+        nano: u32,
+        is_long: bool, //LTIME_OF_DAY
         location: SourceRange,
     },
     LiteralTime {
@@ -397,6 +468,7 @@ pub enum Statement {
         micro: f64,
         nano: u32,
         negative: bool,
+        is_long: bool, //LTIME
         location: SourceRange,
     },
     LiteralReal {
@@ -433,6 +505,11 @@ pub enum Statement {
         reference: Box<Statement>,
         access: Box<Statement>,
     },
+    /// the value a pointer points to (`p^`)
+    PointerAccess {
+        reference: Box<Statement>,
+        location: SourceRange,
+    },
     BinaryExpression {
         operator: Operator,
         left: Box<Statement>,
@@ -460,6 +537,11 @@ pub enum Statement {
         left: Box<Statement>,
         right: Box<Statement>,
     },
+    /// binds the REFERENCE on the left to the variable on the right (`r REF= x`)
+    RefAssignment {
+        left: Box<Statement>,
+        right: Box<Statement>,
+    },
     //Call Statement
     CallStatement {
         operator: Box<Statement>,
@@ -510,12 +592,17 @@ impl Debug for Statement {
                 .field("value", value)
                 .finish(),
             Statement::LiteralDate {
-                year, month, day, ..
+                year,
+                month,
+                day,
+                is_long,
+                ..
             } => f
                 .debug_struct("LiteralDate")
                 .field("year", year)
                 .field("month", month)
                 .field("day", day)
+                .field("is_long", is_long)
                 .finish(),
             Statement::LiteralDateAndTime {
                 year,
@@ -524,7 +611,8 @@ impl Debug for Statement {
                 hour,
                 min,
                 sec,
-                milli,
+                nano,
+                is_long,
                 ..
             } => f
                 .debug_struct("LiteralDateAndTime")
@@ -534,20 +622,23 @@ impl Debug for Statement {
                 .field("hour", hour)
                 .field("min", min)
                 .field("sec", sec)
-                .field("milli", milli)
+                .field("nano", nano)
+                .field("is_long", is_long)
                 .finish(),
             Statement::LiteralTimeOfDay {
                 hour,
                 min,
                 sec,
-                milli,
+                nano,
+                is_long,
                 ..
             } => f
                 .debug_struct("LiteralTimeOfDay")
                 .field("hour", hour)
                 .field("min", min)
                 .field("sec", sec)
-                .field("milli", milli)
+                .field("nano", nano)
+                .field("is_long", is_long)
                 .finish(),
             Statement::LiteralTime {
                 day,
@@ -558,6 +649,7 @@ impl Debug for Statement {
                 micro,
                 nano,
                 negative,
+                is_long,
                 ..
             } => f
                 .debug_struct("LiteralTime")
@@ -569,6 +661,7 @@ impl Debug for Statement {
                 .field("micro", micro)
                 .field("nano", nano)
                 .field("negative", negative)
+                .field("is_long", is_long)
                 .finish(),
             Statement::LiteralReal { value, .. } => {
                 f.debug_struct("LiteralReal").field("value", value).finish()
@@ -629,6 +722,11 @@ impl Debug for Statement {
                 .field("left", left)
                 .field("right", right)
                 .finish(),
+            Statement::RefAssignment { left, right } => f
+                .debug_struct("RefAssignment")
+                .field("left", left)
+                .field("right", right)
+                .finish(),
             Statement::CallStatement {
                 operator,
                 parameters,
@@ -692,6 +790,10 @@ impl Debug for Statement {
                 .field("reference", reference)
                 .field("access", access)
                 .finish(),
+            Statement::PointerAccess { reference, .. } => f
+                .debug_struct("PointerAccess")
+                .field("reference", reference)
+                .finish(),
             Statement::MultipliedStatement {
                 multiplier,
                 element,
@@ -765,7 +867,8 @@ impl Statement {
                 let right_loc = right.get_location();
                 SourceRange::new(left_loc.range.start..right_loc.range.end)
             }
-            Statement::OutputAssignment { left, right } => {
+            Statement::OutputAssignment { left, right }
+            | Statement::RefAssignment { left, right } => {
                 let left_loc = left.get_location();
                 let right_loc = right.get_location();
                 SourceRange::new(left_loc.range.start..right_loc.range.end)
@@ -781,6 +884,7 @@ impl Statement {
                 let access_loc = access.get_location();
                 SourceRange::new(reference_loc.range.start..access_loc.range.end)
             }
+            Statement::PointerAccess { location, .. } => location.clone(),
             Statement::MultipliedStatement { location, .. } => location.clone(),
             Statement::CaseCondition { condition } => condition.get_location(),
         }
@@ -814,7 +918,16 @@ impl Display for Operator {
             Operator::Multiplication => "*",
             Operator::Division => "/",
             Operator::Equal => "=",
-            _ => unimplemented!(),
+            Operator::NotEqual => "<>",
+            Operator::Modulo => "MOD",
+            Operator::Less => "<",
+            Operator::Greater => ">",
+            Operator::LessOrEqual => "<=",
+            Operator::GreaterOrEqual => ">=",
+            Operator::Not => "NOT",
+            Operator::And => "AND",
+            Operator::Or => "OR",
+            Operator::Xor => "XOR",
         };
         f.write_str(symbol)
     }
@@ -855,6 +968,15 @@ pub fn get_array_dimensions(bounds: &Statement) -> result::Result<Vec<Dimension>
     Ok(result)
 }
 
+/// returns true if the given bounds-statement declares an array of variable length
+/// e.g. [*] or [*, *]
+pub fn is_variable_length_array(bounds: &Statement) -> bool {
+    bounds
+        .get_as_list()
+        .iter()
+        .all(|it| matches!(it, Statement::EmptyStatement { .. }))
+}
+
 /// constructs a Dimension for the given RangeStatement
 /// throws an error if the given statement is no RangeStatement
 fn get_single_array_dimension(bounds: &Statement) -> result::Result<Dimension, CompileError> {
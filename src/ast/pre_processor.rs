@@ -18,21 +18,27 @@ pub fn pre_process(unit: &mut CompilationUnit) {
         }
     }
 
-    //process all variables from GVLs
-    let all_variables = unit
-        .global_vars
-        .iter_mut()
-        .flat_map(|gv| gv.variables.iter_mut())
-        .filter(|it| should_generate_implicit_type(it));
+    //process all variables from GVLs, the types of named lists' variables are named after the list
+    for block in unit.global_vars.iter_mut() {
+        let container_name = block.name.as_deref().unwrap_or("global");
+        let all_variables = block
+            .variables
+            .iter_mut()
+            .filter(|it| should_generate_implicit_type(it));
 
-    for var in all_variables {
-        pre_process_variable_data_type("global", var, &mut unit.types)
+        for var in all_variables {
+            pre_process_variable_data_type(container_name, var, &mut unit.types)
+        }
     }
 
     //process all variables in dataTypes
     let mut new_types = vec![];
     for dt in unit.types.iter_mut() {
-        if let DataType::StructType { name, variables } = &mut dt.data_type {
+        if let DataType::StructType {
+            name, variables, ..
+        }
+        | DataType::UnionType { name, variables } = &mut dt.data_type
+        {
             variables
                 .iter_mut()
                 .filter(|it| should_generate_implicit_type(it))
@@ -0,0 +1,142 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+
+/// builtin functions that are generated inline by the compiler instead of being called.
+/// A POU declared with the same name takes precedence over the builtin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuiltIn {
+    /// SHL(IN, N) - shifts IN left by N bits, zero-filled on the right
+    Shl,
+    /// SHR(IN, N) - shifts IN right by N bits, zero-filled on the left
+    Shr,
+    /// ROL(IN, N) - rotates IN left by N bits (circular)
+    Rol,
+    /// ROR(IN, N) - rotates IN right by N bits (circular)
+    Ror,
+    /// SEL(G, IN0, IN1) - selects IN0 if G is FALSE, IN1 otherwise
+    Sel,
+    /// MUX(K, IN0, IN1, ...) - selects the K-th input
+    Mux,
+    /// MAX(IN1, IN2, ...) - the biggest of all inputs
+    Max,
+    /// MIN(IN1, IN2, ...) - the smallest of all inputs
+    Min,
+    /// LIMIT(MN, IN, MX) - IN limited to the range MN..MX
+    Limit,
+    /// MOVE(IN) - the value of IN
+    Move,
+    /// LOWER_BOUND(ARR, DIM) - the lower bound of the array's dimension DIM (1-based)
+    LowerBound,
+    /// UPPER_BOUND(ARR, DIM) - the upper bound of the array's dimension DIM (1-based)
+    UpperBound,
+    /// __NEW(T) / __NEW(T, N) - allocates and initializes one (or N) instances of the type T
+    /// and returns a POINTER TO T
+    New,
+    /// __DELETE(P) - releases the memory allocated by __NEW and sets the pointer P to 0
+    Delete,
+    /// __ISVALIDREF(R) - TRUE if the REFERENCE R is bound to a variable
+    IsValidRef,
+}
+
+impl BuiltIn {
+    /// returns the builtin with the given name or None if there is no such builtin
+    pub fn find(name: &str) -> Option<BuiltIn> {
+        match name.to_uppercase().as_str() {
+            "SHL" => Some(BuiltIn::Shl),
+            "SHR" => Some(BuiltIn::Shr),
+            "ROL" => Some(BuiltIn::Rol),
+            "ROR" => Some(BuiltIn::Ror),
+            "SEL" => Some(BuiltIn::Sel),
+            "MUX" => Some(BuiltIn::Mux),
+            "MAX" => Some(BuiltIn::Max),
+            "MIN" => Some(BuiltIn::Min),
+            "LIMIT" => Some(BuiltIn::Limit),
+            "MOVE" => Some(BuiltIn::Move),
+            "LOWER_BOUND" => Some(BuiltIn::LowerBound),
+            "UPPER_BOUND" => Some(BuiltIn::UpperBound),
+            "__NEW" => Some(BuiltIn::New),
+            "__DELETE" => Some(BuiltIn::Delete),
+            "__ISVALIDREF" => Some(BuiltIn::IsValidRef),
+            _ => None,
+        }
+    }
+
+    /// returns the name of the builtin as it is called in ST
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            BuiltIn::Shl => "SHL",
+            BuiltIn::Shr => "SHR",
+            BuiltIn::Rol => "ROL",
+            BuiltIn::Ror => "ROR",
+            BuiltIn::Sel => "SEL",
+            BuiltIn::Mux => "MUX",
+            BuiltIn::Max => "MAX",
+            BuiltIn::Min => "MIN",
+            BuiltIn::Limit => "LIMIT",
+            BuiltIn::Move => "MOVE",
+            BuiltIn::LowerBound => "LOWER_BOUND",
+            BuiltIn::UpperBound => "UPPER_BOUND",
+            BuiltIn::New => "__NEW",
+            BuiltIn::Delete => "__DELETE",
+            BuiltIn::IsValidRef => "__ISVALIDREF",
+        }
+    }
+
+    /// returns the names of the builtin's formal parameters in the order
+    /// they are passed in a non-formal call. Variadic builtins return
+    /// the parameters they require at least.
+    pub fn get_parameter_names(&self) -> &'static [&'static str] {
+        match self {
+            BuiltIn::Shl | BuiltIn::Shr | BuiltIn::Rol | BuiltIn::Ror => &["IN", "N"],
+            BuiltIn::Sel => &["G", "IN0", "IN1"],
+            BuiltIn::Mux => &["K", "IN0"],
+            BuiltIn::Max | BuiltIn::Min => &["IN1", "IN2"],
+            BuiltIn::Limit => &["MN", "IN", "MX"],
+            BuiltIn::Move => &["IN"],
+            BuiltIn::LowerBound | BuiltIn::UpperBound => &["ARR", "DIM"],
+            BuiltIn::New => &["T", "N"],
+            BuiltIn::Delete => &["P"],
+            BuiltIn::IsValidRef => &["R"],
+        }
+    }
+
+    /// returns the number of parameters that need to be passed, the remaining
+    /// parameters returned by `get_parameter_names` are optional
+    pub fn get_required_parameter_count(&self) -> usize {
+        match self {
+            BuiltIn::New => 1,
+            _ => self.get_parameter_names().len(),
+        }
+    }
+
+    /// returns true if the builtin accepts an arbitrary number of parameters
+    pub fn is_variadic(&self) -> bool {
+        matches!(self, BuiltIn::Mux | BuiltIn::Max | BuiltIn::Min)
+    }
+
+    /// returns the name of the parameter at the given position
+    pub fn get_parameter_name(&self, position: usize) -> String {
+        match self {
+            BuiltIn::Mux if position > 0 => format!("IN{}", position - 1),
+            BuiltIn::Max | BuiltIn::Min => format!("IN{}", position + 1),
+            _ => self.get_parameter_names()[position].to_string(),
+        }
+    }
+
+    /// returns the position of the parameter with the given name
+    /// or None if the builtin has no such parameter
+    pub fn get_parameter_position(&self, name: &str) -> Option<usize> {
+        let fixed_position = self.get_parameter_names().iter().position(|it| *it == name);
+        if fixed_position.is_some() || !self.is_variadic() {
+            return fixed_position;
+        }
+        //variadic inputs are numbered IN0, IN1, ... (MUX) or IN1, IN2, ... (MAX, MIN)
+        let number = name
+            .strip_prefix("IN")
+            .and_then(|it| it.parse::<usize>().ok())?;
+        match self {
+            BuiltIn::Mux => Some(number + 1),
+            _ if number > 0 => Some(number - 1),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 // Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use crate::CodeGenOptions;
 use std::path::Path;
 use structopt::{clap::ArgGroup, StructOpt};
 
@@ -68,6 +69,35 @@ pub struct CompileParameters {
     )]
     pub target: Option<String>,
 
+    #[structopt(
+        short = "D",
+        long = "define",
+        name = "define",
+        number_of_values = 1,
+        parse(from_str = parse_define),
+        help = "Defines a symbol for conditional compilation, as NAME or NAME=VALUE"
+    )]
+    pub defines: Vec<(String, Option<String>)>,
+
+    #[structopt(
+        long = "bounds-checks",
+        help = "Trap if an array is accessed out of its bounds"
+    )]
+    pub bounds_checks: bool,
+
+    #[structopt(
+        long = "overflow-checks",
+        help = "Trap if an integer addition, subtraction or multiplication overflows"
+    )]
+    pub overflow_checks: bool,
+
+    #[structopt(
+        long = "trap-handler",
+        name = "trap-handler",
+        help = "A POU that is called with the failing statement's location before a runtime check traps"
+    )]
+    pub trap_handler: Option<String>,
+
     #[structopt(
         name = "input-files",
         help = "Read input from <input-files>, may be a glob expression like 'src/**/*' or a sequence of files",
@@ -78,6 +108,13 @@ pub struct CompileParameters {
     pub input: Vec<String>,
 }
 
+/// splits a `NAME=VALUE` definition into its name and optional value
+fn parse_define(define: &str) -> (String, Option<String>) {
+    let mut segments = define.splitn(2, '=');
+    let name = segments.next().unwrap_or_default().trim().to_string();
+    (name, segments.next().map(|it| it.trim().to_string()))
+}
+
 impl CompileParameters {
     pub fn parse(args: Vec<String>) -> Result<CompileParameters, ParameterError> {
         CompileParameters::from_iter_safe(args)
@@ -107,6 +144,16 @@ impl CompileParameters {
         self.output_format().unwrap_or(DEFAULT_FORMAT)
     }
 
+    /// return the options used to generate the code
+    pub fn codegen_options(&self) -> CodeGenOptions {
+        CodeGenOptions {
+            defines: self.defines.clone(),
+            bounds_checks: self.bounds_checks,
+            overflow_checks: self.overflow_checks,
+            trap_handler: self.trap_handler.clone(),
+        }
+    }
+
     /// return the output filename with the correct ending
     pub fn output_name(&self) -> Option<String> {
         if let Some(n) = &self.output {
@@ -130,6 +177,7 @@ impl CompileParameters {
 #[cfg(test)]
 mod cli_tests {
     use super::{CompileParameters, FormatOption, ParameterError};
+    use crate::CodeGenOptions;
     use structopt::clap::ErrorKind;
 
     fn expect_argument_error(args: Vec<String>, expected_error_kind: ErrorKind) {
@@ -188,6 +236,52 @@ mod cli_tests {
         );
     }
 
+    #[test]
+    fn runtime_checks_are_passed_to_the_codegen_options() {
+        let parameters = CompileParameters::parse(vec_of_strings!("input.st")).unwrap();
+        assert_eq!(parameters.codegen_options(), CodeGenOptions::default());
+
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "input.st",
+            "--bounds-checks",
+            "--overflow-checks",
+            "--trap-handler",
+            "on_trap"
+        ))
+        .unwrap();
+        assert_eq!(
+            parameters.codegen_options(),
+            CodeGenOptions {
+                bounds_checks: true,
+                overflow_checks: true,
+                trap_handler: Some("on_trap".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn defined_symbols_are_passed_to_the_codegen_options() {
+        let parameters = CompileParameters::parse(vec_of_strings!(
+            "-D",
+            "SIMULATOR",
+            "input.st",
+            "--define",
+            "CHANNELS=4",
+            "-DMODE = fast"
+        ))
+        .unwrap();
+        assert_eq!(parameters.input, vec!["input.st".to_string()]);
+        assert_eq!(
+            parameters.codegen_options().defines,
+            vec![
+                ("SIMULATOR".to_string(), None),
+                ("CHANNELS".to_string(), Some("4".to_string())),
+                ("MODE".to_string(), Some("fast".to_string())),
+            ]
+        );
+    }
+
     #[test]
     fn valid_output_files() {
         //short -o
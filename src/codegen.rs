@@ -3,7 +3,7 @@
 /// module to generate llvm intermediate representation for a CompilationUnit
 use self::{
     generators::{
-        data_type_generator,
+        data_type_generator, intrinsic_generator,
         llvm::Llvm,
         pou_generator::{self, PouGenerator},
         variable_generator,
@@ -17,6 +17,9 @@ use super::index::*;
 use crate::typesystem::{DataType, *};
 use inkwell::context::Context;
 use inkwell::module::Module;
+use inkwell::targets::{
+    CodeModel, InitializationConfig, RelocMode, Target, TargetData, TargetMachine, TargetTriple,
+};
 use inkwell::values::{BasicValueEnum, PointerValue};
 
 mod generators;
@@ -35,6 +38,8 @@ pub struct TypeAndPointer<'a, 'b> {
     type_entry: &'b DataType,
     /// the pointer value
     ptr_value: PointerValue<'a>,
+    /// the bit inside of the pointed to byte if this is a BIT member sharing a byte with other members
+    bit_offset: Option<u32>,
 }
 
 impl<'a, 'b> TypeAndPointer<'a, 'b> {
@@ -43,15 +48,77 @@ impl<'a, 'b> TypeAndPointer<'a, 'b> {
         TypeAndPointer {
             type_entry: entry,
             ptr_value: value,
+            bit_offset: None,
+        }
+    }
+
+    /// constructs a new TypeAndPointer to a single bit of the byte the given pointer points to
+    pub fn new_bit_field(
+        entry: &'b DataType,
+        value: PointerValue<'a>,
+        bit_offset: u32,
+    ) -> TypeAndPointer<'a, 'b> {
+        TypeAndPointer {
+            type_entry: entry,
+            ptr_value: value,
+            bit_offset: Some(bit_offset),
         }
     }
 
+    /// returns true if this points to a single bit instead of a whole value
+    pub fn is_bit_field(&self) -> bool {
+        self.bit_offset.is_some()
+    }
+
     /// returns the DataTypeInformation for the pointer's dereferenced type
     pub fn get_type_information(&self) -> &DataTypeInformation {
         self.type_entry.get_type_information()
     }
 }
 
+/// options that control which parts of the sources are compiled and which additional code is generated
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CodeGenOptions {
+    /// the symbols defined for conditional compilation (e.g. `-D SIMULATOR` or `-D CHANNELS=4`)
+    pub defines: Vec<(String, Option<String>)>,
+    /// generate checks that trap if an array is accessed out of its bounds
+    pub bounds_checks: bool,
+    /// generate checks that trap if an integer addition, subtraction or multiplication overflows
+    pub overflow_checks: bool,
+    /// the name of a POU that is called with the failing statement's location before a check traps
+    pub trap_handler: Option<String>,
+}
+
+/// creates the target machine for the given target triple
+pub fn create_target_machine(
+    triple: &TargetTriple,
+    reloc: RelocMode,
+) -> Result<TargetMachine, CompileError> {
+    Target::initialize_all(&InitializationConfig::default());
+    let target = Target::from_triple(triple)
+        .map_err(|err| CompileError::codegen_error(err.to_string(), SourceRange::undefined()))?;
+    target
+        .create_target_machine(
+            triple,
+            //TODO : Add cpu features as optionals
+            "generic", //TargetMachine::get_host_cpu_name().to_string().as_str(),
+            "",        //TargetMachine::get_host_cpu_features().to_string().as_str(),
+            //TODO Optimisation as parameter
+            inkwell::OptimizationLevel::Default,
+            reloc,
+            CodeModel::Default,
+        )
+        .ok_or_else(|| {
+            CompileError::codegen_error(
+                format!(
+                    "Cannot create a target machine for {}",
+                    triple.as_str().to_string_lossy()
+                ),
+                SourceRange::undefined(),
+            )
+        })
+}
+
 /// the codegen struct carries all dependencies required to generate
 /// the IR code for a compilation unit
 pub struct CodeGen<'ink> {
@@ -59,13 +126,61 @@ pub struct CodeGen<'ink> {
     pub context: &'ink Context,
     /// the module represents a llvm compilation unit
     pub module: Module<'ink>,
+    /// the options used to generate the code
+    options: CodeGenOptions,
+    /// the data layout of the target the types are laid out for
+    target_data: TargetData,
 }
 
 impl<'ink> CodeGen<'ink> {
     /// constructs a new code-generator that generates CompilationUnits into a module with the given module_name
     pub fn new(context: &'ink Context, module_name: &str) -> CodeGen<'ink> {
+        CodeGen::new_with_options(context, module_name, CodeGenOptions::default())
+    }
+
+    /// constructs a new code-generator that generates CompilationUnits into a module with the given module_name
+    /// using the given options. The types are laid out for the machine's target
+    pub fn new_with_options(
+        context: &'ink Context,
+        module_name: &str,
+        options: CodeGenOptions,
+    ) -> CodeGen<'ink> {
+        let machine =
+            create_target_machine(&TargetMachine::get_default_triple(), RelocMode::Default)
+                .expect("the machine's target is supported");
+        CodeGen::new_with_target_data(context, module_name, options, machine.get_target_data())
+    }
+
+    fn new_with_target_data(
+        context: &'ink Context,
+        module_name: &str,
+        options: CodeGenOptions,
+        target_data: TargetData,
+    ) -> CodeGen<'ink> {
         let module = context.create_module(module_name);
-        CodeGen { context, module }
+        CodeGen {
+            context,
+            module,
+            options,
+            target_data,
+        }
+    }
+
+    /// constructs a new code-generator that generates CompilationUnits into a module with the given module_name
+    /// using the given options. The module is generated for the given target machine's triple and data layout
+    pub fn new_for_target(
+        context: &'ink Context,
+        module_name: &str,
+        options: CodeGenOptions,
+        machine: &TargetMachine,
+    ) -> CodeGen<'ink> {
+        let code_generator =
+            CodeGen::new_with_target_data(context, module_name, options, machine.get_target_data());
+        code_generator.module.set_triple(&machine.get_triple());
+        code_generator
+            .module
+            .set_data_layout(&code_generator.target_data.get_data_layout());
+        code_generator
     }
 
     fn generate_llvm_index(
@@ -76,7 +191,8 @@ impl<'ink> CodeGen<'ink> {
         let llvm = Llvm::new(&self.context, self.context.create_builder());
         let mut index = LlvmTypedIndex::new();
         //Generate types index, and any global variables associated with them.
-        let llvm_type_index = data_type_generator::generate_data_types(&llvm, global_index)?;
+        let llvm_type_index =
+            data_type_generator::generate_data_types(&llvm, global_index, &self.target_data)?;
         index.merge(llvm_type_index);
         //Generate global variables
         let llvm_gv_index =
@@ -87,6 +203,18 @@ impl<'ink> CodeGen<'ink> {
         let llvm_impl_index =
             pou_generator::generate_implementation_stubs(module, llvm, global_index, &index)?;
         index.merge(llvm_impl_index);
+        //Declare the llvm-intrinsics that may be used by the generated code
+        let llvm = Llvm::new(&self.context, self.context.create_builder());
+        let llvm_intrinsic_index = intrinsic_generator::generate_intrinsic_stubs(module, &llvm)?;
+        index.merge(llvm_intrinsic_index);
+        //Generate the default allocator hooks used by __NEW and __DELETE
+        let llvm_allocator_index = intrinsic_generator::generate_allocator_hooks(
+            module,
+            &llvm,
+            global_index,
+            &self.target_data,
+        )?;
+        index.merge(llvm_allocator_index);
         Ok(index)
     }
 
@@ -101,7 +229,8 @@ impl<'ink> CodeGen<'ink> {
 
         //generate all pous
         let llvm = Llvm::new(&self.context, self.context.create_builder());
-        let pou_generator = PouGenerator::new(llvm, global_index, &llvm_index);
+        let pou_generator =
+            PouGenerator::new(llvm, global_index, &llvm_index, self.options.clone());
         //Generate the POU stubs in the first go to make sure they can be referenced.
         for implementation in unit.implementations {
             //Don't generate external functions
@@ -109,6 +238,7 @@ impl<'ink> CodeGen<'ink> {
                 pou_generator.generate_implementation(&implementation)?;
             }
         }
+        intrinsic_generator::remove_unused_intrinsics(&self.module, self.context);
         Ok(self.module.print_to_string().to_string())
     }
 }
@@ -1,5 +1,6 @@
 pub mod data_type_generator;
 pub mod expression_generator;
+pub mod intrinsic_generator;
 pub mod llvm;
 pub mod pou_generator;
 pub mod statement_generator;
@@ -1,7 +1,8 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 
 /// the data_type_generator generates user defined data-types
-/// - Structures
+/// - Structures (optionally packed)
+/// - Unions
 /// - Enum types
 /// - SubRange types
 /// - Alias types
@@ -14,19 +15,23 @@ use crate::{
 };
 use crate::{
     codegen::{
-        llvm_index::LlvmTypedIndex,
+        llvm_index::{LlvmTypedIndex, MemberLocation},
         llvm_typesystem::{get_llvm_float_type, get_llvm_int_type},
     },
     typesystem::DataType,
 };
 use inkwell::{
+    targets::TargetData,
     types::{ArrayType, BasicType, BasicTypeEnum},
     values::BasicValueEnum,
     AddressSpace,
 };
+use std::collections::HashSet;
 
 use super::{
-    expression_generator::ExpressionCodeGenerator, llvm::Llvm, struct_generator::StructGenerator,
+    expression_generator::ExpressionCodeGenerator,
+    llvm::Llvm,
+    struct_generator::{StructField, StructGenerator},
 };
 
 /// generates the llvm-type for the given data-type and registers it at the index
@@ -36,15 +41,22 @@ use super::{
 /// - an alias index entry for sub-range types
 /// - Array type for arrays
 /// - array type for sized Strings
+/// - fat pointer struct type for variable-length arrays
+///
+/// unions and packed structs are laid out with the sizes and alignments of the given target_data
 pub fn generate_data_types<'ink>(
     llvm: &Llvm<'ink>,
     index: &Index,
+    target_data: &TargetData,
 ) -> Result<LlvmTypedIndex<'ink>, CompileError> {
     let mut types_index = LlvmTypedIndex::new();
     let types = index.get_types();
     for (name, user_type) in types {
         if let DataTypeInformation::Struct {
             name: struct_name, ..
+        }
+        | DataTypeInformation::Union {
+            name: struct_name, ..
         } = user_type.get_type_information()
         {
             types_index.associate_type(name, llvm.create_struct_stub(struct_name).into())?;
@@ -54,25 +66,130 @@ pub fn generate_data_types<'ink>(
         let gen_type = create_type(llvm, index, &types_index, name, user_type)?;
         types_index.associate_type(name, gen_type)?
     }
+    let mut generated_types = HashSet::new();
     for (name, user_type) in types {
-        expand_opaque_types(llvm, index, &mut types_index, user_type)?;
-        if let Some(initial_value) = generate_initial_value(index, &types_index, llvm, user_type) {
-            types_index.associate_initial_value(name, initial_value)?
-        }
+        generate_initial_values(
+            llvm,
+            index,
+            target_data,
+            &mut types_index,
+            name,
+            user_type,
+            &mut generated_types,
+        )?;
     }
     Ok(types_index)
 }
 
+/// expands the given type and associates its initial value.
+/// The types it is composed of (e.g. the function blocks of a program's instances) are generated first,
+/// so their initial values are available when the type's initial value is built.
+///
+/// - `generated_types` the names of all types that were already generated
+fn generate_initial_values<'ink>(
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    target_data: &TargetData,
+    types_index: &mut LlvmTypedIndex<'ink>,
+    name: &str,
+    data_type: &DataType,
+    generated_types: &mut HashSet<String>,
+) -> Result<(), CompileError> {
+    if !generated_types.insert(name.to_lowercase()) {
+        return Ok(());
+    }
+    let dependencies: Vec<&str> = match data_type.get_type_information() {
+        DataTypeInformation::Struct { member_names, .. }
+        | DataTypeInformation::Union { member_names, .. } => member_names
+            .iter()
+            .filter_map(|member| index.find_member(data_type.get_name(), member))
+            .map(VariableIndexEntry::get_type_name)
+            .collect(),
+        DataTypeInformation::Array {
+            inner_type_name, ..
+        } => vec![inner_type_name],
+        DataTypeInformation::SubRange {
+            referenced_type, ..
+        }
+        | DataTypeInformation::Alias {
+            referenced_type, ..
+        } => vec![referenced_type],
+        _ => vec![],
+    };
+    for dependency in dependencies {
+        if let Some(dependency_type) = index.find_type(dependency) {
+            generate_initial_values(
+                llvm,
+                index,
+                target_data,
+                types_index,
+                dependency,
+                dependency_type,
+                generated_types,
+            )?;
+        }
+    }
+
+    expand_opaque_types(llvm, index, target_data, types_index, data_type)?;
+    //arrays of structs (e.g. function block instances) without an initializer start with their elements' initial values
+    let initial_value = generate_initial_value(index, types_index, llvm, data_type)
+        .or_else(|| generate_array_of_structs_initializer(types_index, data_type));
+    if let Some(initial_value) = initial_value {
+        types_index.associate_initial_value(name, initial_value)?
+    }
+    Ok(())
+}
+
+/// generates an initial value for an array of structs that repeats the struct's initial value
+/// for every element
+fn generate_array_of_structs_initializer<'ink>(
+    types_index: &LlvmTypedIndex<'ink>,
+    data_type: &DataType,
+) -> Option<BasicValueEnum<'ink>> {
+    if let DataTypeInformation::Array {
+        inner_type_name,
+        dimensions,
+        ..
+    } = data_type.get_type_information()
+    {
+        let element = types_index
+            .find_associated_initial_value(inner_type_name)
+            .filter(|it| it.is_struct_value())?;
+        let mut initial_value = element;
+        for dimension in dimensions.iter().rev() {
+            let length = dimension.get_length() as usize;
+            initial_value = match initial_value {
+                BasicValueEnum::StructValue(value) => {
+                    value.get_type().const_array(&vec![value; length])
+                }
+                BasicValueEnum::ArrayValue(value) => {
+                    value.get_type().const_array(&vec![value; length])
+                }
+                _ => unreachable!("arrays of structs are built from structs and arrays"),
+            }
+            .into();
+        }
+        Some(initial_value)
+    } else {
+        None
+    }
+}
+
 /// generates the members of an opaque struct and associates its initial values
 fn expand_opaque_types<'ink>(
     llvm: &Llvm<'ink>,
     index: &Index,
+    target_data: &TargetData,
     types_index: &mut LlvmTypedIndex<'ink>,
     data_type: &DataType,
 ) -> Result<(), CompileError> {
     let information = data_type.get_type_information();
-    if let DataTypeInformation::Struct { member_names, .. } = information {
-        let mut struct_generator = StructGenerator::new(llvm, index, types_index);
+    if let DataTypeInformation::Struct {
+        member_names,
+        pack_mode,
+        ..
+    } = information
+    {
         let members: Vec<&VariableIndexEntry> = member_names
             .iter()
             .map(|variable_name| {
@@ -82,17 +199,206 @@ fn expand_opaque_types<'ink>(
             })
             .filter(|var| !var.is_return())
             .collect();
+        let (fields, packed) = if let Some(pack_mode) = pack_mode {
+            let (fields, _, alignment) =
+                create_packed_struct_fields(index, types_index, target_data, &members, *pack_mode)?;
+            //llvm only has to pack the struct if some members are aligned to less than their natural alignment
+            let mut natural_alignment = 1;
+            for member in &members {
+                natural_alignment = natural_alignment.max(
+                    get_size_and_alignment(
+                        index,
+                        types_index,
+                        target_data,
+                        member.get_type_name(),
+                    )?
+                    .1,
+                );
+            }
+            (fields, alignment < natural_alignment)
+        } else {
+            let fields: Vec<StructField> =
+                members.iter().map(|it| StructField::Member(*it)).collect();
+            (fields, false)
+        };
+        let mut struct_generator = StructGenerator::new(llvm, index, types_index);
         let ((_, initial_value), member_values) =
-            struct_generator.generate_struct_type(&members, data_type.get_name())?;
+            struct_generator.generate_struct_type(&fields, data_type.get_name(), packed)?;
         for (member, value) in member_values {
             let qualified_name = format!("{}.{}", data_type.get_name(), member);
             types_index.associate_initial_value(&qualified_name, value)?;
         }
         types_index.associate_initial_value(data_type.get_name(), initial_value)?;
+        for (field, struct_field) in fields.iter().enumerate() {
+            let field = field as u32;
+            match struct_field {
+                StructField::Member(member) => types_index.associate_member_location(
+                    member.get_qualified_name(),
+                    MemberLocation { field, bit: None },
+                )?,
+                StructField::Bits(members) => {
+                    for (bit, member) in members.iter().enumerate() {
+                        types_index.associate_member_location(
+                            member.get_qualified_name(),
+                            MemberLocation {
+                                field,
+                                bit: Some(bit as u32),
+                            },
+                        )?
+                    }
+                }
+                StructField::Padding(_) => {}
+            }
+        }
+    } else if let DataTypeInformation::Union { member_names, .. } = information {
+        generate_union_type(
+            llvm,
+            index,
+            target_data,
+            types_index,
+            data_type.get_name(),
+            member_names,
+        )?;
     }
     Ok(())
 }
 
+/// generates the body of an opaque union type the way a C compiler lays out a union:
+/// the member with the strictest alignment is followed by enough padding bytes to hold the
+/// largest member. The union starts zero-initialized.
+fn generate_union_type<'ink>(
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    target_data: &TargetData,
+    types_index: &mut LlvmTypedIndex<'ink>,
+    name: &str,
+    member_names: &[String],
+) -> Result<(), CompileError> {
+    let mut largest_size = 0;
+    let mut aligned_member: Option<(&str, u32, u32)> = None;
+    for member_name in member_names {
+        let member_type = index
+            .find_member(name, member_name)
+            .unwrap()
+            .get_type_name();
+        let (size, alignment) =
+            get_size_and_alignment(index, types_index, target_data, member_type)?;
+        largest_size = largest_size.max(size);
+        let is_stricter = aligned_member
+            .map(|(_, aligned_size, aligned)| {
+                alignment > aligned || (alignment == aligned && size > aligned_size)
+            })
+            .unwrap_or(true);
+        if is_stricter {
+            aligned_member = Some((member_type, size, alignment));
+        }
+    }
+
+    let mut body = vec![];
+    if let Some((member_type, size, alignment)) = aligned_member {
+        body.push(types_index.get_associated_type(member_type)?);
+        let padding = align_to(largest_size, alignment) - size;
+        if padding > 0 {
+            body.push(llvm.context.i8_type().array_type(padding).into());
+        }
+    }
+    let union_type = types_index.get_associated_type(name)?.into_struct_type();
+    union_type.set_body(&body, false);
+    types_index.associate_initial_value(name, union_type.const_zero().into())
+}
+
+/// returns the size and the alignment in bytes of the given type on the target as a C compiler would lay it out.
+/// llvm aligns its packed structs to single bytes, so their alignment is derived from their pack mode
+fn get_size_and_alignment(
+    index: &Index,
+    types_index: &LlvmTypedIndex,
+    target_data: &TargetData,
+    type_name: &str,
+) -> Result<(u32, u32), CompileError> {
+    let llvm_type = types_index.get_associated_type(type_name)?;
+    let size = target_data.get_abi_size(&llvm_type) as u32;
+    let alignment = match index.get_type_information(type_name)? {
+        DataTypeInformation::Struct {
+            name,
+            member_names,
+            pack_mode: Some(pack_mode),
+            ..
+        } => {
+            let members: Vec<&VariableIndexEntry> = member_names
+                .iter()
+                .filter_map(|member| index.find_member(&name, member))
+                .filter(|member| !member.is_return())
+                .collect();
+            let (_, _, alignment) =
+                create_packed_struct_fields(index, types_index, target_data, &members, pack_mode)?;
+            alignment
+        }
+        DataTypeInformation::Array {
+            inner_type_name, ..
+        } => get_size_and_alignment(index, types_index, target_data, &inner_type_name)?.1,
+        DataTypeInformation::SubRange {
+            referenced_type, ..
+        }
+        | DataTypeInformation::Alias {
+            referenced_type, ..
+        } => get_size_and_alignment(index, types_index, target_data, &referenced_type)?.1,
+        _ => target_data.get_abi_alignment(&llvm_type),
+    };
+    Ok((size, alignment))
+}
+
+/// lays out the members of a packed struct: every member is aligned to at most `pack_mode` bytes
+/// and consecutive BIT members share a byte. The padding between the members is added as explicit fields.
+///
+/// returns the struct's fields, its size and its alignment in bytes
+fn create_packed_struct_fields<'b>(
+    index: &Index,
+    types_index: &LlvmTypedIndex,
+    target_data: &TargetData,
+    members: &[&'b VariableIndexEntry],
+    pack_mode: u32,
+) -> Result<(Vec<StructField<'b>>, u32, u32), CompileError> {
+    let mut fields = Vec::new();
+    let mut size = 0;
+    let mut struct_alignment = 1;
+    for member in members {
+        let member_type = index.get_type_information(member.get_type_name())?;
+        let is_bit = index
+            .find_effective_type(&member_type)
+            .map_or(false, |it| it.get_name() == "BIT");
+        if is_bit {
+            match fields.last_mut() {
+                Some(StructField::Bits(bits)) if bits.len() < 8 => bits.push(*member),
+                _ => {
+                    fields.push(StructField::Bits(vec![*member]));
+                    size += 1;
+                }
+            }
+            continue;
+        }
+        let (member_size, alignment) =
+            get_size_and_alignment(index, types_index, target_data, member.get_type_name())?;
+        let alignment = alignment.min(pack_mode);
+        let padding = align_to(size, alignment) - size;
+        if padding > 0 {
+            fields.push(StructField::Padding(padding));
+        }
+        fields.push(StructField::Member(*member));
+        size += padding + member_size;
+        struct_alignment = struct_alignment.max(alignment);
+    }
+    let padding = align_to(size, struct_alignment) - size;
+    if padding > 0 {
+        fields.push(StructField::Padding(padding));
+    }
+    Ok((fields, size + padding, struct_alignment))
+}
+
+/// rounds the given size up to the next multiple of alignment
+fn align_to(size: u32, alignment: u32) -> u32 {
+    (size + alignment - 1) / alignment * alignment
+}
+
 /// Creates an llvm type to be associated with the given data type.
 /// Generates only an opaque type for structs.
 /// Eagerly generates but does not associate nested array and referenced aliased types
@@ -105,7 +411,9 @@ fn create_type<'ink>(
 ) -> Result<BasicTypeEnum<'ink>, CompileError> {
     let information = data_type.get_type_information();
     match information {
-        DataTypeInformation::Struct { .. } => types_index.get_associated_type(data_type.get_name()),
+        DataTypeInformation::Struct { .. } | DataTypeInformation::Union { .. } => {
+            types_index.get_associated_type(data_type.get_name())
+        }
         DataTypeInformation::Array {
             inner_type_name,
             dimensions,
@@ -171,6 +479,22 @@ fn create_type<'ink>(
             )?;
             Ok(inner_type.ptr_type(AddressSpace::Generic).into())
         }
+        DataTypeInformation::VariableLengthArray {
+            inner_type_name,
+            dimensions,
+            ..
+        } => {
+            let inner_type = create_type(
+                llvm,
+                index,
+                types_index,
+                inner_type_name,
+                index.get_type(inner_type_name)?,
+            )?;
+            Ok(llvm
+                .get_variable_length_array_type(inner_type, *dimensions)
+                .into())
+        }
     }
 }
 
@@ -183,6 +507,7 @@ fn generate_initial_value<'ink>(
     let information = data_type.get_type_information();
     match information {
         DataTypeInformation::Struct { .. } => None, //Done elsewhere
+        DataTypeInformation::Union { .. } => None,  //Done elsewhere
         DataTypeInformation::Array { .. } => generate_array_initializer(
             data_type,
             data_type.get_name(),
@@ -214,6 +539,7 @@ fn generate_initial_value<'ink>(
         // Void types are not basic type enums, so we return an int here
         DataTypeInformation::Void => None, //get_llvm_int_type(llvm.context, 32, "Void").map(Into::into),
         DataTypeInformation::Pointer { .. } => None,
+        DataTypeInformation::VariableLengthArray { .. } => None,
     }
 }
 
@@ -234,8 +560,7 @@ fn register_aliased_initial_value<'ink>(
     } else {
         // if there's no initializer defined for this alias, we go and check the aliased type for an initial value
         index
-            .get_types()
-            .get(referenced_type)
+            .find_type(referenced_type)
             .and_then(|referenced_data_type| {
                 generate_initial_value(index, types_index, llvm, referenced_data_type)
             })
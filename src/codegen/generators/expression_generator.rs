@@ -2,31 +2,137 @@
 use crate::{ast::SourceRange, index::Index};
 use inkwell::{
     basic_block::BasicBlock,
-    types::BasicTypeEnum,
+    builder::Builder,
+    types::{AnyType, BasicType, BasicTypeEnum, StructType},
     values::{
-        ArrayValue, BasicValue, BasicValueEnum, FloatValue, IntValue, PointerValue, StructValue,
-        VectorValue,
+        ArrayValue, BasicValue, BasicValueEnum, FloatValue, FunctionValue, IntValue, PointerValue,
+        StructValue, VectorValue,
     },
     AddressSpace, FloatPredicate, IntPredicate,
 };
 use std::collections::HashSet;
 
 use crate::{
-    ast::{flatten_expression_list, Dimension, Operator, Statement},
+    ast::{
+        evaluate_constant_int, flatten_expression_list, CallingConvention, Dimension, Operator,
+        Statement,
+    },
+    builtins::BuiltIn,
     codegen::{
-        llvm_index::LlvmTypedIndex,
+        llvm_index::{LlvmTypedIndex, MemberLocation},
         llvm_typesystem::{cast_if_needed, get_llvm_int_type, promote_if_needed},
         TypeAndPointer, TypeAndValue,
     },
     compile_error::CompileError,
-    index::VariableIndexEntry,
-    typesystem::{DataType, DataTypeInformation},
+    index::{ImplementationIndexEntry, VariableIndexEntry, VariableType},
+    typesystem::{
+        fits_into_int_type, get_bigger_type, is_in_type_class, DataType, DataTypeInformation,
+        DateAndTimeType, ALLOC_HOOK_FN, BOUNDS_CHECK_FN, DIV_CHECK_INT_FN, DIV_CHECK_LINT_FN,
+        DIV_CHECK_LREAL_FN, DIV_CHECK_REAL_FN, FREE_HOOK_FN,
+    },
 };
 
-use super::{llvm::Llvm, statement_generator::FunctionContext, struct_generator};
+use super::{
+    intrinsic_generator::{get_int_intrinsic_name, TRAP_INTRINSIC},
+    llvm::Llvm,
+    pou_generator::is_passed_by_reference,
+    statement_generator::FunctionContext,
+    struct_generator,
+};
 
 use chrono::{LocalResult, TimeZone, Utc};
 
+/// returns true if the given statement is a struct literal (`(x := 1, y := 2)`) or an array literal (`[1, 2, 3]`)
+pub fn is_aggregate_literal(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::LiteralArray { .. }
+            | Statement::ExpressionList { .. }
+            | Statement::Assignment { .. }
+    )
+}
+
+/// returns true if the given literal's elements may read variables (or call POUs), so they may
+/// read the struct or array the literal is assigned to
+fn reads_variables(literal: &Statement) -> bool {
+    match literal {
+        Statement::LiteralInteger { .. }
+        | Statement::LiteralDate { .. }
+        | Statement::LiteralDateAndTime { .. }
+        | Statement::LiteralTimeOfDay { .. }
+        | Statement::LiteralTime { .. }
+        | Statement::LiteralReal { .. }
+        | Statement::LiteralBool { .. }
+        | Statement::LiteralString { .. }
+        | Statement::EmptyStatement { .. } => false,
+        Statement::LiteralArray { elements, .. } => {
+            elements.as_deref().map_or(false, reads_variables)
+        }
+        Statement::ExpressionList { expressions } => expressions.iter().any(reads_variables),
+        Statement::Assignment { right, .. } => reads_variables(right),
+        Statement::MultipliedStatement { element, .. } => reads_variables(element),
+        Statement::UnaryExpression { value, .. } => reads_variables(value),
+        Statement::BinaryExpression { left, right, .. } => {
+            reads_variables(left) || reads_variables(right)
+        }
+        _ => true,
+    }
+}
+
+/// returns true if the given type is a POINTER TO another type. Pointers of VAR_IN_OUTs are
+/// dereferenced automatically and are not considered to be raw pointers
+fn is_raw_pointer(data_type: &DataTypeInformation) -> bool {
+    matches!(
+        data_type,
+        DataTypeInformation::Pointer {
+            auto_deref: false,
+            ..
+        }
+    )
+}
+
+/// returns the arguments passed to a call
+fn get_call_arguments(parameters: &Option<Statement>) -> Vec<&Statement> {
+    match parameters {
+        Some(Statement::ExpressionList { expressions }) => expressions.iter().collect(),
+        Some(argument) => vec![argument],
+        None => vec![],
+    }
+}
+
+/// returns true if the given parameter is passed by one of the given call arguments
+fn is_passed(arguments: &[&Statement], parameter: &VariableIndexEntry) -> bool {
+    arguments
+        .iter()
+        .enumerate()
+        .any(|(position, argument)| match argument {
+            Statement::Assignment { left, .. } | Statement::OutputAssignment { left, .. } => {
+                matches!(left.as_ref(), Statement::Reference { name, .. } if name.eq_ignore_ascii_case(parameter.get_name()))
+            }
+            _ => position as u32 == parameter.get_location_in_parent(),
+        })
+}
+
+/// prefixes the name of the variable referenced by the given (array- or pointer-access-) statement
+/// with the given qualifier (`x[2]` becomes `qualifier.x[2]`)
+fn qualify_reference(reference: &mut Statement, qualifier: &str) {
+    match reference {
+        Statement::Reference { name, .. } => *name = format!("{}.{}", qualifier, name),
+        Statement::ArrayAccess { reference, .. } | Statement::PointerAccess { reference, .. } => {
+            qualify_reference(reference, qualifier)
+        }
+        _ => {}
+    }
+}
+
+/// the check generated for an array access
+enum BoundsCheck<'b> {
+    /// the index is passed to the user's CheckBounds function which returns the index to access
+    Function(&'b ImplementationIndexEntry),
+    /// the program traps if the index is out of bounds
+    Trap,
+}
+
 /// the generator for expressions
 pub struct ExpressionCodeGenerator<'a, 'b> {
     llvm: &'b Llvm<'a>,
@@ -156,6 +262,10 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 let l_value = self.generate_element_pointer(expression)?;
                 Ok(self.llvm.load_pointer(&l_value, "load_tmpVar"))
             }
+            Statement::PointerAccess { .. } => {
+                let l_value = self.generate_element_pointer(expression)?;
+                Ok(self.llvm.load_pointer(&l_value, &self.temp_variable_prefix))
+            }
             Statement::BinaryExpression {
                 left,
                 right,
@@ -173,6 +283,41 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 let left_type_and_value = self.generate_expression(left)?;
                 let right_type_and_value = self.generate_expression(right)?;
 
+                if matches!(operator, Operator::Equal | Operator::NotEqual)
+                    && (self.is_aggregate_type(&left_type_and_value.0)
+                        || self.is_aggregate_type(&right_type_and_value.0))
+                {
+                    return self.generate_aggregate_comparison(
+                        operator,
+                        left_type_and_value,
+                        right_type_and_value,
+                        expression,
+                    );
+                }
+
+                if matches!(operator, Operator::Equal | Operator::NotEqual)
+                    && (is_raw_pointer(&left_type_and_value.0)
+                        || is_raw_pointer(&right_type_and_value.0))
+                {
+                    return self.generate_pointer_comparison(
+                        operator,
+                        left_type_and_value,
+                        right_type_and_value,
+                        expression,
+                    );
+                }
+
+                if left_type_and_value.0.is_date_or_time()
+                    || right_type_and_value.0.is_date_or_time()
+                {
+                    return self.generate_date_and_time_binary_expression(
+                        operator,
+                        left_type_and_value,
+                        right_type_and_value,
+                        expression,
+                    );
+                }
+
                 let (common_type, left_value, right_value) = promote_if_needed(
                     self.llvm.context,
                     builder,
@@ -182,13 +327,52 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                     self.llvm_index,
                 );
 
+                let right_value = match operator {
+                    Operator::Division | Operator::Modulo => {
+                        let divisor =
+                            self.generate_division_check(&common_type, right_value, right)?;
+                        self.generate_division_overflow_trap(
+                            &common_type,
+                            left_value,
+                            divisor,
+                            expression,
+                        )?;
+                        divisor
+                    }
+                    _ => right_value,
+                };
+
+                if let (
+                    Operator::Plus | Operator::Minus | Operator::Multiplication,
+                    Some(declared_type),
+                ) = (
+                    operator,
+                    self.get_narrower_declared_type(
+                        &common_type,
+                        (left, &left_type_and_value.0),
+                        (right, &right_type_and_value.0),
+                    ),
+                ) {
+                    if self.is_overflow_check_required(&declared_type) {
+                        return self.generate_overflow_checked_in_declared_type(
+                            operator,
+                            &declared_type,
+                            &common_type,
+                            left_value,
+                            right_value,
+                            expression,
+                        );
+                    }
+                }
+
                 if common_type.is_int() {
-                    Ok(self.create_llvm_int_binary_expression(
+                    self.create_llvm_int_binary_expression(
                         operator,
                         left_value,
                         right_value,
                         &common_type,
-                    ))
+                        expression,
+                    )
                 } else if common_type.is_float() {
                     Ok(self.create_llvm_float_binary_expression(
                         operator,
@@ -253,6 +437,24 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         parameters: &Option<Statement>,
     ) -> Result<TypeAndValue<'a>, CompileError> {
         let function_context = self.get_function_context(operator)?;
+        //builtins are generated inline, unless there is a callable POU with the same name
+        if let Statement::Reference { name, .. } = operator {
+            let builtin = BuiltIn::find(name).filter(|_| {
+                self.index.find_implementation(name).is_none()
+                    && self
+                        .index
+                        .find_callable_instance_variable(
+                            Some(function_context.linking_context.get_type_name()),
+                            &[name.clone()],
+                        )
+                        .is_none()
+            });
+            if let Some(builtin) = builtin {
+                return self.generate_builtin_call(builtin, operator, parameters);
+            }
+        }
+        //instances of functions are allocated for every call, their omitted inputs get their default values
+        let mut is_function_instance = false;
         let instance_and_index_entry = match operator {
             Statement::Reference { name, .. } => {
                 //Get associated Variable or generate a variable for the type with the same name
@@ -281,6 +483,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 } else {
                     let implementation = self.index.find_implementation(name);
                     if let Some(implementation) = implementation {
+                        is_function_instance = true;
                         (
                             implementation,
                             self.allocate_function_struct_instance(
@@ -306,21 +509,24 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
 
                 Ok((callable_reference, implementation))
             }
-            Statement::QualifiedReference { .. } => {
-                let loaded_value = self.generate_element_pointer_for_rec(None, operator);
-                loaded_value.map(
-                    |TypeAndPointer {
-                         type_entry,
-                         ptr_value,
-                     }| {
-                        Ok((
-                            ptr_value,
-                            self.index
-                                .find_implementation(type_entry.get_name())
-                                .unwrap(),
-                        ))
-                    },
-                )?
+            //calls to members (fb.member_fb()), array elements (fbs[i]()) or pointed-to (p^()) instances
+            Statement::QualifiedReference { .. }
+            | Statement::ArrayAccess { .. }
+            | Statement::PointerAccess { .. } => {
+                let TypeAndPointer {
+                    type_entry,
+                    ptr_value,
+                    ..
+                } = self.generate_element_pointer_for_rec(None, operator)?;
+                self.index
+                    .find_implementation(type_entry.get_name())
+                    .map(|implementation| (ptr_value, implementation))
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("{} is not callable", type_entry.get_name()),
+                            operator.get_location(),
+                        )
+                    })
             }
             _ => Err(CompileError::CodeGenError {
                 message: format!("cannot generate call statement for {:?}", operator),
@@ -330,6 +536,8 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
 
         let (instance, index_entry) = instance_and_index_entry?;
         let function_name = index_entry.get_call_name();
+        let calling_convention = index_entry.get_calling_convention();
+        self.validate_call_arguments(index_entry, operator, parameters, is_function_instance)?;
         //Create parameters for input and output blocks
         let current_f = function_context.function;
         let input_block = self.llvm.context.append_basic_block(current_f, "input");
@@ -340,8 +548,11 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         let builder = &self.llvm.builder;
         builder.build_unconditional_branch(input_block);
         builder.position_at_end(input_block);
+        if is_function_instance {
+            self.generate_default_inputs(function_name, instance, parameters)?;
+        }
         //Generate all parameters, this function may jump to the output block
-        let parameters = self.generate_function_parameters(
+        let mut parameters = self.generate_function_parameters(
             function_name,
             instance,
             parameters,
@@ -369,6 +580,25 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 ),
                 location: operator.get_location(),
             })?;
+        if calling_convention == CallingConvention::C {
+            //the instance only collects the arguments, they are passed to the function individually
+            let arguments = self.generate_native_arguments(function_name, instance, operator)?;
+            parameters.splice(0..1, arguments);
+        }
+        //structs, arrays and strings are returned into a buffer passed as the first parameter
+        let out_pointer = match self.index.find_return_type(function_name) {
+            Some(data_type)
+                if is_passed_by_reference(self.index, data_type.get_type_information()) =>
+            {
+                let buffer_type = self.llvm_index.get_associated_type(data_type.get_name())?;
+                let buffer = self
+                    .llvm
+                    .create_local_variable(&format!("{}_result", function_name), &buffer_type);
+                parameters.insert(0, buffer.into());
+                Some(buffer)
+            }
+            _ => None,
+        };
         //If the target is a function, declare the struct locally
         //Assign all parameters into the struct values
         let call_result = builder
@@ -378,6 +608,13 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         //Continue here after function call
         builder.position_at_end(continue_block);
 
+        if let Some(out_pointer) = out_pointer {
+            return Ok((
+                return_type.unwrap(),
+                builder.build_load(out_pointer, "call_result"),
+            ));
+        }
+
         // !! REVIEW !! we return an uninitialized int pointer for void methods :-/
         // dont touch it!!
         let value = call_result.either(Ok, |_| {
@@ -391,6 +628,52 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         Ok((return_type.unwrap(), value))
     }
 
+    /// loads the arguments of a call to a function with the C calling convention from the
+    /// struct-instance the call's parameters were assigned to
+    ///
+    /// VAR_INPUTs are passed as values (strings and arrays as pointers),
+    /// VAR_IN_OUTs and VAR_OUTPUTs are passed as pointers
+    fn generate_native_arguments(
+        &self,
+        function_name: &str,
+        parameter_struct: PointerValue<'a>,
+        context: &Statement,
+    ) -> Result<Vec<BasicValueEnum<'a>>, CompileError> {
+        let builder = &self.llvm.builder;
+        let mut arguments = vec![];
+        for member in self.index.find_local_members(function_name) {
+            let name = member.get_name();
+            let pointer_to_member = || {
+                builder
+                    .build_struct_gep(parameter_struct, member.get_location_in_parent(), name)
+                    .map_err(|_| {
+                        CompileError::codegen_error(
+                            format!("Cannot access parameter {} of {}", name, function_name),
+                            context.get_location(),
+                        )
+                    })
+            };
+            let argument = match member.get_variable_type() {
+                VariableType::Input => {
+                    let data_type = self.index.get_type_information(member.get_type_name())?;
+                    if is_passed_by_reference(self.index, &data_type) {
+                        pointer_to_member()?.as_basic_value_enum()
+                    } else {
+                        builder.build_load(pointer_to_member()?, &format!("load_{}", name))
+                    }
+                }
+                //the member of a VAR_IN_OUT already holds a pointer
+                VariableType::InOut => {
+                    builder.build_load(pointer_to_member()?, &format!("load_{}", name))
+                }
+                VariableType::Output => pointer_to_member()?.as_basic_value_enum(),
+                _ => continue,
+            };
+            arguments.push(argument);
+        }
+        Ok(arguments)
+    }
+
     /// generates a new instance of a function called `function_name` and returns a PointerValue to it
     ///
     /// - `function_name` the name of the function as registered in the index
@@ -413,6 +696,122 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             .create_local_variable(&instance_name, &function_type))
     }
 
+    /// reports calls that mix formal (`x := 1`, `y => z`) and non-formal arguments, calls that
+    /// pass more arguments than the callable declares parameters and calls that omit a constant
+    /// input which has no initial value to point to
+    fn validate_call_arguments(
+        &self,
+        implementation: &ImplementationIndexEntry,
+        operator: &Statement,
+        parameters: &Option<Statement>,
+        is_function: bool,
+    ) -> Result<(), CompileError> {
+        let arguments = get_call_arguments(parameters);
+        let is_formal = |argument: &Statement| {
+            matches!(
+                argument,
+                Statement::Assignment { .. } | Statement::OutputAssignment { .. }
+            )
+        };
+        if let Some(first) = arguments.first() {
+            let formal = is_formal(first);
+            if let Some(mixed) = arguments.iter().find(|it| is_formal(it) != formal) {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "Cannot mix formal and non-formal arguments in call to {}",
+                        implementation.get_call_name()
+                    ),
+                    mixed.get_location(),
+                ));
+            }
+        }
+
+        let type_name = implementation.get_type_name();
+        let is_variadic = self
+            .index
+            .find_type_information(type_name)
+            .map(|it| it.is_variadic())
+            .unwrap_or(false);
+        let declared_parameters = self
+            .index
+            .find_local_members(type_name)
+            .iter()
+            .filter(|it| {
+                matches!(
+                    it.get_variable_type(),
+                    VariableType::Input | VariableType::InOut | VariableType::Output
+                )
+            })
+            .count();
+        if !is_variadic && arguments.len() > declared_parameters {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Too many arguments in call to {}, expected at most {} but found {}",
+                    implementation.get_call_name(),
+                    declared_parameters,
+                    arguments.len()
+                ),
+                arguments[declared_parameters].get_location(),
+            ));
+        }
+
+        //function blocks and programs keep their inputs between calls, so a constant input
+        //would still point to the value of a previous call
+        let missing_input = self
+            .index
+            .find_local_members(type_name)
+            .into_iter()
+            .filter(|it| it.is_constant() && !is_passed(&arguments, it))
+            .find(|it| !is_function || it.initial_value.is_none());
+        if let Some(input) = missing_input {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Missing argument for the constant input {} in call to {}",
+                    input.get_name(),
+                    implementation.get_call_name()
+                ),
+                operator.get_location(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// assigns the initial values of all VAR_INPUTs that are not passed by the call to the
+    /// function's parameter-struct
+    ///
+    /// - `function_name` the name of the function we're calling
+    /// - `parameter_struct` a pointer to the function's freshly allocated struct-instance
+    /// - `parameters` the call's arguments
+    fn generate_default_inputs(
+        &self,
+        function_name: &str,
+        parameter_struct: PointerValue<'a>,
+        parameters: &Option<Statement>,
+    ) -> Result<(), CompileError> {
+        let arguments = get_call_arguments(parameters);
+        let defaults = self
+            .index
+            .find_local_members(function_name)
+            .into_iter()
+            .filter(|it| it.get_variable_type() == VariableType::Input)
+            .filter(|it| !is_passed(&arguments, it));
+        for input in defaults {
+            if let Some(initial_value) = &input.initial_value {
+                self.generate_nameless_parameter(
+                    &ParameterContext {
+                        assignment_statement: initial_value,
+                        function_name,
+                        parameter_type: None,
+                        index: input.get_location_in_parent(),
+                        parameter_struct,
+                    },
+                    initial_value,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// generates the assignments of a function-call's parameters
     /// the call parameters are passed to the function using a struct-instance with all the parameters
     ///
@@ -489,13 +888,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         let parameter_value = match assignment_statement {
             // explicit call parameter: foo(param := value)
             Statement::Assignment { left, right } => {
-                self.generate_formal_parameter(
-                    param_context,
-                    left,
-                    right,
-                    input_block,
-                    output_block,
-                )?;
+                self.generate_formal_parameter(param_context, left, right, input_block)?;
                 None
             }
             // foo (param => value)
@@ -527,27 +920,65 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             let parameter = parameter_type
                 .or_else(|| {
                     self.index
-                        .find_input_parameter(function_name, index as u32)
+                        .find_positional_parameter(function_name, index as u32)
                         .and_then(|var| self.index.find_type(var.get_type_name()))
                 })
                 .map(|var| var.get_type_information())
                 .unwrap();
+            if let DataTypeInformation::VariableLengthArray {
+                inner_type_name,
+                dimensions,
+                ..
+            } = parameter
+            {
+                self.generate_variable_length_array_argument(
+                    pointer_to_param,
+                    inner_type_name,
+                    *dimensions,
+                    assignment_statement,
+                )?;
+                return Ok(None);
+            }
+            if self.is_aggregate_type(parameter) && is_aggregate_literal(assignment_statement) {
+                self.generate_aggregate_literal(pointer_to_param, parameter, assignment_statement)?;
+                return Ok(None);
+            }
+            let is_constant_input = self
+                .index
+                .find_input_parameter(function_name, index as u32)
+                .map_or(false, VariableIndexEntry::is_constant);
             let (value_type, generated_exp) = if let DataTypeInformation::Pointer {
                 auto_deref: true,
+                inner_type_name,
                 ..
             } = parameter
             {
-                //this is VAR_IN_OUT assignemt, so don't load the value, assign the pointer
-                self.generate_element_pointer_for_rec(None, assignment_statement)
-                    //get a pointer for that variable
-                    .and_then(|tp| self.auto_deref_if_necessary(tp.type_entry, tp.ptr_value))
-                    // auto-deref, if it is a var_in_out itself
-                    .map(|v| {
-                        (
-                            v.type_entry.get_type_information().clone(),
-                            v.ptr_value.as_basic_value_enum(),
-                        )
-                    })?
+                if is_constant_input {
+                    self.generate_constant_argument(inner_type_name, assignment_statement)?
+                } else {
+                    //this is VAR_IN_OUT assignemt, so don't load the value, assign the pointer
+                    self.validate_writable(assignment_statement)?;
+                    self.generate_element_pointer_for_rec(None, assignment_statement)
+                        //get a pointer for that variable
+                        .and_then(|tp| {
+                            if tp.is_bit_field() {
+                                Err(CompileError::codegen_error(
+                                    "Cannot pass a BIT member of a packed struct by reference"
+                                        .to_string(),
+                                    assignment_statement.get_location(),
+                                ))
+                            } else {
+                                self.auto_deref_if_necessary(tp.type_entry, tp.ptr_value)
+                            }
+                        })
+                        // auto-deref, if it is a var_in_out itself
+                        .map(|v| {
+                            (
+                                v.type_entry.get_type_information().clone(),
+                                v.ptr_value.as_basic_value_enum(),
+                            )
+                        })?
+                }
             } else {
                 self.generate_expression(assignment_statement)?
             };
@@ -567,6 +998,95 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         }
     }
 
+    /// generates the pointer passed to a constant input. Variables of the input's type are passed
+    /// by reference, all other arguments are copied into a temporary variable first
+    ///
+    /// - `input_type_name` the declared type of the constant input
+    /// - `argument` the passed argument
+    fn generate_constant_argument(
+        &self,
+        input_type_name: &str,
+        argument: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let input_type = self.index.get_type(input_type_name)?.get_type_information();
+        let llvm_type = self.llvm_index.get_associated_type(input_type_name)?;
+        if matches!(
+            argument,
+            Statement::Reference { .. }
+                | Statement::QualifiedReference { .. }
+                | Statement::ArrayAccess { .. }
+                | Statement::PointerAccess { .. }
+        ) {
+            let variable = self.generate_element_pointer_for_rec(None, argument)?;
+            if !variable.is_bit_field() {
+                let variable =
+                    self.auto_deref_if_necessary(variable.type_entry, variable.ptr_value)?;
+                if variable.ptr_value.get_type().get_element_type() == llvm_type.as_any_type_enum()
+                {
+                    return Ok((input_type.clone(), variable.ptr_value.as_basic_value_enum()));
+                }
+            }
+        }
+
+        let temporary = self.llvm.create_local_variable("", &llvm_type);
+        if self.is_aggregate_type(input_type) && is_aggregate_literal(argument) {
+            self.generate_aggregate_literal_into(temporary, input_type, argument)?;
+        } else {
+            let (value_type, value) = self.generate_expression(argument)?;
+            if self.is_aggregate_type(input_type)
+                && !self.is_same_aggregate_type(input_type, &value_type)
+            {
+                return Err(CompileError::casting_error(
+                    value_type.get_name(),
+                    input_type.get_name(),
+                    argument.get_location(),
+                ));
+            }
+            let value = cast_if_needed(
+                self.llvm,
+                self.index,
+                input_type,
+                value,
+                &value_type,
+                argument,
+            )?;
+            self.llvm.builder.build_store(temporary, value);
+        }
+        Ok((input_type.clone(), temporary.as_basic_value_enum()))
+    }
+
+    /// reports writes to a constant input of the current POU. Constant inputs are passed by
+    /// reference, so they must neither be assigned nor passed on to a VAR_IN_OUT
+    ///
+    /// - `target` the written reference
+    pub fn validate_writable(&self, target: &Statement) -> Result<(), CompileError> {
+        match target {
+            Statement::Reference { name, location, .. } => {
+                let is_constant_input = self
+                    .function_context
+                    .and_then(|it| {
+                        self.index
+                            .find_member(it.linking_context.get_type_name(), name)
+                    })
+                    .map_or(false, VariableIndexEntry::is_constant);
+                if is_constant_input {
+                    Err(CompileError::codegen_error(
+                        format!("Cannot assign to the constant input {}", name),
+                        location.clone(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            Statement::QualifiedReference { elements, .. } => elements
+                .first()
+                .map_or(Ok(()), |first| self.validate_writable(first)),
+            Statement::ArrayAccess { reference, .. } => self.validate_writable(reference),
+            //writing through a pointer does not change the pointer itself
+            _ => Ok(()),
+        }
+    }
+
     fn generate_output_parameter(
         &self,
         param_context: &ParameterContext,
@@ -597,6 +1117,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 .build_struct_gep(parameter_struct, index as u32, "")
                 .unwrap();
 
+            self.validate_writable(right)?;
             let l_value = self.generate_element_pointer_for_rec(None, right)?;
             let loaded_value = builder.build_load(pointer_to_param, parameter.get_name());
             let value = cast_if_needed(
@@ -607,7 +1128,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 param_type,
                 right,
             )?;
-            builder.build_store(l_value.ptr_value, value);
+            self.llvm.store_pointer(&l_value, value);
         }
         builder.position_at_end(current_block);
         Ok(())
@@ -619,7 +1140,6 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         left: &Statement,
         right: &Statement,
         input_block: &BasicBlock,
-        output_block: &BasicBlock,
     ) -> Result<(), CompileError> {
         let builder = &self.llvm.builder;
         let function_name = param_context.function_name;
@@ -629,7 +1149,8 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             let parameter = self.index.find_member(function_name, &name).unwrap();
             let index = parameter.get_location_in_parent();
             let param_type = self.index.find_type(parameter.get_type_name());
-            self.generate_single_parameter(
+            //the right side is the passed value, even if it is a struct literal with a single assignment
+            self.generate_nameless_parameter(
                 &ParameterContext {
                     assignment_statement: right,
                     function_name,
@@ -637,8 +1158,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                     index,
                     parameter_struct,
                 },
-                input_block,
-                output_block,
+                right,
             )?;
         };
         Ok(())
@@ -659,7 +1179,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             Statement::ArrayAccess { reference, access } => {
                 self.generate_element_pointer_for_array(None, reference, access)
             }
-            Statement::QualifiedReference { .. } => {
+            Statement::QualifiedReference { .. } | Statement::PointerAccess { .. } => {
                 self.generate_element_pointer_for_rec(None, reference_statement)
             }
             _ => Err(CompileError::codegen_error(
@@ -668,7 +1188,124 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             )),
         };
 
-        result.and_then(|it| self.auto_deref_if_necessary(it.type_entry, it.ptr_value))
+        result.and_then(|it| {
+            if it.is_bit_field() {
+                //a bit field is never a VAR_IN_OUT pointer
+                Ok(it)
+            } else {
+                self.auto_deref_if_necessary(it.type_entry, it.ptr_value)
+            }
+        })
+    }
+
+    /// generates `left REF= right` which binds the REFERENCE `left` to the variable `right`,
+    /// `left REF= 0` unbinds it
+    ///
+    /// - `left` the bound reference
+    /// - `right` the variable the reference is bound to
+    pub fn generate_ref_assignment(
+        &self,
+        left: &Statement,
+        right: &Statement,
+    ) -> Result<(), CompileError> {
+        //parameters passed by reference are bound by the caller
+        if let Statement::Reference { name, location } = left {
+            let is_parameter = self
+                .function_context
+                .and_then(|it| {
+                    self.index
+                        .find_member(it.linking_context.get_type_name(), name)
+                })
+                .map_or(false, |it| {
+                    it.get_variable_type() == VariableType::InOut || it.is_constant()
+                });
+            if is_parameter {
+                return Err(CompileError::codegen_error(
+                    format!("Cannot bind the parameter {} with REF=", name),
+                    location.clone(),
+                ));
+            }
+        }
+        let reference = self.generate_reference_lvalue(left)?;
+        let target_type = reference
+            .ptr_value
+            .get_type()
+            .get_element_type()
+            .into_pointer_type();
+        let target = match right {
+            Statement::LiteralInteger { value, .. } if value == "0" => target_type.const_null(),
+            _ => {
+                //a bound reference could be used to write to a read-only input
+                self.validate_writable(right)?;
+                //a reference on the right side is bound to the same variable
+                let variable = self.generate_element_pointer(right)?;
+                if variable.is_bit_field() {
+                    return Err(CompileError::codegen_error(
+                        "Cannot bind a REFERENCE to a BIT member of a packed struct".to_string(),
+                        right.get_location(),
+                    ));
+                }
+                if variable.ptr_value.get_type() != target_type {
+                    let inner_type_name = match reference.get_type_information() {
+                        DataTypeInformation::Pointer {
+                            inner_type_name, ..
+                        } => inner_type_name.as_str(),
+                        data_type => data_type.get_name(),
+                    };
+                    return Err(CompileError::casting_error(
+                        variable.get_type_information().get_name(),
+                        inner_type_name,
+                        right.get_location(),
+                    ));
+                }
+                variable.ptr_value
+            }
+        };
+        self.llvm.builder.build_store(reference.ptr_value, target);
+        Ok(())
+    }
+
+    /// returns the pointer to the given REFERENCE variable itself instead of the pointer to the
+    /// variable it is bound to
+    ///
+    /// - `reference` the reference variable
+    fn generate_reference_lvalue(
+        &self,
+        reference: &Statement,
+    ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
+        let lvalue = match reference {
+            Statement::Reference { .. }
+            | Statement::QualifiedReference { .. }
+            | Statement::ArrayAccess { .. }
+            | Statement::PointerAccess { .. } => {
+                self.generate_element_pointer_for_rec(None, reference)?
+            }
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("Expected a REFERENCE but found {:?}", reference),
+                    reference.get_location(),
+                ))
+            }
+        };
+        match lvalue.get_type_information() {
+            DataTypeInformation::Pointer {
+                auto_deref: true, ..
+            } if !lvalue.is_bit_field() => Ok(lvalue),
+            data_type => Err(CompileError::codegen_error(
+                format!("Expected a REFERENCE but found {}", data_type.get_name()),
+                reference.get_location(),
+            )),
+        }
+    }
+
+    /// returns the position of the given member in the llvm struct type of its container
+    fn get_member_location(&self, member: &VariableIndexEntry) -> MemberLocation {
+        self.llvm_index
+            .find_member_location(member.get_qualified_name())
+            .unwrap_or(MemberLocation {
+                field: member.get_location_in_parent(),
+                bit: None,
+            })
     }
 
     /// geneartes a gep for the given reference with an optional qualifier
@@ -686,28 +1323,50 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         let offset = &context.get_location();
         let l_value = if let Some(l_value) = qualifier {
             let qualifier_name = l_value.type_entry.get_name();
-            let member = self.index.find_member(l_value.type_entry.get_name(), name);
-            let member_location =
-                member
-                    .map(|it| it.get_location_in_parent())
-                    .ok_or_else(|| {
-                        CompileError::invalid_reference(
-                            &format!("{:}.{:}", qualifier_name, name),
-                            offset.clone(),
-                        )
-                    })?;
+            let member = self
+                .index
+                .find_member(l_value.type_entry.get_name(), name)
+                .ok_or_else(|| {
+                    CompileError::invalid_reference(
+                        &format!("{:}.{:}", qualifier_name, name),
+                        offset.clone(),
+                    )
+                })?;
+            //a constant input points to the argument of the current call, it is invalid outside of it
+            if member.is_constant() && !self.is_inside_of(qualifier_name) {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "The constant input {}.{} can only be accessed inside of {}",
+                        qualifier_name, name, qualifier_name
+                    ),
+                    offset.clone(),
+                ));
+            }
 
-            //.unwrap();
-            let member_data_type = member.map(|it| it.get_type_name()).unwrap();
+            let member_data_type = member.get_type_name();
             let member_type = self.index.get_type(member_data_type)?;
-            let gep = self.llvm.get_member_pointer_from_struct(
-                l_value.ptr_value,
-                member_location,
-                name,
-                offset,
-            )?;
-
-            TypeAndPointer::new(member_type, gep)
+            if let DataTypeInformation::Union { .. } = l_value.type_entry.get_type_information() {
+                //all members of a union start at the union's address
+                let member_llvm_type = self.llvm_index.get_associated_type(member_data_type)?;
+                let pointer = self.llvm.builder.build_pointer_cast(
+                    l_value.ptr_value,
+                    member_llvm_type.ptr_type(AddressSpace::Generic),
+                    name,
+                );
+                TypeAndPointer::new(member_type, pointer)
+            } else {
+                let member_location = self.get_member_location(member);
+                let gep = self.llvm.get_member_pointer_from_struct(
+                    l_value.ptr_value,
+                    member_location.field,
+                    name,
+                    offset,
+                )?;
+                match member_location.bit {
+                    Some(bit) => TypeAndPointer::new_bit_field(member_type, gep, bit),
+                    None => TypeAndPointer::new(member_type, gep),
+                }
+            }
         } else {
             //no context
 
@@ -743,6 +1402,15 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         Ok(l_value)
     }
 
+    /// returns true if the code of the POU with the given name is currently generated
+    fn is_inside_of(&self, pou_name: &str) -> bool {
+        self.function_context.map_or(false, |it| {
+            it.linking_context
+                .get_type_name()
+                .eq_ignore_ascii_case(pou_name)
+        })
+    }
+
     /// automatically derefs an inout variable pointer so it can be used like a normal variable
     ///
     /// # Arguments
@@ -783,24 +1451,275 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         access_expression: &Statement,
     ) -> Result<IntValue<'a>, CompileError> {
         let start_offset = dimension.start_offset;
-        let (_, access_value) = self.generate_expression(access_expression)?;
+        let (access_type, access_value) = self.generate_expression(access_expression)?;
+        let access_value = if let Some(check) = self.find_bounds_check() {
+            let i32_type = self.llvm.i32_type();
+            self.generate_bounds_check(
+                check,
+                (access_type, access_value),
+                i32_type.const_int(start_offset as u64, true),
+                i32_type.const_int(dimension.end_offset as u64, true),
+                access_expression,
+            )?
+        } else {
+            access_value.into_int_value()
+        };
         //If start offset is not 0, adjust the current statement with an add operation
         if start_offset != 0 {
             Ok(self.llvm.builder.build_int_sub(
-                access_value.into_int_value(),
+                access_value,
                 self.llvm.i32_type().const_int(start_offset as u64, true),
                 "",
             ))
         } else {
-            Ok(access_value.into_int_value())
+            Ok(access_value)
         }
     }
 
-    /// generates a gep statement for a array-reference with an optional qualifier
+    /// returns the check to generate for array accesses or None if array accesses are not checked.
+    /// A CheckBounds function declared by the user takes precedence over the trapping checks
+    fn find_bounds_check(&self) -> Option<BoundsCheck<'b>> {
+        let function_context = self.function_context?;
+        //don't check the accesses inside the check function itself
+        if function_context
+            .linking_context
+            .get_call_name()
+            .eq_ignore_ascii_case(BOUNDS_CHECK_FN)
+        {
+            return None;
+        }
+        if let Some(implementation) = self.index.find_implementation(BOUNDS_CHECK_FN) {
+            Some(BoundsCheck::Function(implementation))
+        } else if function_context.options.bounds_checks {
+            Some(BoundsCheck::Trap)
+        } else {
+            None
+        }
+    }
+
+    /// generates the given check for an array access and returns the index to access as a DINT
     ///
-    /// - `qualifier` an optional qualifier for a reference (e.g. myStruct.x[2] where myStruct is the qualifier for x)
-    /// - `reference` the reference-statement pointing to the array
-    /// - `access` the accessor expression (the expression between the brackets: reference[access])
+    /// - `check` the check to generate
+    /// - `access` the type and value of the generated access-expression
+    /// - `lower` the lower bound of the accessed dimension
+    /// - `upper` the upper bound of the accessed dimension
+    /// - `access_expression` the expression inside the array-statement
+    fn generate_bounds_check(
+        &self,
+        check: BoundsCheck,
+        access: TypeAndValue<'a>,
+        lower: IntValue<'a>,
+        upper: IntValue<'a>,
+        access_expression: &Statement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let dint = self.index.get_type_information("DINT")?;
+        let (access_type, access_value) = access;
+        let access_value = cast_if_needed(
+            self.llvm,
+            self.index,
+            &dint,
+            access_value,
+            &access_type,
+            access_expression,
+        )?
+        .into_int_value();
+
+        match check {
+            BoundsCheck::Function(implementation) => {
+                let function_name = implementation.get_call_name();
+                let (result_type, result) = self
+                    .generate_call_with_values(
+                        function_name,
+                        &[
+                            (dint.clone(), access_value.into()),
+                            (dint.clone(), lower.into()),
+                            (dint.clone(), upper.into()),
+                        ],
+                        access_expression,
+                    )?
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("{} must return the index to access", function_name),
+                            access_expression.get_location(),
+                        )
+                    })?;
+                Ok(cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    &dint,
+                    result,
+                    &result_type,
+                    access_expression,
+                )?
+                .into_int_value())
+            }
+            BoundsCheck::Trap => {
+                let out_of_bounds = builder.build_or(
+                    builder.build_int_compare(IntPredicate::SLT, access_value, lower, ""),
+                    builder.build_int_compare(IntPredicate::SGT, access_value, upper, ""),
+                    "",
+                );
+                self.generate_trap_if(
+                    out_of_bounds,
+                    "out_of_bounds",
+                    "in_bounds",
+                    access_expression,
+                )?;
+                Ok(access_value)
+            }
+        }
+    }
+
+    /// calls the given function passing the given values as its VAR_INPUTs in the order of their declaration.
+    /// Returns the function's result or None if the function does not return a value
+    ///
+    /// - `function_name` the name of the function to call
+    /// - `arguments` the types and values passed to the function's inputs
+    /// - `context` the statement used to report a possible CompileError on
+    fn generate_call_with_values(
+        &self,
+        function_name: &str,
+        arguments: &[TypeAndValue<'a>],
+        context: &Statement,
+    ) -> Result<Option<TypeAndValue<'a>>, CompileError> {
+        let builder = &self.llvm.builder;
+        let function = self
+            .llvm_index
+            .find_associated_implementation(function_name)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "No callable implementation associated to {:?}",
+                        function_name
+                    ),
+                    context.get_location(),
+                )
+            })?;
+        let instance = self.allocate_function_struct_instance(function_name, context)?;
+        for (position, (argument_type, argument)) in arguments.iter().enumerate() {
+            let parameter = self
+                .index
+                .find_input_parameter(function_name, position as u32)
+                .ok_or_else(|| {
+                    CompileError::codegen_error(
+                        format!("{} must declare {} inputs", function_name, arguments.len()),
+                        context.get_location(),
+                    )
+                })?;
+            let parameter_type = self.index.get_type_information(parameter.get_type_name())?;
+            let value = cast_if_needed(
+                self.llvm,
+                self.index,
+                &parameter_type,
+                *argument,
+                argument_type,
+                context,
+            )?;
+            let pointer = self.llvm.get_member_pointer_from_struct(
+                instance,
+                parameter.get_location_in_parent(),
+                "",
+                &context.get_location(),
+            )?;
+            builder.build_store(pointer, value);
+        }
+        let result = builder
+            .build_call(function, &[instance.into()], "call")
+            .try_as_basic_value()
+            .left();
+        Ok(result.and_then(|result| {
+            self.index
+                .find_return_type(function_name)
+                .map(|return_type| (return_type.get_type_information().clone(), result))
+        }))
+    }
+
+    /// generates a branch to a block that aborts the program if the given condition is true.
+    /// If a trap handler is configured, it is called with the start- and end-offset of the
+    /// failing statement before the program is aborted.
+    /// Continues generating code into a new block that is reached if the condition is false
+    ///
+    /// - `condition` the condition that causes the program to abort
+    /// - `trap_block_name` the name of the block that aborts the program
+    /// - `continue_block_name` the name of the block to continue with
+    /// - `context` the failing statement
+    fn generate_trap_if(
+        &self,
+        condition: IntValue<'a>,
+        trap_block_name: &str,
+        continue_block_name: &str,
+        context: &Statement,
+    ) -> Result<(), CompileError> {
+        let builder = &self.llvm.builder;
+        let function_context = self.function_context.ok_or_else(|| {
+            CompileError::codegen_error(
+                "Cannot generate a runtime check outside of a POU".into(),
+                context.get_location(),
+            )
+        })?;
+        let trap = self
+            .llvm_index
+            .find_associated_implementation(TRAP_INTRINSIC)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Missing declaration of {}", TRAP_INTRINSIC),
+                    context.get_location(),
+                )
+            })?;
+        let trap_block = self
+            .llvm
+            .context
+            .append_basic_block(function_context.function, trap_block_name);
+        let continue_block = self
+            .llvm
+            .context
+            .append_basic_block(function_context.function, continue_block_name);
+        builder.build_conditional_branch(condition, trap_block, continue_block);
+
+        builder.position_at_end(trap_block);
+        if let Some(trap_handler) = &function_context.options.trap_handler {
+            if self.index.find_implementation(trap_handler).is_none() {
+                return Err(CompileError::codegen_error(
+                    format!("Unknown trap handler {}", trap_handler),
+                    context.get_location(),
+                ));
+            }
+            let dint = self.index.get_type_information("DINT")?;
+            let location = context.get_location();
+            self.generate_call_with_values(
+                trap_handler,
+                &[
+                    (
+                        dint.clone(),
+                        self.llvm
+                            .i32_type()
+                            .const_int(location.get_start() as u64, false)
+                            .into(),
+                    ),
+                    (
+                        dint,
+                        self.llvm
+                            .i32_type()
+                            .const_int(location.get_end() as u64, false)
+                            .into(),
+                    ),
+                ],
+                context,
+            )?;
+        }
+        builder.build_call(trap, &[], "");
+        builder.build_unreachable();
+
+        builder.position_at_end(continue_block);
+        Ok(())
+    }
+
+    /// generates a gep statement for a array-reference with an optional qualifier
+    ///
+    /// - `qualifier` an optional qualifier for a reference (e.g. myStruct.x[2] where myStruct is the qualifier for x)
+    /// - `reference` the reference-statement pointing to the array
+    /// - `access` the accessor expression (the expression between the brackets: reference[access])
     fn generate_element_pointer_for_array(
         &self,
         qualifier: Option<&TypeAndPointer<'a, '_>>,
@@ -809,6 +1728,14 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
     ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
         //Load the reference
         self.generate_element_pointer_for_rec(qualifier, reference)
+            //an array passed by reference (e.g. a VAR_IN_OUT) points to its value
+            .and_then(|lvalue| {
+                if lvalue.is_bit_field() {
+                    Ok(lvalue)
+                } else {
+                    self.auto_deref_if_necessary(lvalue.type_entry, lvalue.ptr_value)
+                }
+            })
             .and_then(|lvalue| {
                 if let DataTypeInformation::Array {
                     inner_type_name,
@@ -844,6 +1771,46 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                     let internal_type = self.index.get_type(inner_type_name)?; //TODO this is WRONG!!! typename is not correct
                     return Ok(TypeAndPointer::new(internal_type, pointer));
                 }
+                if let DataTypeInformation::VariableLengthArray {
+                    inner_type_name,
+                    dimensions,
+                    ..
+                } = lvalue.get_type_information()
+                {
+                    return self.generate_element_pointer_for_variable_length_array(
+                        lvalue.ptr_value,
+                        inner_type_name,
+                        *dimensions,
+                        access,
+                    );
+                }
+                if let DataTypeInformation::Pointer {
+                    inner_type_name,
+                    auto_deref: false,
+                    ..
+                } = lvalue.get_type_information()
+                {
+                    //a pointer is indexed like an ARRAY[0..] of the type it points to
+                    let lint = self.index.get_type_information("LINT")?;
+                    let (access_type, access_value) = self.generate_expression(access)?;
+                    let access_value = cast_if_needed(
+                        self.llvm,
+                        self.index,
+                        &lint,
+                        access_value,
+                        &access_type,
+                        access,
+                    )?
+                    .into_int_value();
+                    let (_, pointer) = self.llvm.load_pointer(&lvalue, "deref");
+                    let element = self.llvm.load_array_element(
+                        pointer.into_pointer_value(),
+                        &[access_value],
+                        "tmpVar",
+                    )?;
+                    let element_type = self.index.get_type(inner_type_name)?;
+                    return Ok(TypeAndPointer::new(element_type, element));
+                }
                 Err(CompileError::codegen_error(
                     "Invalid array access".to_string(),
                     access.get_location(),
@@ -851,6 +1818,194 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             })
     }
 
+    /// generates a gep statement for an element of a variable-length array. The elements
+    /// are stored row-major, so the offset is calculated from the bounds stored in the fat pointer
+    ///
+    /// - `fat_pointer` a pointer to the array's fat pointer
+    /// - `inner_type_name` the name of the array's element type
+    /// - `dimensions` the number of the array's dimensions
+    /// - `access` the accessor expression (the expression between the brackets: reference[access])
+    fn generate_element_pointer_for_variable_length_array(
+        &self,
+        fat_pointer: PointerValue<'a>,
+        inner_type_name: &str,
+        dimensions: u32,
+        access: &Statement,
+    ) -> Result<TypeAndPointer<'a, 'b>, CompileError> {
+        let statements = access.get_as_list();
+        if statements.len() != dimensions as usize {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Mismatched array access : {} -> {} ",
+                    statements.len(),
+                    dimensions
+                ),
+                access.get_location(),
+            ));
+        }
+        let builder = &self.llvm.builder;
+        let dint = self.index.get_type_information("DINT")?;
+        let mut offset: Option<IntValue<'a>> = None;
+        for (dimension, statement) in statements.iter().enumerate() {
+            let lower_bound =
+                self.load_variable_length_array_bound(fat_pointer, dimension as u32, false);
+            let (access_type, access_value) = self.generate_expression(statement)?;
+            let access_value = if let Some(check) = self.find_bounds_check() {
+                let upper_bound =
+                    self.load_variable_length_array_bound(fat_pointer, dimension as u32, true);
+                self.generate_bounds_check(
+                    check,
+                    (access_type, access_value),
+                    lower_bound,
+                    upper_bound,
+                    statement,
+                )?
+            } else {
+                cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    &dint,
+                    access_value,
+                    &access_type,
+                    statement,
+                )?
+                .into_int_value()
+            };
+            let index_in_dimension = builder.build_int_sub(access_value, lower_bound, "");
+            offset = Some(if let Some(offset) = offset {
+                let upper_bound =
+                    self.load_variable_length_array_bound(fat_pointer, dimension as u32, true);
+                let length = builder.build_int_add(
+                    builder.build_int_sub(upper_bound, lower_bound, ""),
+                    self.llvm.i32_type().const_int(1, false),
+                    "",
+                );
+                builder.build_int_add(
+                    builder.build_int_mul(offset, length, ""),
+                    index_in_dimension,
+                    "",
+                )
+            } else {
+                index_in_dimension
+            });
+        }
+
+        let data_pointer =
+            self.llvm
+                .get_member_pointer_from_struct(fat_pointer, 0, "", &access.get_location())?;
+        let data = builder.build_load(data_pointer, "").into_pointer_value();
+        let pointer = self
+            .llvm
+            .load_array_element(data, &[offset.unwrap()], "tmpVar")?;
+        Ok(TypeAndPointer::new(
+            self.index.get_type(inner_type_name)?,
+            pointer,
+        ))
+    }
+
+    /// loads a bound of a variable-length array's dimension from its fat pointer
+    ///
+    /// - `fat_pointer` a pointer to the array's fat pointer
+    /// - `dimension` the 0-based dimension
+    /// - `upper` true to load the upper bound, false to load the lower bound
+    fn load_variable_length_array_bound(
+        &self,
+        fat_pointer: PointerValue<'a>,
+        dimension: u32,
+        upper: bool,
+    ) -> IntValue<'a> {
+        let builder = &self.llvm.builder;
+        let i32_type = self.llvm.i32_type();
+        let bound = unsafe {
+            builder.build_in_bounds_gep(
+                fat_pointer,
+                &[
+                    i32_type.const_zero(),
+                    i32_type.const_int(1, false),
+                    i32_type.const_int((dimension * 2 + upper as u32) as u64, false),
+                ],
+                "",
+            )
+        };
+        builder.build_load(bound, "").into_int_value()
+    }
+
+    /// stores the fat pointer for the given argument of a variable-length array parameter.
+    /// The argument needs to be an array with the same element type and the same number
+    /// of dimensions, variable-length arrays are forwarded as they are
+    ///
+    /// - `parameter` a pointer to the parameter's fat pointer
+    /// - `inner_type_name` the name of the parameter's element type
+    /// - `dimensions` the number of the parameter's dimensions
+    /// - `argument` the argument passed to the parameter
+    fn generate_variable_length_array_argument(
+        &self,
+        parameter: PointerValue<'a>,
+        inner_type_name: &str,
+        dimensions: u32,
+        argument: &Statement,
+    ) -> Result<(), CompileError> {
+        let builder = &self.llvm.builder;
+        let argument_pointer = self.generate_element_pointer(argument)?;
+        match argument_pointer.get_type_information() {
+            DataTypeInformation::Array {
+                inner_type_name: argument_inner_type_name,
+                dimensions: argument_dimensions,
+                ..
+            } if argument_inner_type_name.eq_ignore_ascii_case(inner_type_name)
+                && argument_dimensions.len() == dimensions as usize =>
+            {
+                let i32_type = self.llvm.i32_type();
+                //a pointer to the array's first element
+                let zeros = vec![i32_type.const_zero(); argument_dimensions.len() + 1];
+                let data = self.llvm.load_array_element(
+                    argument_pointer.ptr_value,
+                    zeros.as_slice(),
+                    "",
+                )?;
+                let bounds = argument_dimensions
+                    .iter()
+                    .flat_map(|it| {
+                        vec![
+                            i32_type.const_int(it.start_offset as u64, true),
+                            i32_type.const_int(it.end_offset as u64, true),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let location = argument.get_location();
+                let data_pointer = self
+                    .llvm
+                    .get_member_pointer_from_struct(parameter, 0, "", &location)?;
+                builder.build_store(data_pointer, data);
+                let bounds_pointer = self
+                    .llvm
+                    .get_member_pointer_from_struct(parameter, 1, "", &location)?;
+                builder.build_store(bounds_pointer, i32_type.const_array(bounds.as_slice()));
+                Ok(())
+            }
+            DataTypeInformation::VariableLengthArray {
+                inner_type_name: argument_inner_type_name,
+                dimensions: argument_dimensions,
+                ..
+            } if argument_inner_type_name.eq_ignore_ascii_case(inner_type_name)
+                && *argument_dimensions == dimensions =>
+            {
+                let (_, fat_pointer) = self.llvm.load_pointer(&argument_pointer, "");
+                builder.build_store(parameter, fat_pointer);
+                Ok(())
+            }
+            argument_type => Err(CompileError::codegen_error(
+                format!(
+                    "Expected an array of {} with {} dimension(s) but found {}",
+                    inner_type_name,
+                    dimensions,
+                    argument_type.get_name()
+                ),
+                argument.get_location(),
+            )),
+        }
+    }
+
     /// the entry function for recursive reference-generation (for qualified references)
     ///
     /// - `qualifier` the qualifier (TypeAndPointer) for the given reference-statement
@@ -862,6 +2017,9 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
     ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
         match reference {
             Statement::QualifiedReference { elements } => {
+                if let Some(list_member) = self.resolve_global_list_access(qualifier, elements) {
+                    return self.generate_element_pointer_for_rec(None, &list_member);
+                }
                 let mut element_iter = elements.iter();
                 let current_element = element_iter.next();
                 let mut current_lvalue =
@@ -869,6 +2027,12 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
 
                 for it in element_iter {
                     let ctx = current_lvalue?;
+                    //a qualifier passed by reference (e.g. a VAR_IN_OUT struct) points to its value
+                    let ctx = if ctx.is_bit_field() {
+                        ctx
+                    } else {
+                        self.auto_deref_if_necessary(ctx.type_entry, ctx.ptr_value)?
+                    };
                     let context_ptr = ctx.ptr_value;
                     let type_information = ctx.type_entry;
 
@@ -885,10 +2049,10 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                     let qualified_name = format!("{}.{}", qualifier.type_entry.get_name(), name);
                     let implementation = self.index.find_implementation(&qualified_name);
                     if implementation.is_some() {
-                        let result = TypeAndPointer {
-                            type_entry: self.index.get_type(&qualified_name)?,
-                            ptr_value: qualifier.ptr_value,
-                        };
+                        let result = TypeAndPointer::new(
+                            self.index.get_type(&qualified_name)?,
+                            qualifier.ptr_value,
+                        );
                         return Ok(result);
                     }
                 };
@@ -898,6 +2062,9 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
             Statement::ArrayAccess { reference, access } => {
                 self.generate_element_pointer_for_array(qualifier, reference, access)
             }
+            Statement::PointerAccess {
+                reference: pointer, ..
+            } => self.generate_element_pointer_for_pointer(qualifier, pointer),
             _ => Err(CompileError::codegen_error(
                 format!("Unsupported Statement {:?}", reference),
                 reference.get_location(),
@@ -905,179 +2072,1272 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         }
     }
 
-    /// generates the result of an int/bool binary-expression (+, -, *, /, %, ==)
-    ///
-    /// - `operator` the binary operator
-    /// - `left_value` the left side of the binary expression, needs to be an int-value
-    /// - `right_value` the right side of the binary expression, needs to be an int-value
-    /// - `target_type` the resulting type
-    pub fn create_llvm_int_binary_expression(
+    /// returns the reference to the global variable accessed by `List.variable` (with its remaining
+    /// elements, e.g. `List.variable.member`) or None if the given elements do not access a variable
+    /// of a global variable list. A variable with the list's name takes precedence over the list
+    fn resolve_global_list_access(
         &self,
-        operator: &Operator,
-        left_value: BasicValueEnum<'a>,
-        right_value: BasicValueEnum<'a>,
-        target_type: &DataTypeInformation,
-    ) -> TypeAndValue<'a> {
-        let int_lvalue = left_value.into_int_value();
-        let int_rvalue = right_value.into_int_value();
+        qualifier: Option<&TypeAndPointer<'a, '_>>,
+        elements: &[Statement],
+    ) -> Option<Statement> {
+        let list_name = match (qualifier, elements) {
+            (None, [Statement::Reference { name, .. }, _, ..]) => name,
+            _ => return None,
+        };
+        let context = self
+            .function_context
+            .map(|it| it.linking_context.get_type_name());
+        let list = self
+            .index
+            .find_global_variable_list(list_name)
+            .filter(|_| {
+                self.index
+                    .find_variable(context, &[list_name.clone()])
+                    .is_none()
+            })?;
 
-        let (value, data_type) = match operator {
-            Operator::Plus => (
-                self.llvm
-                    .builder
-                    .build_int_add(int_lvalue, int_rvalue, "tmpVar"),
-                target_type.clone(),
-            ),
-            Operator::Minus => (
-                self.llvm
-                    .builder
-                    .build_int_sub(int_lvalue, int_rvalue, "tmpVar"),
-                target_type.clone(),
-            ),
-            Operator::Multiplication => (
-                self.llvm
-                    .builder
-                    .build_int_mul(int_lvalue, int_rvalue, "tmpVar"),
-                target_type.clone(),
-            ),
-            Operator::Division => (
-                self.llvm
-                    .builder
-                    .build_int_signed_div(int_lvalue, int_rvalue, "tmpVar"),
-                target_type.clone(),
-            ),
-            Operator::Modulo => (
-                self.llvm
-                    .builder
-                    .build_int_signed_rem(int_lvalue, int_rvalue, "tmpVar"),
-                target_type.clone(),
-            ),
-            Operator::Equal => (
-                self.llvm.builder.build_int_compare(
-                    IntPredicate::EQ,
-                    int_lvalue,
-                    int_rvalue,
-                    "tmpVar",
-                ),
-                self.index.find_type_information("BOOL").unwrap(),
-            ),
+        let mut variable = elements[1].clone();
+        qualify_reference(&mut variable, list.get_name());
+        if elements.len() > 2 {
+            let mut remaining_elements = vec![variable];
+            remaining_elements.extend(elements[2..].iter().cloned());
+            Some(Statement::QualifiedReference {
+                elements: remaining_elements,
+            })
+        } else {
+            Some(variable)
+        }
+    }
 
-            Operator::NotEqual => (
-                self.llvm.builder.build_int_compare(
-                    IntPredicate::NE,
-                    int_lvalue,
-                    int_rvalue,
-                    "tmpVar",
+    /// dereferences the given pointer with an optional qualifier (e.g. `myStruct.p^`) and returns the
+    /// pointer to the value it points to
+    ///
+    /// - `qualifier` an optional qualifier for the pointer (e.g. myStruct.p^ where myStruct is the qualifier for p)
+    /// - `pointer` the reference-statement pointing to the dereferenced pointer
+    fn generate_element_pointer_for_pointer(
+        &self,
+        qualifier: Option<&TypeAndPointer<'a, '_>>,
+        pointer: &Statement,
+    ) -> Result<TypeAndPointer<'a, '_>, CompileError> {
+        let lvalue = self.generate_element_pointer_for_rec(qualifier, pointer)?;
+        let lvalue = if lvalue.is_bit_field() {
+            lvalue
+        } else {
+            self.auto_deref_if_necessary(lvalue.type_entry, lvalue.ptr_value)?
+        };
+        if let DataTypeInformation::Pointer {
+            inner_type_name,
+            auto_deref: false,
+            ..
+        } = lvalue.get_type_information()
+        {
+            let (_, value) = self.llvm.load_pointer(&lvalue, "deref");
+            let inner_type = self.index.get_type(inner_type_name)?;
+            Ok(TypeAndPointer::new(inner_type, value.into_pointer_value()))
+        } else {
+            Err(CompileError::codegen_error(
+                format!(
+                    "Cannot dereference {}, it is not a pointer",
+                    lvalue.get_type_information().get_name()
                 ),
-                self.index.find_type_information("BOOL").unwrap(),
-            ),
+                pointer.get_location(),
+            ))
+        }
+    }
 
-            Operator::Less => (
-                self.llvm.builder.build_int_compare(
-                    IntPredicate::SLT,
-                    int_lvalue,
-                    int_rvalue,
-                    "tmpVar",
-                ),
-                self.index.find_type_information("BOOL").unwrap(),
-            ),
+    /// generates a struct or array literal at runtime by storing its elements into the given struct or array.
+    /// Its elements may be any expression (e.g. `(x := 1.0, y := a + b)` or `[0, 1, n, 3]`), elements that
+    /// are not listed keep the type's initial value.
+    ///
+    /// Elements may read the target itself (e.g. `pos := (x := pos.y, y := pos.x)`), so a literal that
+    /// reads variables is built in a temporary which is copied into the target once all elements are evaluated.
+    ///
+    /// - `target` the pointer to the struct or array to store the literal into
+    /// - `target_type` the type of the struct or array
+    /// - `literal` the struct literal (an ExpressionList or a single Assignment) or the LiteralArray
+    pub fn generate_aggregate_literal(
+        &self,
+        target: PointerValue<'a>,
+        target_type: &DataTypeInformation,
+        literal: &Statement,
+    ) -> Result<(), CompileError> {
+        if !reads_variables(literal) {
+            return self.generate_aggregate_literal_into(target, target_type, literal);
+        }
+        let effective_type = self
+            .index
+            .find_effective_type(target_type)
+            .unwrap_or(target_type);
+        let llvm_type = self
+            .llvm_index
+            .get_associated_type(effective_type.get_name())?;
+        let temporary = self.llvm.create_local_variable("", &llvm_type);
+        self.generate_aggregate_literal_into(temporary, target_type, literal)?;
+        let value = self.llvm.builder.build_load(temporary, "");
+        self.llvm.builder.build_store(target, value);
+        Ok(())
+    }
 
-            Operator::Greater => (
-                self.llvm.builder.build_int_compare(
-                    IntPredicate::SGT,
-                    int_lvalue,
-                    int_rvalue,
-                    "tmpVar",
-                ),
-                self.index.find_type_information("BOOL").unwrap(),
+    /// stores the elements of the given struct or array literal directly into the given target
+    ///
+    /// - `target` the pointer to the struct or array to store the literal into
+    /// - `target_type` the type of the struct or array
+    /// - `literal` the struct literal (an ExpressionList or a single Assignment) or the LiteralArray
+    fn generate_aggregate_literal_into(
+        &self,
+        target: PointerValue<'a>,
+        target_type: &DataTypeInformation,
+        literal: &Statement,
+    ) -> Result<(), CompileError> {
+        let builder = &self.llvm.builder;
+        let effective_type = self
+            .index
+            .find_effective_type(target_type)
+            .unwrap_or(target_type);
+        match (effective_type, literal) {
+            (
+                DataTypeInformation::Struct {
+                    name, member_names, ..
+                },
+                Statement::ExpressionList { .. } | Statement::Assignment { .. },
+            ) => {
+                let assignments = flatten_expression_list(literal);
+                if assignments.len() < member_names.len() {
+                    self.generate_initial_value_store(target, effective_type)?;
+                }
+                for assignment in assignments {
+                    if let Statement::Assignment { left, right } = assignment {
+                        if let Statement::Reference {
+                            name: member_name,
+                            location,
+                        } = left.as_ref()
+                        {
+                            let member =
+                                self.index.find_member(name, member_name).ok_or_else(|| {
+                                    CompileError::invalid_reference(
+                                        format!("{}.{}", name, member_name).as_str(),
+                                        location.clone(),
+                                    )
+                                })?;
+                            let member_type = self.index.get_type(member.get_type_name())?;
+                            let member_location = self.get_member_location(member);
+                            let member_pointer = builder
+                                .build_struct_gep(target, member_location.field, member.get_name())
+                                .unwrap();
+                            let member_pointer = match member_location.bit {
+                                Some(bit) => {
+                                    TypeAndPointer::new_bit_field(member_type, member_pointer, bit)
+                                }
+                                None => TypeAndPointer::new(member_type, member_pointer),
+                            };
+                            self.generate_aggregate_element(&member_pointer, right)?;
+                        } else {
+                            return Err(CompileError::codegen_error(
+                                "struct member lvalue required as left operand of assignment"
+                                    .to_string(),
+                                left.get_location(),
+                            ));
+                        }
+                    } else {
+                        return Err(CompileError::codegen_error("struct literal must consist of explicit assignments in the form of member := value".to_string(), assignment.get_location()));
+                    }
+                }
+                Ok(())
+            }
+            (
+                DataTypeInformation::Array {
+                    name,
+                    inner_type_name,
+                    dimensions,
+                },
+                Statement::LiteralArray { elements, location },
+            ) => {
+                let elements = elements
+                    .as_ref()
+                    .map(|it| flatten_expression_list(it))
+                    .unwrap_or_default();
+                let length: u32 = dimensions.iter().map(Dimension::get_length).product();
+                if elements.len() > length as usize {
+                    return Err(CompileError::codegen_error(
+                        format!(
+                            "Expected at most {} elements for Array {}, but found {}.",
+                            length,
+                            name,
+                            elements.len()
+                        ),
+                        location.clone(),
+                    ));
+                }
+                if elements.len() < length as usize {
+                    self.generate_initial_value_store(target, effective_type)?;
+                }
+                let inner_type = self.index.get_type(inner_type_name)?;
+                let i32_type = self.llvm.i32_type();
+                for (position, element) in elements.into_iter().enumerate() {
+                    //the elements are listed row by row, split the position into the index of every dimension
+                    let mut indices = vec![i32_type.const_zero()];
+                    let mut remainder = position as u64;
+                    let mut stride = length as u64;
+                    for dimension in dimensions {
+                        stride /= dimension.get_length() as u64;
+                        indices.push(i32_type.const_int(remainder / stride, false));
+                        remainder %= stride;
+                    }
+                    let element_pointer =
+                        unsafe { builder.build_in_bounds_gep(target, &indices, "") };
+                    self.generate_aggregate_element(
+                        &TypeAndPointer::new(inner_type, element_pointer),
+                        element,
+                    )?;
+                }
+                Ok(())
+            }
+            _ => Err(CompileError::codegen_error(
+                format!(
+                    "Cannot assign literal {:?} to {}",
+                    literal,
+                    target_type.get_name()
+                ),
+                literal.get_location(),
+            )),
+        }
+    }
+
+    /// stores the type's initial value (or zero if it has none) into the given struct or array
+    fn generate_initial_value_store(
+        &self,
+        target: PointerValue<'a>,
+        data_type: &DataTypeInformation,
+    ) -> Result<(), CompileError> {
+        let initial_value = match self
+            .llvm_index
+            .find_associated_initial_value(data_type.get_name())
+        {
+            Some(value) => value,
+            None => struct_generator::get_default_for(
+                self.llvm_index.get_associated_type(data_type.get_name())?,
             ),
+        };
+        self.llvm.builder.build_store(target, initial_value);
+        Ok(())
+    }
 
-            Operator::LessOrEqual => (
-                self.llvm.builder.build_int_compare(
-                    IntPredicate::SLE,
-                    int_lvalue,
-                    int_rvalue,
-                    "tmpVar",
+    /// stores a single member or element of a struct or array literal.
+    /// The value is type-checked against the member's type and casted if necessary.
+    fn generate_aggregate_element(
+        &self,
+        target: &TypeAndPointer<'a, '_>,
+        element: &Statement,
+    ) -> Result<(), CompileError> {
+        let target_type = target.get_type_information();
+        if self.is_aggregate_type(target_type) && is_aggregate_literal(element) {
+            return self.generate_aggregate_literal_into(target.ptr_value, target_type, element);
+        }
+        let (value_type, value) = self
+            .morph_to_typed(target_type)
+            .generate_expression(element)?;
+        let value = if self.is_aggregate_type(target_type) {
+            if !self.is_same_aggregate_type(target_type, &value_type) {
+                return Err(CompileError::casting_error(
+                    value_type.get_name(),
+                    target_type.get_name(),
+                    element.get_location(),
+                ));
+            }
+            value
+        } else {
+            cast_if_needed(
+                self.llvm,
+                self.index,
+                target_type,
+                value,
+                &value_type,
+                element,
+            )?
+        };
+        self.llvm.store_pointer(target, value);
+        Ok(())
+    }
+
+    /// returns true if the given type is a struct or a fixed-size array
+    pub fn is_aggregate_type(&self, data_type: &DataTypeInformation) -> bool {
+        matches!(
+            self.index.find_effective_type(data_type),
+            Some(DataTypeInformation::Struct { .. })
+                | Some(DataTypeInformation::Union { .. })
+                | Some(DataTypeInformation::Array { .. })
+        )
+    }
+
+    /// returns true if values of the given aggregate types can be assigned to and compared with each other.
+    /// Structs and unions need to be of the same type, arrays need to have the same element-type and
+    /// dimensions of the same length
+    pub fn is_same_aggregate_type(
+        &self,
+        left: &DataTypeInformation,
+        right: &DataTypeInformation,
+    ) -> bool {
+        match (
+            self.index.find_effective_type(left),
+            self.index.find_effective_type(right),
+        ) {
+            (
+                Some(DataTypeInformation::Struct {
+                    name: left_name, ..
+                }),
+                Some(DataTypeInformation::Struct {
+                    name: right_name, ..
+                }),
+            ) => left_name.eq_ignore_ascii_case(right_name),
+            (
+                Some(DataTypeInformation::Union {
+                    name: left_name, ..
+                }),
+                Some(DataTypeInformation::Union {
+                    name: right_name, ..
+                }),
+            ) => left_name.eq_ignore_ascii_case(right_name),
+            (
+                Some(DataTypeInformation::Array {
+                    inner_type_name: left_inner,
+                    dimensions: left_dimensions,
+                    ..
+                }),
+                Some(DataTypeInformation::Array {
+                    inner_type_name: right_inner,
+                    dimensions: right_dimensions,
+                    ..
+                }),
+            ) => {
+                let same_dimensions = left_dimensions.len() == right_dimensions.len()
+                    && left_dimensions
+                        .iter()
+                        .zip(right_dimensions.iter())
+                        .all(|(l, r)| l.get_length() == r.get_length());
+                let same_inner_type = match (
+                    self.index.find_type_information(left_inner),
+                    self.index.find_type_information(right_inner),
+                ) {
+                    (Some(left_inner), Some(right_inner)) => {
+                        if self.is_aggregate_type(&left_inner) {
+                            self.is_same_aggregate_type(&left_inner, &right_inner)
+                        } else {
+                            self.index.find_effective_type(&left_inner)
+                                == self.index.find_effective_type(&right_inner)
+                        }
+                    }
+                    _ => false,
+                };
+                same_dimensions && same_inner_type
+            }
+            _ => false,
+        }
+    }
+
+    /// generates an element-wise `=` or `<>` of two structs or arrays of the same type
+    ///
+    /// - `operator` Equal or NotEqual
+    /// - `left` the generated left side of the comparison
+    /// - `right` the generated right side of the comparison
+    /// - `expression` the comparison, used to report errors
+    fn generate_aggregate_comparison(
+        &self,
+        operator: &Operator,
+        left: TypeAndValue<'a>,
+        right: TypeAndValue<'a>,
+        expression: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let (left_type, left_value) = left;
+        let (right_type, right_value) = right;
+        if !self.is_same_aggregate_type(&left_type, &right_type) {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Cannot compare {} with {}",
+                    left_type.get_name(),
+                    right_type.get_name()
                 ),
-                self.index.find_type_information("BOOL").unwrap(),
-            ),
+                expression.get_location(),
+            ));
+        }
+        let equal =
+            self.generate_element_wise_equality(&left_type, left_value, right_value, expression)?;
+        let result = if let Operator::NotEqual = operator {
+            self.llvm.builder.build_not(equal, "tmpVar")
+        } else {
+            equal
+        };
+        Ok((self.index.get_type_information("BOOL")?, result.into()))
+    }
 
-            Operator::GreaterOrEqual => (
-                self.llvm.builder.build_int_compare(
-                    IntPredicate::SGE,
-                    int_lvalue,
-                    int_rvalue,
-                    "tmpVar",
+    /// generates an `=` or `<>` of two pointers or of a pointer and an integer (e.g. `p <> 0`)
+    /// by comparing their addresses
+    ///
+    /// - `operator` Equal or NotEqual
+    /// - `left` the generated left side of the comparison
+    /// - `right` the generated right side of the comparison
+    /// - `expression` the comparison, used to report errors
+    fn generate_pointer_comparison(
+        &self,
+        operator: &Operator,
+        left: TypeAndValue<'a>,
+        right: TypeAndValue<'a>,
+        expression: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let (left_type, left_value) = left;
+        let (right_type, right_value) = right;
+        let is_address = |data_type: &DataTypeInformation| {
+            is_raw_pointer(data_type)
+                || self
+                    .index
+                    .find_effective_type(data_type)
+                    .map_or(false, DataTypeInformation::is_int)
+        };
+        if !is_address(&left_type) || !is_address(&right_type) {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Cannot compare {} with {}",
+                    left_type.get_name(),
+                    right_type.get_name()
                 ),
-                self.index.find_type_information("BOOL").unwrap(),
-            ),
-            Operator::Xor => (
-                self.llvm
-                    .builder
-                    .build_xor(int_lvalue, int_rvalue, "tmpVar"),
-                self.index.find_type_information("BOOL").unwrap(),
-            ),
-            _ => unimplemented!(),
+                expression.get_location(),
+            ));
+        }
+        let left_address = self.generate_address(&left_type, left_value);
+        let right_address = self.generate_address(&right_type, right_value);
+        let predicate = if let Operator::NotEqual = operator {
+            IntPredicate::NE
+        } else {
+            IntPredicate::EQ
         };
-        (data_type, value.into())
+        let result =
+            self.llvm
+                .builder
+                .build_int_compare(predicate, left_address, right_address, "tmpVar");
+        Ok((self.index.get_type_information("BOOL")?, result.into()))
     }
 
-    /// generates the result of a float binary-expression (+, -, *, /, %, ==)
+    /// returns the given pointer or integer as a byte-pointer, llvm extends or truncates the
+    /// integer to the target's pointer width
+    fn generate_address(
+        &self,
+        data_type: &DataTypeInformation,
+        value: BasicValueEnum<'a>,
+    ) -> PointerValue<'a> {
+        let byte_pointer_type = self.llvm.context.i8_type().ptr_type(AddressSpace::Generic);
+        if is_raw_pointer(data_type) {
+            self.llvm
+                .builder
+                .build_pointer_cast(value.into_pointer_value(), byte_pointer_type, "")
+        } else {
+            self.llvm
+                .builder
+                .build_int_to_ptr(value.into_int_value(), byte_pointer_type, "")
+        }
+    }
+
+    /// compares two values of the given type element by element and returns the result as an i1
+    fn generate_element_wise_equality(
+        &self,
+        data_type: &DataTypeInformation,
+        left: BasicValueEnum<'a>,
+        right: BasicValueEnum<'a>,
+        expression: &Statement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        match self.index.find_effective_type(data_type) {
+            Some(DataTypeInformation::Struct { name, .. }) => {
+                let mut results = Vec::new();
+                for member in self.index.find_local_members(name) {
+                    let member_type = self.index.get_type_information(member.get_type_name())?;
+                    let location = self.get_member_location(member);
+                    let extract_member = |value| {
+                        extract_value(builder, value, location.field, expression).map(|it| {
+                            match location.bit {
+                                Some(bit) => {
+                                    self.llvm.extract_bit(it.into_int_value(), bit, "").into()
+                                }
+                                None => it,
+                            }
+                        })
+                    };
+                    results.push(self.generate_element_wise_equality(
+                        &member_type,
+                        extract_member(left)?,
+                        extract_member(right)?,
+                        expression,
+                    )?);
+                }
+                Ok(self.generate_conjunction(results))
+            }
+            Some(DataTypeInformation::Array {
+                inner_type_name,
+                dimensions,
+                ..
+            }) => {
+                let inner_type = self.index.get_type_information(inner_type_name)?;
+                self.generate_array_equality(&inner_type, dimensions.len(), left, right, expression)
+            }
+            Some(DataTypeInformation::String { encoding, .. }) => {
+                //only the characters up to the terminating NUL are compared, the bytes after it may differ
+                self.generate_string_scan(
+                    encoding.get_bytes_per_char(),
+                    left,
+                    right,
+                    true,
+                    |left_char, right_char| {
+                        builder.build_int_compare(IntPredicate::EQ, left_char, right_char, "")
+                    },
+                    expression,
+                )
+            }
+            Some(DataTypeInformation::Float { .. }) => Ok(builder.build_float_compare(
+                FloatPredicate::OEQ,
+                left.into_float_value(),
+                right.into_float_value(),
+                "",
+            )),
+            Some(DataTypeInformation::Integer { .. }) => Ok(builder.build_int_compare(
+                IntPredicate::EQ,
+                left.into_int_value(),
+                right.into_int_value(),
+                "",
+            )),
+            Some(DataTypeInformation::Pointer { .. }) => Ok(builder.build_int_compare(
+                IntPredicate::EQ,
+                left.into_pointer_value(),
+                right.into_pointer_value(),
+                "",
+            )),
+            _ => Err(CompileError::codegen_error(
+                format!("Cannot compare values of type {}", data_type.get_name()),
+                expression.get_location(),
+            )),
+        }
+    }
+
+    /// compares two (nested) arrays element by element in a loop that stops at the first difference
+    /// and returns the result as an i1
+    ///
+    /// - `inner_type` the type of the array's elements
+    /// - `dimension_count` the number of the arrays' dimensions
+    fn generate_array_equality(
+        &self,
+        inner_type: &DataTypeInformation,
+        dimension_count: usize,
+        left: BasicValueEnum<'a>,
+        right: BasicValueEnum<'a>,
+        expression: &Statement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let (left_elements, length) = self.store_as_flat_array(left, dimension_count);
+        let (right_elements, _) = self.store_as_flat_array(right, dimension_count);
+        self.generate_compare_loop(
+            length,
+            self.llvm.context.bool_type().const_all_ones(),
+            expression,
+            |index| {
+                let load_element = |elements| {
+                    let element = unsafe { builder.build_in_bounds_gep(elements, &[index], "") };
+                    builder.build_load(element, "")
+                };
+                let equal = self.generate_element_wise_equality(
+                    inner_type,
+                    load_element(left_elements),
+                    load_element(right_elements),
+                    expression,
+                )?;
+                Ok((
+                    builder.build_not(equal, ""),
+                    self.llvm.context.bool_type().const_zero(),
+                ))
+            },
+        )
+    }
+
+    /// stores the given (nested) array-value in a temporary and returns the pointer to its first
+    /// element and the number of elements of all dimensions
+    ///
+    /// - `dimension_count` the number of nested arrays to flatten
+    fn store_as_flat_array(
+        &self,
+        array: BasicValueEnum<'a>,
+        dimension_count: usize,
+    ) -> (PointerValue<'a>, u32) {
+        let builder = &self.llvm.builder;
+        let array_type = array.into_array_value().get_type().as_basic_type_enum();
+        let temporary = self.llvm.create_local_variable("", &array_type);
+        builder.build_store(temporary, array);
+        let mut element_type = array_type;
+        let mut length = 1;
+        for _ in 0..dimension_count {
+            let dimension_type = element_type.into_array_type();
+            length *= dimension_type.len();
+            element_type = dimension_type.get_element_type();
+        }
+        let elements =
+            builder.build_pointer_cast(temporary, element_type.ptr_type(AddressSpace::Generic), "");
+        (elements, length)
+    }
+
+    /// compares two strings character by character until they differ or end with a NUL character,
+    /// the bytes after the terminating NUL are ignored
+    ///
+    /// - `bytes_per_char` the number of (little endian) bytes per character
+    /// - `equal_result` the result if both strings are equal up to their full length
+    /// - `result_at_stop` returns the result from the left and right character the comparison stopped at
+    fn generate_string_scan(
+        &self,
+        bytes_per_char: u32,
+        left: BasicValueEnum<'a>,
+        right: BasicValueEnum<'a>,
+        equal_result: bool,
+        result_at_stop: impl Fn(IntValue<'a>, IntValue<'a>) -> IntValue<'a>,
+        expression: &Statement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let char_type = self.llvm.context.custom_width_int_type(bytes_per_char * 8);
+        let index_type = self.llvm.i32_type();
+        let (left_bytes, length) = self.store_as_flat_array(left, 1);
+        let (right_bytes, _) = self.store_as_flat_array(right, 1);
+        //assembles the character at the given index from its bytes
+        let load_char = |bytes, index: IntValue<'a>| {
+            let first_byte = if bytes_per_char > 1 {
+                builder.build_int_mul(
+                    index,
+                    index_type.const_int(bytes_per_char as u64, false),
+                    "",
+                )
+            } else {
+                index
+            };
+            let mut result = char_type.const_zero();
+            for i in 0..bytes_per_char {
+                let offset = if i > 0 {
+                    builder.build_int_add(first_byte, index_type.const_int(i as u64, false), "")
+                } else {
+                    first_byte
+                };
+                let byte = unsafe { builder.build_in_bounds_gep(bytes, &[offset], "") };
+                let byte = builder.build_int_z_extend_or_bit_cast(
+                    builder.build_load(byte, "").into_int_value(),
+                    char_type,
+                    "",
+                );
+                let byte = if i > 0 {
+                    builder.build_left_shift(byte, char_type.const_int(i as u64 * 8, false), "")
+                } else {
+                    byte
+                };
+                result = builder.build_or(result, byte, "");
+            }
+            result
+        };
+        self.generate_compare_loop(
+            length / bytes_per_char,
+            self.llvm
+                .context
+                .bool_type()
+                .const_int(equal_result as u64, false),
+            expression,
+            |index| {
+                let left_char = load_char(left_bytes, index);
+                let right_char = load_char(right_bytes, index);
+                let differs =
+                    builder.build_int_compare(IntPredicate::NE, left_char, right_char, "");
+                let terminated = builder.build_int_compare(
+                    IntPredicate::EQ,
+                    left_char,
+                    char_type.const_zero(),
+                    "",
+                );
+                Ok((
+                    builder.build_or(differs, terminated, ""),
+                    result_at_stop(left_char, right_char),
+                ))
+            },
+        )
+    }
+
+    /// generates a loop over the indices `0..length` that stops as soon as `compare` decides the
+    /// comparison and returns the result as an i1
+    ///
+    /// - `length` the number of compared elements
+    /// - `equal_result` the result if the loop did not stop before the end
+    /// - `compare` generates the comparison of the elements at the given index and returns whether
+    ///   the comparison is decided and its result in that case
+    fn generate_compare_loop(
+        &self,
+        length: u32,
+        equal_result: IntValue<'a>,
+        expression: &Statement,
+        compare: impl Fn(IntValue<'a>) -> Result<(IntValue<'a>, IntValue<'a>), CompileError>,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let context = self.llvm.context;
+        let function = self.get_function_context(expression)?.function;
+        let index_type = self.llvm.i32_type();
+        let start_block = builder.get_insert_block().unwrap();
+        let loop_block = context.append_basic_block(function, "compare_loop");
+        let element_block = context.append_basic_block(function, "compare_element");
+        let next_block = context.append_basic_block(function, "compare_next");
+        let end_block = context.append_basic_block(function, "compare_end");
+        builder.build_unconditional_branch(loop_block);
+
+        builder.position_at_end(loop_block);
+        let index = builder.build_phi(index_type, "index");
+        let index_value = index.as_basic_value().into_int_value();
+        let in_range = builder.build_int_compare(
+            IntPredicate::ULT,
+            index_value,
+            index_type.const_int(length as u64, false),
+            "",
+        );
+        builder.build_conditional_branch(in_range, element_block, end_block);
+
+        builder.position_at_end(element_block);
+        let (is_decided, decided_result) = compare(index_value)?;
+        //the comparison may have added blocks (e.g. to compare nested arrays)
+        let decided_block = builder.get_insert_block().unwrap();
+        builder.build_conditional_branch(is_decided, end_block, next_block);
+
+        builder.position_at_end(next_block);
+        let next_index = builder.build_int_add(index_value, index_type.const_int(1, false), "");
+        builder.build_unconditional_branch(loop_block);
+        index.add_incoming(&[
+            (&index_type.const_zero(), start_block),
+            (&next_index, next_block),
+        ]);
+
+        builder.position_at_end(end_block);
+        let result = builder.build_phi(context.bool_type(), "");
+        result.add_incoming(&[
+            (&equal_result, loop_block),
+            (&decided_result, decided_block),
+        ]);
+        Ok(result.as_basic_value().into_int_value())
+    }
+
+    /// combines the given i1-values with `and`, returns true if no values are given
+    fn generate_conjunction(&self, values: Vec<IntValue<'a>>) -> IntValue<'a> {
+        let mut values = values.into_iter();
+        if let Some(first) = values.next() {
+            values.fold(first, |result, value| {
+                self.llvm.builder.build_and(result, value, "")
+            })
+        } else {
+            self.llvm.context.bool_type().const_all_ones()
+        }
+    }
+
+    /// generates the result of an int/bool binary-expression (+, -, *, /, %, ==)
     ///
     /// - `operator` the binary operator
-    /// - `left_value` the left side of the binary expression, needs to be a float-value
-    /// - `right_value` the right side of the binary expression, needs to be a float-value
+    /// - `left_value` the left side of the binary expression, needs to be an int-value
+    /// - `right_value` the right side of the binary expression, needs to be an int-value
     /// - `target_type` the resulting type
-    fn create_llvm_float_binary_expression(
+    /// - `context` the binary expression, used to report errors and the location of a failing overflow check
+    pub fn create_llvm_int_binary_expression(
         &self,
         operator: &Operator,
-        lvalue: BasicValueEnum<'a>,
-        rvalue: BasicValueEnum<'a>,
+        left_value: BasicValueEnum<'a>,
+        right_value: BasicValueEnum<'a>,
         target_type: &DataTypeInformation,
-    ) -> TypeAndValue<'a> {
-        let float_lvalue = lvalue.into_float_value();
-        let float_rvalue = rvalue.into_float_value();
+        context: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let int_lvalue = left_value.into_int_value();
+        let int_rvalue = right_value.into_int_value();
 
         let (value, data_type) = match operator {
+            Operator::Plus | Operator::Minus | Operator::Multiplication
+                if self.is_overflow_check_required(target_type) =>
+            {
+                let operation = match operator {
+                    Operator::Plus => "add",
+                    Operator::Minus => "sub",
+                    _ => "mul",
+                };
+                (
+                    self.generate_overflow_checked_operation(
+                        operation,
+                        target_type,
+                        int_lvalue,
+                        int_rvalue,
+                        context,
+                    )?,
+                    target_type.clone(),
+                )
+            }
             Operator::Plus => (
                 self.llvm
                     .builder
-                    .build_float_add(float_lvalue, float_rvalue, "tmpVar")
-                    .into(),
+                    .build_int_add(int_lvalue, int_rvalue, "tmpVar"),
                 target_type.clone(),
             ),
             Operator::Minus => (
                 self.llvm
                     .builder
-                    .build_float_sub(float_lvalue, float_rvalue, "tmpVar")
-                    .into(),
+                    .build_int_sub(int_lvalue, int_rvalue, "tmpVar"),
                 target_type.clone(),
             ),
             Operator::Multiplication => (
                 self.llvm
                     .builder
-                    .build_float_mul(float_lvalue, float_rvalue, "tmpVar")
-                    .into(),
+                    .build_int_mul(int_lvalue, int_rvalue, "tmpVar"),
                 target_type.clone(),
             ),
             Operator::Division => (
                 self.llvm
                     .builder
-                    .build_float_div(float_lvalue, float_rvalue, "tmpVar")
-                    .into(),
+                    .build_int_signed_div(int_lvalue, int_rvalue, "tmpVar"),
                 target_type.clone(),
             ),
             Operator::Modulo => (
                 self.llvm
                     .builder
-                    .build_float_rem(float_lvalue, float_rvalue, "tmpVar")
-                    .into(),
+                    .build_int_signed_rem(int_lvalue, int_rvalue, "tmpVar"),
                 target_type.clone(),
             ),
             Operator::Equal => (
-                self.llvm
-                    .builder
-                    .build_float_compare(FloatPredicate::OEQ, float_lvalue, float_rvalue, "tmpVar")
+                self.llvm.builder.build_int_compare(
+                    IntPredicate::EQ,
+                    int_lvalue,
+                    int_rvalue,
+                    "tmpVar",
+                ),
+                self.index.find_type_information("BOOL").unwrap(),
+            ),
+
+            Operator::NotEqual => (
+                self.llvm.builder.build_int_compare(
+                    IntPredicate::NE,
+                    int_lvalue,
+                    int_rvalue,
+                    "tmpVar",
+                ),
+                self.index.find_type_information("BOOL").unwrap(),
+            ),
+
+            Operator::Less => (
+                self.llvm.builder.build_int_compare(
+                    IntPredicate::SLT,
+                    int_lvalue,
+                    int_rvalue,
+                    "tmpVar",
+                ),
+                self.index.find_type_information("BOOL").unwrap(),
+            ),
+
+            Operator::Greater => (
+                self.llvm.builder.build_int_compare(
+                    IntPredicate::SGT,
+                    int_lvalue,
+                    int_rvalue,
+                    "tmpVar",
+                ),
+                self.index.find_type_information("BOOL").unwrap(),
+            ),
+
+            Operator::LessOrEqual => (
+                self.llvm.builder.build_int_compare(
+                    IntPredicate::SLE,
+                    int_lvalue,
+                    int_rvalue,
+                    "tmpVar",
+                ),
+                self.index.find_type_information("BOOL").unwrap(),
+            ),
+
+            Operator::GreaterOrEqual => (
+                self.llvm.builder.build_int_compare(
+                    IntPredicate::SGE,
+                    int_lvalue,
+                    int_rvalue,
+                    "tmpVar",
+                ),
+                self.index.find_type_information("BOOL").unwrap(),
+            ),
+            Operator::And => (
+                self.llvm
+                    .builder
+                    .build_and(int_lvalue, int_rvalue, "tmpVar"),
+                target_type.clone(),
+            ),
+            Operator::Or => (
+                self.llvm.builder.build_or(int_lvalue, int_rvalue, "tmpVar"),
+                target_type.clone(),
+            ),
+            Operator::Xor => (
+                self.llvm
+                    .builder
+                    .build_xor(int_lvalue, int_rvalue, "tmpVar"),
+                target_type.clone(),
+            ),
+            _ => unimplemented!(),
+        };
+        Ok((data_type, value.into()))
+    }
+
+    /// passes the divisor of a division or modulo to the CheckDivDInt, CheckDivLInt, CheckDivReal or
+    /// CheckDivLReal function (depending on the divisor's type) if declared by the user and returns the
+    /// divisor to use
+    ///
+    /// - `data_type` the type of the division
+    /// - `divisor` the generated divisor
+    /// - `divisor_statement` the divisor's expression
+    fn generate_division_check(
+        &self,
+        data_type: &DataTypeInformation,
+        divisor: BasicValueEnum<'a>,
+        divisor_statement: &Statement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let function_context = if let Some(function_context) = self.function_context {
+            function_context
+        } else {
+            return Ok(divisor);
+        };
+        //a divisor is never passed to a hook with a narrower parameter, it would be truncated
+        let check_function = match self.index.find_effective_type(data_type) {
+            Some(DataTypeInformation::Integer { size, .. }) if *size <= 32 => DIV_CHECK_INT_FN,
+            Some(DataTypeInformation::Integer { .. }) => DIV_CHECK_LINT_FN,
+            Some(DataTypeInformation::Float { size, .. }) if *size <= 32 => DIV_CHECK_REAL_FN,
+            Some(DataTypeInformation::Float { .. }) => DIV_CHECK_LREAL_FN,
+            _ => return Ok(divisor),
+        };
+        if self.index.find_implementation(check_function).is_none() {
+            //without a check function a zero divisor aborts the program if checks are enabled
+            if function_context.options.overflow_checks {
+                self.generate_zero_divisor_trap(divisor, divisor_statement)?;
+            }
+            return Ok(divisor);
+        }
+        //don't check the divisions inside the check function itself
+        if function_context
+            .linking_context
+            .get_call_name()
+            .eq_ignore_ascii_case(check_function)
+        {
+            return Ok(divisor);
+        }
+        let (result_type, result) = self
+            .generate_call_with_values(
+                check_function,
+                &[(data_type.clone(), divisor)],
+                divisor_statement,
+            )?
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("{} must return the divisor to use", check_function),
+                    divisor_statement.get_location(),
+                )
+            })?;
+        cast_if_needed(
+            self.llvm,
+            self.index,
+            data_type,
+            result,
+            &result_type,
+            divisor_statement,
+        )
+    }
+
+    /// generates a check that aborts the program if the given int or float divisor is zero
+    ///
+    /// - `divisor` the generated divisor
+    /// - `divisor_statement` the divisor's expression, reported to the trap handler
+    fn generate_zero_divisor_trap(
+        &self,
+        divisor: BasicValueEnum<'a>,
+        divisor_statement: &Statement,
+    ) -> Result<(), CompileError> {
+        let builder = &self.llvm.builder;
+        let is_zero = match divisor {
+            BasicValueEnum::IntValue(value) => builder.build_int_compare(
+                IntPredicate::EQ,
+                value,
+                value.get_type().const_zero(),
+                "",
+            ),
+            BasicValueEnum::FloatValue(value) => builder.build_float_compare(
+                FloatPredicate::OEQ,
+                value,
+                value.get_type().const_zero(),
+                "",
+            ),
+            _ => return Ok(()),
+        };
+        self.generate_trap_if(
+            is_zero,
+            "division_by_zero",
+            "nonzero_divisor",
+            divisor_statement,
+        )
+    }
+
+    /// generates a check that aborts the program if a signed division or modulo overflows (the smallest
+    /// value divided by -1) and overflow checks are enabled
+    ///
+    /// - `data_type` the type of the division
+    /// - `dividend` / `divisor` the generated operands
+    /// - `expression` the division, reported to the trap handler
+    fn generate_division_overflow_trap(
+        &self,
+        data_type: &DataTypeInformation,
+        dividend: BasicValueEnum<'a>,
+        divisor: BasicValueEnum<'a>,
+        expression: &Statement,
+    ) -> Result<(), CompileError> {
+        let is_signed = matches!(
+            self.index.find_effective_type(data_type),
+            Some(DataTypeInformation::Integer { signed: true, .. })
+        );
+        if !is_signed || !self.is_overflow_check_required(data_type) {
+            return Ok(());
+        }
+        let builder = &self.llvm.builder;
+        let dividend = dividend.into_int_value();
+        let divisor = divisor.into_int_value();
+        let int_type = dividend.get_type();
+        let min_value = int_type.const_int(1 << (int_type.get_bit_width() - 1), false);
+        let is_min = builder.build_int_compare(IntPredicate::EQ, dividend, min_value, "");
+        let is_minus_one =
+            builder.build_int_compare(IntPredicate::EQ, divisor, int_type.const_all_ones(), "");
+        let overflows = builder.build_and(is_min, is_minus_one, "");
+        self.generate_trap_if(overflows, "overflow", "no_overflow", expression)
+    }
+
+    /// returns the type the operands of an arithmetic operation were declared with if it is narrower
+    /// than the `common_type` they were promoted to (e.g. INT for `INT + SINT` calculated as DINT).
+    /// An integer literal takes the type of the other operand if its value fits into it
+    ///
+    /// - `common_type` the type the operands were promoted to
+    /// - `left` / `right` the operands' expressions and their types before the promotion
+    fn get_narrower_declared_type(
+        &self,
+        common_type: &DataTypeInformation,
+        left: (&Statement, &DataTypeInformation),
+        right: (&Statement, &DataTypeInformation),
+    ) -> Option<DataTypeInformation> {
+        let common_size = match common_type {
+            DataTypeInformation::Integer { size, .. } => *size,
+            _ => return None,
+        };
+        let operand_types = [left, right]
+            .iter()
+            .map(|(statement, data_type)| {
+                let data_type = self.index.find_effective_type(data_type)?;
+                if data_type.is_int() {
+                    Some((*statement, data_type))
+                } else {
+                    None
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let declared_types = operand_types
+            .iter()
+            .filter(|(statement, _)| !matches!(statement, Statement::LiteralInteger { .. }))
+            .map(|(_, data_type)| *data_type)
+            .collect::<Vec<_>>();
+        let declared_type = match declared_types.as_slice() {
+            [data_type] => (*data_type).clone(),
+            [left_type, right_type] => get_bigger_type(left_type, right_type),
+            _ => return None,
+        };
+        let literals_fit = operand_types.iter().all(|(statement, _)| match statement {
+            Statement::LiteralInteger { value, .. } => fits_into_int_type(value, &declared_type),
+            _ => true,
+        });
+        let is_narrower = matches!(
+            declared_type,
+            DataTypeInformation::Integer { size, .. } if size > 1 && size < common_size
+        );
+        if is_narrower && literals_fit {
+            Some(declared_type)
+        } else {
+            None
+        }
+    }
+
+    /// generates an overflow-checked `+`, `-` or `*` of two promoted operands in the (narrower) type
+    /// they were declared with and extends the result back to the promoted type, an overflow of the
+    /// declared type would go unnoticed in the promoted type
+    ///
+    /// - `declared_type` the type the operation is checked in
+    /// - `common_type` the type the operands were promoted to
+    /// - `left_value` / `right_value` the promoted operands
+    /// - `expression` the binary expression, reported to the trap handler
+    fn generate_overflow_checked_in_declared_type(
+        &self,
+        operator: &Operator,
+        declared_type: &DataTypeInformation,
+        common_type: &DataTypeInformation,
+        left_value: BasicValueEnum<'a>,
+        right_value: BasicValueEnum<'a>,
+        expression: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let declared_llvm_type = get_llvm_int_type(
+            self.llvm.context,
+            declared_type.get_size(),
+            declared_type.get_name(),
+        )?;
+        let left_value =
+            builder.build_int_truncate(left_value.into_int_value(), declared_llvm_type, "");
+        let right_value =
+            builder.build_int_truncate(right_value.into_int_value(), declared_llvm_type, "");
+        let (_, result) = self.create_llvm_int_binary_expression(
+            operator,
+            left_value.into(),
+            right_value.into(),
+            declared_type,
+            expression,
+        )?;
+        let result = cast_if_needed(
+            self.llvm,
+            self.index,
+            common_type,
+            result,
+            declared_type,
+            expression,
+        )?;
+        Ok((common_type.clone(), result))
+    }
+
+    /// returns true if arithmetic operations on the given type need to be checked for overflows
+    fn is_overflow_check_required(&self, data_type: &DataTypeInformation) -> bool {
+        let is_checked_type = matches!(
+            self.index.find_effective_type(data_type),
+            Some(DataTypeInformation::Integer { size, .. }) if *size > 1
+        );
+        is_checked_type
+            && self
+                .function_context
+                .map(|it| it.options.overflow_checks)
+                .unwrap_or(false)
+    }
+
+    /// generates the given arithmetic operation using the `llvm.*.with.overflow` intrinsics and traps
+    /// if the operation overflows
+    ///
+    /// - `operation` the operation to generate (add, sub or mul)
+    /// - `data_type` the int-type of the operation, decides whether a signed or unsigned overflow is checked
+    /// - `left` / `right` the operands
+    /// - `context` the statement reported to the trap handler if the operation overflows
+    fn generate_overflow_checked_operation(
+        &self,
+        operation: &str,
+        data_type: &DataTypeInformation,
+        left: IntValue<'a>,
+        right: IntValue<'a>,
+        context: &Statement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let signed = matches!(
+            self.index.find_effective_type(data_type),
+            Some(DataTypeInformation::Integer { signed: true, .. })
+        );
+        let intrinsic_name = get_int_intrinsic_name(
+            &format!(
+                "llvm.{}{}.with.overflow",
+                if signed { "s" } else { "u" },
+                operation
+            ),
+            left.get_type(),
+        );
+        let intrinsic = self
+            .llvm_index
+            .find_associated_implementation(&intrinsic_name)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Missing declaration of {}", intrinsic_name),
+                    context.get_location(),
+                )
+            })?;
+        let result = builder
+            .build_call(intrinsic, &[left.into(), right.into()], "")
+            .try_as_basic_value()
+            .left()
+            .map(BasicValueEnum::into_struct_value)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("{} did not return a result", intrinsic_name),
+                    context.get_location(),
+                )
+            })?;
+        //the intrinsics return the (wrapped) result and an overflow flag
+        let value = builder.build_extract_value(result, 0, "tmpVar");
+        let overflow = builder.build_extract_value(result, 1, "");
+        if let (Some(value), Some(overflow)) = (value, overflow) {
+            self.generate_trap_if(
+                overflow.into_int_value(),
+                "overflow",
+                "no_overflow",
+                context,
+            )?;
+            Ok(value.into_int_value())
+        } else {
+            Err(CompileError::codegen_error(
+                format!("Cannot read the result of {}", intrinsic_name),
+                context.get_location(),
+            ))
+        }
+    }
+
+    /// generates the result of a float binary-expression (+, -, *, /, %, ==)
+    ///
+    /// - `operator` the binary operator
+    /// - `left_value` the left side of the binary expression, needs to be a float-value
+    /// - `right_value` the right side of the binary expression, needs to be a float-value
+    /// - `target_type` the resulting type
+    fn create_llvm_float_binary_expression(
+        &self,
+        operator: &Operator,
+        lvalue: BasicValueEnum<'a>,
+        rvalue: BasicValueEnum<'a>,
+        target_type: &DataTypeInformation,
+    ) -> TypeAndValue<'a> {
+        let float_lvalue = lvalue.into_float_value();
+        let float_rvalue = rvalue.into_float_value();
+
+        let (value, data_type) = match operator {
+            Operator::Plus => (
+                self.llvm
+                    .builder
+                    .build_float_add(float_lvalue, float_rvalue, "tmpVar")
+                    .into(),
+                target_type.clone(),
+            ),
+            Operator::Minus => (
+                self.llvm
+                    .builder
+                    .build_float_sub(float_lvalue, float_rvalue, "tmpVar")
+                    .into(),
+                target_type.clone(),
+            ),
+            Operator::Multiplication => (
+                self.llvm
+                    .builder
+                    .build_float_mul(float_lvalue, float_rvalue, "tmpVar")
+                    .into(),
+                target_type.clone(),
+            ),
+            Operator::Division => (
+                self.llvm
+                    .builder
+                    .build_float_div(float_lvalue, float_rvalue, "tmpVar")
+                    .into(),
+                target_type.clone(),
+            ),
+            Operator::Modulo => (
+                self.llvm
+                    .builder
+                    .build_float_rem(float_lvalue, float_rvalue, "tmpVar")
+                    .into(),
+                target_type.clone(),
+            ),
+            Operator::Equal => (
+                self.llvm
+                    .builder
+                    .build_float_compare(FloatPredicate::OEQ, float_lvalue, float_rvalue, "tmpVar")
                     .into(),
                 self.index.find_type_information("BOOL").unwrap(),
             ),
@@ -1144,15 +3404,20 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 year,
                 month,
                 day,
+                is_long,
                 location,
-            } => self.llvm.create_const_int(
-                self.index,
-                &Some(self.llvm.i64_type().into()),
-                calculate_date_time(*year, *month, *day, 0, 0, 0, 0)
-                    .map_err(|op| CompileError::codegen_error(op, location.clone()))
-                    .map(|millis| format!("{}", millis))?
-                    .as_str(),
-            ),
+            } => {
+                let date_type = if *is_long {
+                    DateAndTimeType::LDate
+                } else {
+                    DateAndTimeType::Date
+                };
+                self.create_const_date_and_time(
+                    date_type,
+                    calculate_date_time(date_type, *year, *month, *day, 0, 0, 0, 0)
+                        .map_err(|op| CompileError::codegen_error(op, location.clone()))?,
+                )
+            }
             Statement::LiteralDateAndTime {
                 year,
                 month,
@@ -1160,30 +3425,40 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 hour,
                 min,
                 sec,
-                milli,
+                nano,
+                is_long,
                 location,
-            } => self.llvm.create_const_int(
-                self.index,
-                &Some(self.llvm.i64_type().into()),
-                calculate_date_time(*year, *month, *day, *hour, *min, *sec, *milli)
-                    .map_err(|op| CompileError::codegen_error(op, location.clone()))
-                    .map(|millis| format!("{}", millis))?
-                    .as_str(),
-            ),
+            } => {
+                let date_type = if *is_long {
+                    DateAndTimeType::LDateAndTime
+                } else {
+                    DateAndTimeType::DateAndTime
+                };
+                self.create_const_date_and_time(
+                    date_type,
+                    calculate_date_time(date_type, *year, *month, *day, *hour, *min, *sec, *nano)
+                        .map_err(|op| CompileError::codegen_error(op, location.clone()))?,
+                )
+            }
             Statement::LiteralTimeOfDay {
                 hour,
                 min,
                 sec,
-                milli,
+                nano,
+                is_long,
                 location,
-            } => self.llvm.create_const_int(
-                self.index,
-                &Some(self.llvm.i64_type().into()),
-                calculate_date_time(1970, 1, 1, *hour, *min, *sec, *milli)
-                    .map_err(|op| CompileError::codegen_error(op, location.clone()))
-                    .map(|millis| format!("{}", millis))?
-                    .as_str(),
-            ),
+            } => {
+                let date_type = if *is_long {
+                    DateAndTimeType::LTimeOfDay
+                } else {
+                    DateAndTimeType::TimeOfDay
+                };
+                self.create_const_date_and_time(
+                    date_type,
+                    calculate_date_time(date_type, 1970, 1, 1, *hour, *min, *sec, *nano)
+                        .map_err(|op| CompileError::codegen_error(op, location.clone()))?,
+                )
+            }
             Statement::LiteralTime {
                 day,
                 hour,
@@ -1193,21 +3468,21 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 micro,
                 nano,
                 negative,
+                is_long,
                 ..
-            } => self.llvm.create_const_int(
-                self.index,
-                &Some(self.llvm.i64_type().into()),
-                format!(
-                    "{}",
-                    calculate_time_nano(
-                        *negative,
-                        calculate_dhm_time_seconds(*day, *hour, *min, *sec),
-                        *milli,
-                        *micro,
-                        *nano
-                    )
-                )
-                .as_str(),
+            } => self.create_const_date_and_time(
+                if *is_long {
+                    DateAndTimeType::LTime
+                } else {
+                    DateAndTimeType::Time
+                },
+                calculate_time_nano(
+                    *negative,
+                    calculate_dhm_time_seconds(*day, *hour, *min, *sec),
+                    *milli,
+                    *micro,
+                    *nano,
+                ),
             ),
             Statement::LiteralReal { value, .. } => {
                 self.llvm
@@ -1238,6 +3513,26 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         }
     }
 
+    /// creates a constant value of the given date and time type
+    ///
+    /// - `date_type` the date and time type of the constant
+    /// - `value` the value in the type's resolution
+    fn create_const_date_and_time(
+        &self,
+        date_type: DateAndTimeType,
+        value: i64,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let (_, value) = self.llvm.create_const_int(
+            self.index,
+            &Some(self.llvm.i64_type().into()),
+            value.to_string().as_str(),
+        )?;
+        Ok((
+            self.index.get_type_information(date_type.get_type_name())?,
+            value,
+        ))
+    }
+
     /// generates a struct literal value with the given value assignments (ExpressionList)
     fn generate_literal_struct(
         &self,
@@ -1254,7 +3549,7 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 let generated_type = self.llvm_index.get_associated_type(struct_name)?;
                 let mut uninitialized_members: HashSet<&str> =
                     member_names.iter().map(|it| it.as_str()).collect();
-                let mut member_values: Vec<(u32, BasicValueEnum<'a>)> = Vec::new();
+                let mut member_values: Vec<(&VariableIndexEntry, BasicValueEnum<'a>)> = Vec::new();
                 for assignment in flatten_expression_list(assignments) {
                     if let Statement::Assignment { left, right } = assignment {
                         if let Statement::Reference {
@@ -1272,15 +3567,13 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                                     )
                                 })?;
 
-                            let index_in_parent = member.get_location_in_parent();
-
                             let typed_generator = self.morph_to_typed(
                                 &self.index.get_type_information(member.get_type_name())?,
                             );
                             let (_, value) = typed_generator.generate_expression(right)?;
 
                             uninitialized_members.remove(member.get_name());
-                            member_values.push((index_in_parent, value));
+                            member_values.push((member, value));
                         } else {
                             return Err(CompileError::codegen_error(
                                 "struct member lvalue required as left operand of assignment"
@@ -1306,8 +3599,6 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                             )
                         })?;
 
-                    let index_in_parent = member.get_location_in_parent();
-
                     let initial_value = self
                         .llvm_index
                         .find_associated_variable_value(&member.get_qualified_name())
@@ -1318,24 +3609,18 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                         })
                         .unwrap();
 
-                    member_values.push((index_in_parent, initial_value));
+                    member_values.push((member, initial_value));
                 }
-                if member_values.len() == struct_type.count_fields() as usize {
-                    member_values.sort_by(|(a, _), (b, _)| a.cmp(b));
-                    let ordered_values: Vec<BasicValueEnum<'a>> =
-                        member_values.iter().map(|(_, v)| *v).collect();
-
+                if member_values.len() == member_names.len() {
                     return Ok((
                         type_info.clone(),
-                        struct_type
-                            .const_named_struct(ordered_values.as_slice())
-                            .as_basic_value_enum(),
+                        self.create_struct_value(struct_type, &member_values),
                     ));
                 } else {
                     return Err(CompileError::codegen_error(
                         format!(
                             "Expected {} fields for Struct {}, but found {}.",
-                            struct_type.count_fields(),
+                            member_names.len(),
                             struct_name,
                             member_values.len()
                         ),
@@ -1353,6 +3638,40 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
         ))
     }
 
+    /// builds a constant of the given struct type from the constant values of all of its members.
+    /// Padding fields are zero, BIT members sharing a byte are combined into that byte
+    fn create_struct_value(
+        &self,
+        struct_type: StructType<'a>,
+        member_values: &[(&VariableIndexEntry, BasicValueEnum<'a>)],
+    ) -> BasicValueEnum<'a> {
+        let mut fields: Vec<BasicValueEnum<'a>> = struct_type
+            .get_field_types()
+            .into_iter()
+            .map(struct_generator::get_default_for)
+            .collect();
+        for (member, value) in member_values {
+            let location = self.get_member_location(member);
+            let field = &mut fields[location.field as usize];
+            *field = match location.bit {
+                Some(bit) => {
+                    let byte = field.into_int_value();
+                    let is_set = value
+                        .into_int_value()
+                        .get_zero_extended_constant()
+                        .map_or(false, |it| it & 1 == 1) as u64;
+                    let bits =
+                        byte.get_zero_extended_constant().unwrap_or_default() | is_set << bit;
+                    byte.get_type().const_int(bits, false).into()
+                }
+                None => *value,
+            };
+        }
+        struct_type
+            .const_named_struct(fields.as_slice())
+            .as_basic_value_enum()
+    }
+
     /// generates an array literal with the given optional elements (represented as an ExpressionList)
     fn generate_literal_array(
         &self,
@@ -1374,137 +3693,1176 @@ impl<'a, 'b> ExpressionCodeGenerator<'a, 'b> {
                 }
             }
         }
-        Err(CompileError::codegen_error(
-            "Internal error when generating Array literal: unknown inner array-type.".to_string(),
-            location.clone(),
-        ))
+        Err(CompileError::codegen_error(
+            "Internal error when generating Array literal: unknown inner array-type.".to_string(),
+            location.clone(),
+        ))
+    }
+
+    /// constructs an ArrayValue (returned as a BasicValueEnum) of the given element-literals constructing an array-value of the
+    /// type described by inner_array_type.
+    ///
+    /// passing an epxression-lists with LiteralIntegers and inner_array_type is INT-description will return an
+    /// i16-array-value
+    fn generate_literal_array_value(
+        &self,
+        elements: Vec<&Statement>,
+        inner_array_type: &DataTypeInformation,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let element_expression_gen = self.morph_to_typed(inner_array_type);
+        let llvm_type = self
+            .llvm_index
+            .get_associated_type(inner_array_type.get_name())?;
+
+        let mut v = Vec::new();
+        for e in elements {
+            //generate with correct type hint
+            let (_, value) = element_expression_gen.generate_literal(e)?;
+            v.push(value.as_basic_value_enum());
+        }
+
+        //TODO Validation: fail with compile-error if value cannot be converted into... correctly
+        let array_value = match llvm_type {
+            BasicTypeEnum::ArrayType(_) => llvm_type.into_array_type().const_array(
+                v.iter()
+                    .map(|it| it.into_array_value())
+                    .collect::<Vec<ArrayValue>>()
+                    .as_slice(),
+            ),
+            BasicTypeEnum::FloatType(_) => llvm_type.into_float_type().const_array(
+                v.iter()
+                    .map(|it| it.into_float_value())
+                    .collect::<Vec<FloatValue>>()
+                    .as_slice(),
+            ),
+            BasicTypeEnum::IntType(_) => llvm_type.into_int_type().const_array(
+                v.iter()
+                    .map(|it| it.into_int_value())
+                    .collect::<Vec<IntValue>>()
+                    .as_slice(),
+            ),
+            BasicTypeEnum::PointerType(_) => llvm_type.into_pointer_type().const_array(
+                v.iter()
+                    .map(|it| it.into_pointer_value())
+                    .collect::<Vec<PointerValue>>()
+                    .as_slice(),
+            ),
+            BasicTypeEnum::StructType(_) => llvm_type.into_struct_type().const_array(
+                v.iter()
+                    .map(|it| it.into_struct_value())
+                    .collect::<Vec<StructValue>>()
+                    .as_slice(),
+            ),
+            BasicTypeEnum::VectorType(_) => llvm_type.into_vector_type().const_array(
+                v.iter()
+                    .map(|it| it.into_vector_value())
+                    .collect::<Vec<VectorValue>>()
+                    .as_slice(),
+            ),
+        };
+        Ok(array_value.as_basic_value_enum())
+    }
+
+    /// generates a phi-expression (&& or || expression) with respect to short-circuit evaluation
+    /// if the left side is not a BOOL, the expression is generated as a bitwise operation
+    ///
+    /// - `operator` AND or OR
+    /// - `left` the left side of the expression
+    /// - `right` the right side of the expression
+    pub fn generate_short_circuit_boolean_expression(
+        &self,
+        operator: &Operator,
+        left: &Statement,
+        right: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let function = self.get_function_context(left)?.function;
+
+        let (left_type, left_value) = self.generate_expression(left)?;
+        if !self.is_bool_type(&left_type) {
+            //ANY_BIT values (e.g. WORD-masks) are combined bit by bit, there is nothing to short-circuit
+            return self.generate_bitwise_binary_expression(
+                operator,
+                left,
+                (left_type, left_value),
+                right,
+            );
+        }
+        let final_left_block = builder.get_insert_block().unwrap();
+        let right_branch = self.llvm.context.append_basic_block(function, "");
+        let continue_branch = self.llvm.context.append_basic_block(function, "");
+
+        let left_llvm_type = self.llvm_index.get_associated_type(left_type.get_name())?;
+        //Compare left to 0
+        let lhs = builder.build_int_compare(
+            IntPredicate::NE,
+            left_value.into_int_value(),
+            left_llvm_type.into_int_type().const_int(0, false),
+            "",
+        );
+        match operator {
+            Operator::Or => builder.build_conditional_branch(lhs, continue_branch, right_branch),
+            Operator::And => builder.build_conditional_branch(lhs, right_branch, continue_branch),
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!("Cannot generate phi-expression for operator {:}", operator),
+                    left.get_location(),
+                ))
+            }
+        };
+
+        builder.position_at_end(right_branch);
+        let (right_type, right_value) = self.generate_expression(right)?;
+        let final_right_block = builder.get_insert_block().unwrap();
+        let rhs = right_value;
+        builder.build_unconditional_branch(continue_branch);
+
+        builder.position_at_end(continue_branch);
+        //Generate phi
+        let target_type = if left_type.get_size() > right_type.get_size() {
+            left_type
+        } else {
+            right_type
+        };
+        let llvm_target_type = self
+            .llvm_index
+            .get_associated_type(target_type.get_name())?;
+        let phi_value = builder.build_phi(llvm_target_type, "");
+        phi_value.add_incoming(&[
+            (&left_value.into_int_value(), final_left_block),
+            (&rhs, final_right_block),
+        ]);
+
+        Ok((target_type, phi_value.as_basic_value()))
+    }
+
+    /// generates a bitwise AND or OR without short-circuit evaluation
+    ///
+    /// - `operator` AND or OR
+    /// - `left` the left side of the expression
+    /// - `left_type_and_value` the already generated left side of the expression
+    /// - `right` the right side of the expression
+    fn generate_bitwise_binary_expression(
+        &self,
+        operator: &Operator,
+        left: &Statement,
+        left_type_and_value: TypeAndValue<'a>,
+        right: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let right_type_and_value = self.generate_expression(right)?;
+        for (data_type, statement) in &[
+            (&left_type_and_value.0, left),
+            (&right_type_and_value.0, right),
+        ] {
+            let is_int = self
+                .index
+                .find_effective_type(data_type)
+                .map(DataTypeInformation::is_int)
+                .unwrap_or(false);
+            if !is_int {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "invalid type {}, cannot generate bitwise {}",
+                        data_type.get_name(),
+                        operator
+                    ),
+                    statement.get_location(),
+                ));
+            }
+        }
+        let (common_type, left_value, right_value) = promote_if_needed(
+            self.llvm.context,
+            &self.llvm.builder,
+            &left_type_and_value,
+            &right_type_and_value,
+            self.index,
+            self.llvm_index,
+        );
+        self.create_llvm_int_binary_expression(
+            operator,
+            left_value,
+            right_value,
+            &common_type,
+            right,
+        )
+    }
+
+    /// generates a binary expression with at least one date or time operand. Only the
+    /// combinations defined by IEC 61131-3 are allowed:
+    /// - comparisons of two values of the same type
+    /// - TIME + TIME, TIME - TIME -> TIME (LTIME likewise)
+    /// - DT + TIME, DT - TIME -> DT and TOD + TIME, TOD - TIME -> TOD (LDT, LTOD with LTIME likewise)
+    /// - DT - DT, TOD - TOD, DATE - DATE -> TIME (LDT, LTOD, LDATE -> LTIME)
+    /// - TIME * ANY_NUM, ANY_NUM * TIME, TIME / ANY_NUM -> TIME (LTIME likewise)
+    ///
+    /// short and long types cannot be mixed, they need to be converted explicitly (e.g. `TIME_TO_LTIME`)
+    ///
+    /// - `operator` the binary operator
+    /// - `left` the generated left side of the expression
+    /// - `right` the generated right side of the expression
+    /// - `expression` the binary expression, used to report errors
+    fn generate_date_and_time_binary_expression(
+        &self,
+        operator: &Operator,
+        left: TypeAndValue<'a>,
+        right: TypeAndValue<'a>,
+        expression: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let (left_type, left_value) = left;
+        let (right_type, right_value) = right;
+        match (
+            left_type.get_date_and_time_type(),
+            operator,
+            right_type.get_date_and_time_type(),
+        ) {
+            (
+                Some(left_date_type),
+                Operator::Equal
+                | Operator::NotEqual
+                | Operator::Less
+                | Operator::Greater
+                | Operator::LessOrEqual
+                | Operator::GreaterOrEqual,
+                Some(right_date_type),
+            ) if left_date_type == right_date_type => self.create_llvm_int_binary_expression(
+                operator,
+                left_value,
+                right_value,
+                &left_type,
+                expression,
+            ),
+            (Some(left_date_type), Operator::Plus | Operator::Minus, Some(right_date_type))
+                if left_date_type.is_duration() && left_date_type == right_date_type =>
+            {
+                self.create_llvm_int_binary_expression(
+                    operator,
+                    left_value,
+                    right_value,
+                    &left_type,
+                    expression,
+                )
+            }
+            (
+                Some(left_date_type @ DateAndTimeType::DateAndTime)
+                | Some(left_date_type @ DateAndTimeType::LDateAndTime)
+                | Some(left_date_type @ DateAndTimeType::TimeOfDay)
+                | Some(left_date_type @ DateAndTimeType::LTimeOfDay),
+                Operator::Plus | Operator::Minus,
+                Some(right_date_type),
+            ) if left_date_type.get_duration() == right_date_type => {
+                //durations are stored in nanoseconds, DT and TOD in milliseconds
+                let resolution = left_date_type.get_resolution();
+                let duration = if resolution > 1 {
+                    self.llvm
+                        .builder
+                        .build_int_signed_div(
+                            right_value.into_int_value(),
+                            self.llvm.i64_type().const_int(resolution, false),
+                            "",
+                        )
+                        .into()
+                } else {
+                    right_value
+                };
+                self.create_llvm_int_binary_expression(
+                    operator, left_value, duration, &left_type, expression,
+                )
+            }
+            (Some(left_date_type), Operator::Minus, Some(right_date_type))
+                if left_date_type == right_date_type =>
+            {
+                let (_, difference) = self.create_llvm_int_binary_expression(
+                    operator,
+                    left_value,
+                    right_value,
+                    &left_type,
+                    expression,
+                )?;
+                let resolution = left_date_type.get_resolution();
+                let nanos = if resolution > 1 {
+                    self.llvm
+                        .builder
+                        .build_int_mul(
+                            difference.into_int_value(),
+                            self.llvm.i64_type().const_int(resolution, false),
+                            "tmpVar",
+                        )
+                        .into()
+                } else {
+                    difference
+                };
+                Ok((
+                    self.index
+                        .get_type_information(left_date_type.get_duration().get_type_name())?,
+                    nanos,
+                ))
+            }
+            (Some(left_date_type), Operator::Multiplication | Operator::Division, None)
+                if left_date_type.is_duration() && right_type.is_int() =>
+            {
+                let factor = cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    &left_type,
+                    right_value,
+                    &right_type,
+                    expression,
+                )?;
+                self.create_llvm_int_binary_expression(
+                    operator, left_value, factor, &left_type, expression,
+                )
+            }
+            (Some(left_date_type), Operator::Multiplication | Operator::Division, None)
+                if left_date_type.is_duration() && right_type.is_float() =>
+            {
+                let lreal = self.index.get_type_information("LREAL")?;
+                let time = cast_if_needed(
+                    self.llvm, self.index, &lreal, left_value, &left_type, expression,
+                )?;
+                let factor = cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    &lreal,
+                    right_value,
+                    &right_type,
+                    expression,
+                )?;
+                let (_, result) =
+                    self.create_llvm_float_binary_expression(operator, time, factor, &lreal);
+                let result = cast_if_needed(
+                    self.llvm, self.index, &left_type, result, &lreal, expression,
+                )?;
+                Ok((left_type, result))
+            }
+            (None, Operator::Multiplication, Some(right_date_type))
+                if right_date_type.is_duration() && left_type.is_numerical() =>
+            {
+                //the multiplication is commutative, generate it as TIME * ANY_NUM
+                self.generate_date_and_time_binary_expression(
+                    operator,
+                    (right_type, right_value),
+                    (left_type, left_value),
+                    expression,
+                )
+            }
+            _ => Err(CompileError::codegen_error(
+                format!(
+                    "invalid types, cannot generate {} {} {}",
+                    left_type.get_name(),
+                    operator,
+                    right_type.get_name()
+                ),
+                expression.get_location(),
+            )),
+        }
+    }
+
+    /// returns true if the given type is a BOOL (or an alias of it)
+    fn is_bool_type(&self, data_type: &DataTypeInformation) -> bool {
+        matches!(
+            self.index.find_effective_type(data_type),
+            Some(DataTypeInformation::Integer { size: 1, .. })
+        )
+    }
+
+    /// generates the call of a builtin function inline
+    ///
+    /// - `builtin` the builtin to generate
+    /// - `operator` the call's operator, used to report errors
+    /// - `parameters` the call's optional parameters
+    fn generate_builtin_call(
+        &self,
+        builtin: BuiltIn,
+        operator: &Statement,
+        parameters: &Option<Statement>,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let arguments = self.get_builtin_arguments(builtin, operator, parameters)?;
+        match builtin {
+            BuiltIn::Shl | BuiltIn::Shr | BuiltIn::Rol | BuiltIn::Ror => {
+                self.generate_shift_builtin(builtin, arguments[0], arguments[1])
+            }
+            BuiltIn::Sel => self.generate_sel_builtin(arguments[0], &arguments[1..]),
+            BuiltIn::Mux => self.generate_mux_builtin(arguments[0], &arguments[1..]),
+            BuiltIn::Max | BuiltIn::Min => {
+                let (data_type, values) =
+                    self.generate_builtin_inputs(builtin, arguments.as_slice())?;
+                let mut result = values[0];
+                for value in values.into_iter().skip(1) {
+                    result = self.build_min_max(
+                        builtin == BuiltIn::Max,
+                        &data_type,
+                        result,
+                        value,
+                        operator,
+                    )?;
+                }
+                Ok((data_type, result))
+            }
+            BuiltIn::Limit => {
+                let (data_type, values) =
+                    self.generate_builtin_inputs(builtin, arguments.as_slice())?;
+                let lower_limited =
+                    self.build_min_max(true, &data_type, values[1], values[0], operator)?;
+                let result =
+                    self.build_min_max(false, &data_type, lower_limited, values[2], operator)?;
+                Ok((data_type, result))
+            }
+            BuiltIn::Move => {
+                let (data_type, mut values) = self.generate_builtin_inputs(builtin, &arguments)?;
+                Ok((data_type, values.remove(0)))
+            }
+            BuiltIn::LowerBound | BuiltIn::UpperBound => {
+                self.generate_bound_builtin(builtin, arguments[0], arguments[1])
+            }
+            BuiltIn::New => self.generate_new_builtin(arguments[0], arguments.get(1).copied()),
+            BuiltIn::Delete => self.generate_delete_builtin(arguments[0]),
+            BuiltIn::IsValidRef => self.generate_is_valid_ref_builtin(arguments[0]),
+        }
+    }
+
+    /// generates `__NEW(T)` or `__NEW(T, N)`. The memory for one (or N) instances of T is allocated
+    /// through the allocator hook, every instance is initialized with T's initial value (this
+    /// initializes the members of function block instances). Returns a POINTER TO T that is 0 if
+    /// the allocation failed
+    ///
+    /// - `type_reference` the name of the allocated type
+    /// - `count` the optional number of allocated instances
+    fn generate_new_builtin(
+        &self,
+        type_reference: &Statement,
+        count: Option<&Statement>,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let type_name = match type_reference {
+            Statement::Reference { name, .. } if self.index.find_type(name).is_some() => name,
+            _ => {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "{} expects the name of a type as its first argument",
+                        BuiltIn::New.get_name()
+                    ),
+                    type_reference.get_location(),
+                ))
+            }
+        };
+        let llvm_type = self.llvm_index.get_associated_type(type_name)?;
+        let instance_size = llvm_type.size_of().ok_or_else(|| {
+            CompileError::codegen_error(
+                format!("Cannot allocate {}, its size is unknown", type_name),
+                type_reference.get_location(),
+            )
+        })?;
+        let count = count
+            .map(|count| {
+                let lint = self.index.get_type_information("LINT")?;
+                let (count_type, count_value) = self.generate_expression(count)?;
+                cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    &lint,
+                    count_value,
+                    &count_type,
+                    count,
+                )
+                .map(BasicValueEnum::into_int_value)
+            })
+            .transpose()?;
+        let size = match count {
+            Some(count) => builder.build_int_mul(instance_size, count, "size"),
+            None => instance_size,
+        };
+
+        //allocate the memory
+        let alloc_hook = self.find_allocator_hook(ALLOC_HOOK_FN, type_reference)?;
+        let size_type = alloc_hook
+            .get_type()
+            .get_param_types()
+            .first()
+            .filter(|it| it.is_int_type())
+            .map(|it| it.into_int_type())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "{} must take the size to allocate as its input",
+                        ALLOC_HOOK_FN
+                    ),
+                    type_reference.get_location(),
+                )
+            })?;
+        let memory = builder
+            .build_call(
+                alloc_hook,
+                &[builder.build_int_cast(size, size_type, "").into()],
+                "memory",
+            )
+            .try_as_basic_value()
+            .left()
+            .filter(|it| it.is_pointer_value())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("{} must return a pointer", ALLOC_HOOK_FN),
+                    type_reference.get_location(),
+                )
+            })?;
+        let instances = builder.build_pointer_cast(
+            memory.into_pointer_value(),
+            llvm_type.ptr_type(AddressSpace::Generic),
+            "instances",
+        );
+
+        //initialize the allocated instances if the allocation succeeded
+        let function = self.get_function_context(type_reference)?.function;
+        let initial_value = self
+            .llvm_index
+            .find_associated_initial_value(type_name)
+            .unwrap_or_else(|| struct_generator::get_default_for(llvm_type));
+        let allocated = builder.build_is_not_null(instances, "allocated");
+        let allocation_block = builder.get_insert_block().unwrap();
+        let init_block = self.llvm.context.append_basic_block(function, "new_init");
+        let continue_block = self
+            .llvm
+            .context
+            .append_basic_block(function, "new_continue");
+        builder.build_conditional_branch(allocated, init_block, continue_block);
+        builder.position_at_end(init_block);
+        match count {
+            Some(count) => {
+                let body_block = self.llvm.context.append_basic_block(function, "new_body");
+                let i64_type = self.llvm.i64_type();
+                let position = builder.build_phi(i64_type, "position");
+                let more = builder.build_int_compare(
+                    IntPredicate::SLT,
+                    position.as_basic_value().into_int_value(),
+                    count,
+                    "",
+                );
+                builder.build_conditional_branch(more, body_block, continue_block);
+
+                builder.position_at_end(body_block);
+                let instance = self.llvm.load_array_element(
+                    instances,
+                    &[position.as_basic_value().into_int_value()],
+                    "instance",
+                )?;
+                builder.build_store(instance, initial_value);
+                let next = builder.build_int_add(
+                    position.as_basic_value().into_int_value(),
+                    i64_type.const_int(1, false),
+                    "",
+                );
+                builder.build_unconditional_branch(init_block);
+                position.add_incoming(&[
+                    (&i64_type.const_zero(), allocation_block),
+                    (&next, body_block),
+                ]);
+            }
+            None => {
+                builder.build_store(instances, initial_value);
+                builder.build_unconditional_branch(continue_block);
+            }
+        }
+        builder.position_at_end(continue_block);
+
+        let pointer_type = DataTypeInformation::Pointer {
+            name: format!("POINTER TO {}", type_name),
+            inner_type_name: type_name.to_string(),
+            auto_deref: false,
+        };
+        Ok((pointer_type, instances.into()))
+    }
+
+    /// generates `__ISVALIDREF(R)` which is TRUE if the REFERENCE R is bound to a variable
+    ///
+    /// - `reference` the checked reference
+    fn generate_is_valid_ref_builtin(
+        &self,
+        reference: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let lvalue = self.generate_reference_lvalue(reference)?;
+        let (_, value) = self.llvm.load_pointer(&lvalue, "reference");
+        let is_valid = self
+            .llvm
+            .builder
+            .build_is_not_null(value.into_pointer_value(), "is_valid");
+        Ok((self.index.get_type_information("BOOL")?, is_valid.into()))
+    }
+
+    /// generates `__DELETE(P)` which releases the memory P points to through the allocator hook
+    /// and sets P to 0
+    ///
+    /// - `pointer` the pointer to the released memory
+    fn generate_delete_builtin(
+        &self,
+        pointer: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let lvalue = self.generate_element_pointer(pointer)?;
+        if !is_raw_pointer(lvalue.get_type_information()) {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "{} expects a pointer, but found {}",
+                    BuiltIn::Delete.get_name(),
+                    lvalue.get_type_information().get_name()
+                ),
+                pointer.get_location(),
+            ));
+        }
+        let free_hook = self.find_allocator_hook(FREE_HOOK_FN, pointer)?;
+        let memory_type = free_hook
+            .get_type()
+            .get_param_types()
+            .first()
+            .filter(|it| it.is_pointer_type())
+            .map(|it| it.into_pointer_type())
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "{} must take the released pointer as its input",
+                        FREE_HOOK_FN
+                    ),
+                    pointer.get_location(),
+                )
+            })?;
+        let (_, value) = self.llvm.load_pointer(&lvalue, "deref");
+        let value = value.into_pointer_value();
+        let memory = builder.build_pointer_cast(value, memory_type, "memory");
+        builder.build_call(free_hook, &[memory.into()], "");
+        //the pointer must not point to the released memory anymore
+        self.llvm
+            .store_pointer(&lvalue, value.get_type().const_null().into());
+
+        // like the call of a function without a result, we return a null pointer
+        let void_type = self.index.get_type_information("__VOID")?;
+        let result = get_llvm_int_type(self.llvm.context, 16, "INT")?
+            .ptr_type(AddressSpace::Const)
+            .const_null();
+        Ok((void_type, result.into()))
+    }
+
+    /// returns the allocator hook with the given name. Hooks declared by the user need to use
+    /// the C calling convention, otherwise the compiler's default hook is returned
+    ///
+    /// - `name` the name of the hook
+    /// - `context` the statement used to report a possible CompileError on
+    fn find_allocator_hook(
+        &self,
+        name: &str,
+        context: &Statement,
+    ) -> Result<FunctionValue<'a>, CompileError> {
+        if let Some(implementation) = self.index.find_implementation(name) {
+            if implementation.get_calling_convention() != CallingConvention::C {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "The allocator hook {} must use the C calling convention",
+                        name
+                    ),
+                    context.get_location(),
+                ));
+            }
+        }
+        self.llvm_index
+            .find_associated_implementation(name)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!("Missing declaration of {}", name),
+                    context.get_location(),
+                )
+            })
     }
 
-    /// constructs an ArrayValue (returned as a BasicValueEnum) of the given element-literals constructing an array-value of the
-    /// type described by inner_array_type.
+    /// generates LOWER_BOUND or UPPER_BOUND of an array's dimension. The dimension needs to be
+    /// a constant, the bounds of arrays with a fixed length are known at compile time
     ///
-    /// passing an epxression-lists with LiteralIntegers and inner_array_type is INT-description will return an
-    /// i16-array-value
-    fn generate_literal_array_value(
+    /// - `builtin` either LOWER_BOUND or UPPER_BOUND
+    /// - `array` the array to get the bound from
+    /// - `dimension` the 1-based dimension
+    fn generate_bound_builtin(
         &self,
-        elements: Vec<&Statement>,
-        inner_array_type: &DataTypeInformation,
-    ) -> Result<BasicValueEnum<'a>, CompileError> {
-        let element_expression_gen = self.morph_to_typed(inner_array_type);
-        let llvm_type = self
-            .llvm_index
-            .get_associated_type(inner_array_type.get_name())?;
-
-        let mut v = Vec::new();
-        for e in elements {
-            //generate with correct type hint
-            let (_, value) = element_expression_gen.generate_literal(e)?;
-            v.push(value.as_basic_value_enum());
-        }
+        builtin: BuiltIn,
+        array: &Statement,
+        dimension: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let upper = builtin == BuiltIn::UpperBound;
+        let dint = self.index.get_type_information("DINT")?;
+        let array_pointer = self.generate_element_pointer(array)?;
+        let array_dimensions = match array_pointer.get_type_information() {
+            DataTypeInformation::Array { dimensions, .. } => dimensions.len(),
+            DataTypeInformation::VariableLengthArray { dimensions, .. } => *dimensions as usize,
+            data_type => {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "{} expects an array, but found {}",
+                        builtin.get_name(),
+                        data_type.get_name()
+                    ),
+                    array.get_location(),
+                ))
+            }
+        };
+        let dimension_number = evaluate_constant_int(dimension)
+            .ok()
+            .filter(|it| *it >= 1 && *it as usize <= array_dimensions)
+            .ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "{} expects a constant dimension between 1 and {}",
+                        builtin.get_name(),
+                        array_dimensions
+                    ),
+                    dimension.get_location(),
+                )
+            })? as usize;
 
-        //TODO Validation: fail with compile-error if value cannot be converted into... correctly
-        let array_value = match llvm_type {
-            BasicTypeEnum::ArrayType(_) => llvm_type.into_array_type().const_array(
-                v.iter()
-                    .map(|it| it.into_array_value())
-                    .collect::<Vec<ArrayValue>>()
-                    .as_slice(),
-            ),
-            BasicTypeEnum::FloatType(_) => llvm_type.into_float_type().const_array(
-                v.iter()
-                    .map(|it| it.into_float_value())
-                    .collect::<Vec<FloatValue>>()
-                    .as_slice(),
-            ),
-            BasicTypeEnum::IntType(_) => llvm_type.into_int_type().const_array(
-                v.iter()
-                    .map(|it| it.into_int_value())
-                    .collect::<Vec<IntValue>>()
-                    .as_slice(),
-            ),
-            BasicTypeEnum::PointerType(_) => llvm_type.into_pointer_type().const_array(
-                v.iter()
-                    .map(|it| it.into_pointer_value())
-                    .collect::<Vec<PointerValue>>()
-                    .as_slice(),
-            ),
-            BasicTypeEnum::StructType(_) => llvm_type.into_struct_type().const_array(
-                v.iter()
-                    .map(|it| it.into_struct_value())
-                    .collect::<Vec<StructValue>>()
-                    .as_slice(),
-            ),
-            BasicTypeEnum::VectorType(_) => llvm_type.into_vector_type().const_array(
-                v.iter()
-                    .map(|it| it.into_vector_value())
-                    .collect::<Vec<VectorValue>>()
-                    .as_slice(),
+        let value = match array_pointer.get_type_information() {
+            DataTypeInformation::Array { dimensions, .. } => {
+                let dimension = &dimensions[dimension_number - 1];
+                let bound = if upper {
+                    dimension.end_offset
+                } else {
+                    dimension.start_offset
+                };
+                self.llvm.i32_type().const_int(bound as u64, true)
+            }
+            _ => self.load_variable_length_array_bound(
+                array_pointer.ptr_value,
+                (dimension_number - 1) as u32,
+                upper,
             ),
         };
-        Ok(array_value.as_basic_value_enum())
+        Ok((dint, value.into()))
     }
 
-    /// generates a phi-expression (&& or || expression) with respect to short-circuit evaluation
+    /// generates the given inputs of a builtin and casts them to their common type
+    /// inputs need to be ANY_ELEMENTARY, numerical inputs are promoted to the biggest type,
+    /// strings of the same encoding are extended to the longest string,
+    /// all other inputs need to be of the same type
     ///
-    /// - `operator` AND or OR
-    /// - `left` the left side of the expression
-    /// - `right` the right side of the expression
-    pub fn generate_short_circuit_boolean_expression(
+    /// returns the common type and the generated values
+    fn generate_builtin_inputs(
         &self,
-        operator: &Operator,
-        left: &Statement,
-        right: &Statement,
-    ) -> Result<TypeAndValue<'a>, CompileError> {
-        let builder = &self.llvm.builder;
-        let function = self.get_function_context(left)?.function;
+        builtin: BuiltIn,
+        inputs: &[&Statement],
+    ) -> Result<(DataTypeInformation, Vec<BasicValueEnum<'a>>), CompileError> {
+        let mut generated_inputs = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let (data_type, value) = self.generate_expression(input)?;
+            let effective_type = self
+                .index
+                .find_effective_type(&data_type)
+                .cloned()
+                .unwrap_or(data_type);
+            if !matches!(
+                effective_type,
+                DataTypeInformation::Integer { .. }
+                    | DataTypeInformation::Float { .. }
+                    | DataTypeInformation::String { .. }
+            ) {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "{} expects ANY_ELEMENTARY inputs, but found {}",
+                        builtin.get_name(),
+                        effective_type.get_name()
+                    ),
+                    input.get_location(),
+                ));
+            }
+            generated_inputs.push((effective_type, value, *input));
+        }
 
-        let right_branch = self.llvm.context.append_basic_block(function, "");
-        let continue_branch = self.llvm.context.append_basic_block(function, "");
+        let mut common_type = generated_inputs[0].0.clone();
+        for (data_type, _, input) in generated_inputs.iter().skip(1) {
+            common_type = if common_type.is_numerical() && data_type.is_numerical() {
+                get_bigger_type(&common_type, data_type)
+            } else if &common_type == data_type {
+                common_type
+            } else if let Some(string_type) = get_longer_string_type(&common_type, data_type) {
+                string_type
+            } else {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "{} expects inputs of the same type, but found {} and {}",
+                        builtin.get_name(),
+                        common_type.get_name(),
+                        data_type.get_name()
+                    ),
+                    input.get_location(),
+                ));
+            };
+        }
 
-        let (left_type, left_value) = self.generate_expression(left)?;
-        let final_left_block = builder.get_insert_block().unwrap();
-        let left_llvm_type = self.llvm_index.get_associated_type(left_type.get_name())?;
-        //Compare left to 0
-        let lhs = builder.build_int_compare(
-            IntPredicate::NE,
-            left_value.into_int_value(),
-            left_llvm_type.into_int_type().const_int(0, false),
+        let values = generated_inputs
+            .iter()
+            .map(|(data_type, value, input)| {
+                cast_if_needed(
+                    self.llvm,
+                    self.index,
+                    &common_type,
+                    *value,
+                    data_type,
+                    input,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        //all strings need the same llvm type to select one of them
+        let values = match values.iter().filter_map(get_string_byte_length).max() {
+            Some(length) if matches!(common_type, DataTypeInformation::String { .. }) => values
+                .into_iter()
+                .map(|value| self.extend_string(value, length))
+                .collect(),
+            _ => values,
+        };
+        Ok((common_type, values))
+    }
+
+    /// copies the given string value into a zero-filled buffer of the given length in bytes
+    fn extend_string(&self, value: BasicValueEnum<'a>, length: u32) -> BasicValueEnum<'a> {
+        let builder = &self.llvm.builder;
+        let buffer_type = self.llvm.context.i8_type().array_type(length);
+        if value.get_type() == buffer_type.as_basic_type_enum() {
+            return value;
+        }
+        let buffer = self
+            .llvm
+            .create_local_variable("", &buffer_type.as_basic_type_enum());
+        builder.build_store(buffer, buffer_type.const_zero());
+        let prefix = builder.build_pointer_cast(
+            buffer,
+            value.get_type().ptr_type(AddressSpace::Generic),
             "",
         );
-        match operator {
-            Operator::Or => builder.build_conditional_branch(lhs, continue_branch, right_branch),
-            Operator::And => builder.build_conditional_branch(lhs, right_branch, continue_branch),
+        builder.build_store(prefix, value);
+        builder.build_load(buffer, "")
+    }
+
+    /// generates a `select` of the bigger (`is_max`) or the smaller of the given values,
+    /// strings are ordered lexicographically
+    fn build_min_max(
+        &self,
+        is_max: bool,
+        data_type: &DataTypeInformation,
+        left: BasicValueEnum<'a>,
+        right: BasicValueEnum<'a>,
+        expression: &Statement,
+    ) -> Result<BasicValueEnum<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let right_is_selected = match data_type {
+            DataTypeInformation::String { encoding, .. } => {
+                let bytes_per_char = encoding.get_bytes_per_char();
+                if is_max {
+                    self.build_string_less_than(bytes_per_char, left, right, expression)?
+                } else {
+                    self.build_string_less_than(bytes_per_char, right, left, expression)?
+                }
+            }
+            DataTypeInformation::Float { .. } => builder.build_float_compare(
+                if is_max {
+                    FloatPredicate::OGT
+                } else {
+                    FloatPredicate::OLT
+                },
+                right.into_float_value(),
+                left.into_float_value(),
+                "",
+            ),
             _ => {
-                return Err(CompileError::codegen_error(
-                    format!("Cannot generate phi-expression for operator {:}", operator),
-                    left.get_location(),
-                ))
+                let signed = matches!(data_type, DataTypeInformation::Integer { signed: true, .. });
+                let predicate = match (is_max, signed) {
+                    (true, true) => IntPredicate::SGT,
+                    (true, false) => IntPredicate::UGT,
+                    (false, true) => IntPredicate::SLT,
+                    (false, false) => IntPredicate::ULT,
+                };
+                builder.build_int_compare(
+                    predicate,
+                    right.into_int_value(),
+                    left.into_int_value(),
+                    "",
+                )
             }
         };
+        Ok(builder.build_select(right_is_selected, right, left, "tmpVar"))
+    }
 
-        builder.position_at_end(right_branch);
-        let (right_type, right_value) = self.generate_expression(right)?;
-        let final_right_block = builder.get_insert_block().unwrap();
-        let rhs = right_value;
-        builder.build_unconditional_branch(continue_branch);
+    /// compares two strings character by character up to their terminating NUL and returns
+    /// true (i1) if `left` is ordered before `right`
+    fn build_string_less_than(
+        &self,
+        bytes_per_char: u32,
+        left: BasicValueEnum<'a>,
+        right: BasicValueEnum<'a>,
+        expression: &Statement,
+    ) -> Result<IntValue<'a>, CompileError> {
+        self.generate_string_scan(
+            bytes_per_char,
+            left,
+            right,
+            false,
+            |left_char, right_char| {
+                self.llvm
+                    .builder
+                    .build_int_compare(IntPredicate::ULT, left_char, right_char, "")
+            },
+            expression,
+        )
+    }
 
-        builder.position_at_end(continue_branch);
-        //Generate phi
-        let target_type = if left_type.get_size() > right_type.get_size() {
-            left_type
+    /// generates SEL(G, IN0, IN1) as a `select` on G
+    fn generate_sel_builtin(
+        &self,
+        selector: &Statement,
+        inputs: &[&Statement],
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let (selector_type, selector_value) = self.generate_expression(selector)?;
+        if !self.is_bool_type(&selector_type) {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "SEL expects a BOOL selector, but found {}",
+                    selector_type.get_name()
+                ),
+                selector.get_location(),
+            ));
+        }
+        let (data_type, values) = self.generate_builtin_inputs(BuiltIn::Sel, inputs)?;
+        let value = self.llvm.builder.build_select(
+            selector_value.into_int_value(),
+            values[1],
+            values[0],
+            "tmpVar",
+        );
+        Ok((data_type, value))
+    }
+
+    /// generates MUX(K, IN0, IN1, ...) as a chain of `select`s comparing K to every input's position
+    /// if K is out of range, the result is IN0
+    fn generate_mux_builtin(
+        &self,
+        selector: &Statement,
+        inputs: &[&Statement],
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let (selector_type, selector_value) = self.generate_expression(selector)?;
+        let is_int = self
+            .index
+            .find_effective_type(&selector_type)
+            .map(DataTypeInformation::is_int)
+            .unwrap_or(false);
+        if !is_int {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "MUX expects an ANY_INT selector, but found {}",
+                    selector_type.get_name()
+                ),
+                selector.get_location(),
+            ));
+        }
+        let (data_type, values) = self.generate_builtin_inputs(BuiltIn::Mux, inputs)?;
+        let selector_value = selector_value.into_int_value();
+        let builder = &self.llvm.builder;
+        let mut result = values[0];
+        for (position, value) in values.into_iter().enumerate().skip(1) {
+            let is_selected = builder.build_int_compare(
+                IntPredicate::EQ,
+                selector_value,
+                selector_value.get_type().const_int(position as u64, false),
+                "",
+            );
+            result = builder.build_select(is_selected, value, result, "tmpVar");
+        }
+        Ok((data_type, result))
+    }
+
+    /// returns the arguments of a builtin-call ordered by the builtin's parameters
+    /// formal (`SHL(IN := x, N := 2)`) and non-formal (`SHL(x, 2)`) arguments are supported
+    fn get_builtin_arguments<'s>(
+        &self,
+        builtin: BuiltIn,
+        operator: &Statement,
+        parameters: &'s Option<Statement>,
+    ) -> Result<Vec<&'s Statement>, CompileError> {
+        let required_parameters = builtin.get_parameter_names().len();
+        let mut arguments: Vec<Option<&Statement>> = vec![None; required_parameters];
+        let call_arguments = parameters
+            .as_ref()
+            .map(Statement::get_as_list)
+            .unwrap_or_default();
+        for (position, argument) in call_arguments.into_iter().enumerate() {
+            let (position, value) = match argument {
+                Statement::Assignment { left, right } => {
+                    let position = if let Statement::Reference { name, .. } = left.as_ref() {
+                        builtin.get_parameter_position(name)
+                    } else {
+                        None
+                    };
+                    let position = position.ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("unknown parameter {:?} for {}", left, builtin.get_name()),
+                            left.get_location(),
+                        )
+                    })?;
+                    (position, right.as_ref())
+                }
+                _ => (position, argument),
+            };
+            if position >= arguments.len() {
+                if !builtin.is_variadic() {
+                    return Err(CompileError::codegen_error(
+                        format!(
+                            "too many parameters for {}, expected {}",
+                            builtin.get_name(),
+                            required_parameters
+                        ),
+                        value.get_location(),
+                    ));
+                }
+                arguments.resize(position + 1, None);
+            }
+            arguments[position] = Some(value);
+        }
+        arguments
+            .into_iter()
+            .enumerate()
+            .filter(|(position, argument)| {
+                argument.is_some() || *position < builtin.get_required_parameter_count()
+            })
+            .map(|(position, argument)| {
+                argument.ok_or_else(|| {
+                    CompileError::codegen_error(
+                        format!(
+                            "missing parameter {} for {}",
+                            builtin.get_parameter_name(position),
+                            builtin.get_name()
+                        ),
+                        operator.get_location(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// generates the shift-builtins SHL, SHR, ROL and ROR
+    /// the operation is performed in the bit-width of the shifted value
+    ///
+    /// - `builtin` one of SHL, SHR, ROL, ROR
+    /// - `value` the value to shift (IN)
+    /// - `shift` the number of bits to shift (N)
+    fn generate_shift_builtin(
+        &self,
+        builtin: BuiltIn,
+        value: &Statement,
+        shift: &Statement,
+    ) -> Result<TypeAndValue<'a>, CompileError> {
+        let builder = &self.llvm.builder;
+        let (value_type, loaded_value) = self.generate_expression(value)?;
+        //BOOL is an ANY_BIT too, but there are no bits to shift
+        let is_bit_string = self
+            .index
+            .find_effective_type(&value_type)
+            .filter(|it| matches!(it, DataTypeInformation::Integer { size, .. } if *size > 1))
+            .map(|it| is_in_type_class("ANY_BIT", it))
+            .unwrap_or(false);
+        if !is_bit_string {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "{} expects an ANY_BIT value, but found {}",
+                    builtin.get_name(),
+                    value_type.get_name()
+                ),
+                value.get_location(),
+            ));
+        }
+        let (shift_type, shift_value) = self.generate_expression(shift)?;
+        if !self
+            .index
+            .find_effective_type(&shift_type)
+            .map(DataTypeInformation::is_int)
+            .unwrap_or(false)
+        {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "{} expects an ANY_INT shift-count, but found {}",
+                    builtin.get_name(),
+                    shift_type.get_name()
+                ),
+                shift.get_location(),
+            ));
+        }
+        let int_value = loaded_value.into_int_value();
+        let int_type = int_value.get_type();
+        let bit_width = int_type.get_bit_width() as u64;
+        let count = shift_value.into_int_value();
+        //shift in the width of the value, but range-check a wider shift-count before truncating it
+        let (count, shift_value) = if count.get_type().get_bit_width() as u64 > bit_width {
+            (count, builder.build_int_truncate(count, int_type, ""))
         } else {
-            right_type
+            let shift_value = cast_if_needed(
+                self.llvm,
+                self.index,
+                &value_type,
+                shift_value,
+                &shift_type,
+                shift,
+            )?
+            .into_int_value();
+            (shift_value, shift_value)
         };
-        let llvm_target_type = self
-            .llvm_index
-            .get_associated_type(target_type.get_name())?;
-        let phi_value = builder.build_phi(llvm_target_type, "");
-        phi_value.add_incoming(&[
-            (&left_value.into_int_value(), final_left_block),
-            (&rhs, final_right_block),
-        ]);
 
-        Ok((target_type, phi_value.as_basic_value()))
+        let result = match builtin {
+            BuiltIn::Shl | BuiltIn::Shr => {
+                let shifted = if builtin == BuiltIn::Shl {
+                    builder.build_left_shift(int_value, shift_value, "tmpVar")
+                } else {
+                    builder.build_right_shift(int_value, shift_value, false, "tmpVar")
+                };
+                //llvm's result is undefined when shifting by the bit-width or more, IEC expects all bits shifted out
+                match count.get_zero_extended_constant() {
+                    Some(constant_shift) if constant_shift < bit_width => {
+                        shifted.as_basic_value_enum()
+                    }
+                    Some(_) => int_type.const_zero().as_basic_value_enum(),
+                    None => {
+                        let in_range = builder.build_int_compare(
+                            IntPredicate::ULT,
+                            count,
+                            count.get_type().const_int(bit_width, false),
+                            "",
+                        );
+                        builder.build_select(in_range, shifted, int_type.const_zero(), "tmpVar")
+                    }
+                }
+            }
+            BuiltIn::Rol | BuiltIn::Ror => {
+                //a funnel-shift of a value with itself is a rotation (modulo the bit-width)
+                let intrinsic = if builtin == BuiltIn::Rol {
+                    "llvm.fshl"
+                } else {
+                    "llvm.fshr"
+                };
+                let intrinsic_name = get_int_intrinsic_name(intrinsic, int_type);
+                let function = self
+                    .llvm_index
+                    .find_associated_implementation(&intrinsic_name)
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("cannot find intrinsic {}", intrinsic_name),
+                            value.get_location(),
+                        )
+                    })?;
+                builder
+                    .build_call(
+                        function,
+                        &[int_value.into(), int_value.into(), shift_value.into()],
+                        "tmpVar",
+                    )
+                    .try_as_basic_value()
+                    .left()
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("{} did not return a value", intrinsic_name),
+                            value.get_location(),
+                        )
+                    })?
+            }
+        };
+        Ok((value_type, result))
     }
 }
 
@@ -1530,25 +4888,83 @@ fn calculate_time_nano(negative: bool, sec: f64, milli: f64, micro: f64, nano: u
     }
 }
 
-/// calculates the milliseconds since 1970-01-01-00:00:00 for the given
-/// point in time
+/// calculates the time since 1970-01-01-00:00:00 for the given point in time
+/// in the resolution of the given date and time type
+#[allow(clippy::too_many_arguments)]
 fn calculate_date_time(
+    date_type: DateAndTimeType,
     year: i32,
     month: u32,
     day: u32,
     hour: u32,
     min: u32,
     sec: u32,
-    milli: u32,
+    nano: u32,
 ) -> Result<i64, String> {
     if let LocalResult::Single(date_time) = Utc
         .ymd_opt(year, month, day)
-        .and_hms_milli_opt(hour, min, sec, milli)
+        .and_hms_nano_opt(hour, min, sec, nano)
     {
-        return Ok(date_time.timestamp_millis());
+        let resolution = date_type.get_resolution() as i64;
+        let value = date_time
+            .timestamp()
+            .checked_mul(1_000_000_000 / resolution)
+            .and_then(|it| it.checked_add(date_time.timestamp_subsec_nanos() as i64 / resolution));
+        if let Some(value) = value {
+            return Ok(value);
+        }
     }
     Err(format!(
-        "Invalid Date {}-{}-{}-{}:{}:{}.{}",
-        year, month, day, hour, min, sec, milli
+        "Invalid Date {}-{}-{}-{}:{}:{}.{:09}",
+        year, month, day, hour, min, sec, nano
     ))
 }
+
+/// returns the type of the longer string if both given types are strings of the same encoding
+fn get_longer_string_type(
+    left: &DataTypeInformation,
+    right: &DataTypeInformation,
+) -> Option<DataTypeInformation> {
+    match (left, right) {
+        (
+            DataTypeInformation::String { size, encoding },
+            DataTypeInformation::String {
+                size: other_size,
+                encoding: other_encoding,
+            },
+        ) if encoding == other_encoding => Some(DataTypeInformation::String {
+            size: *size.max(other_size),
+            encoding: encoding.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// returns the length in bytes of the given string value (an array or a constant vector of bytes)
+fn get_string_byte_length(value: &BasicValueEnum) -> Option<u32> {
+    match value {
+        BasicValueEnum::ArrayValue(it) => Some(it.get_type().len()),
+        BasicValueEnum::VectorValue(it) => Some(it.get_type().get_size()),
+        _ => None,
+    }
+}
+
+/// extracts the member or element at the given index from the given struct- or array-value
+fn extract_value<'a>(
+    builder: &Builder<'a>,
+    aggregate: BasicValueEnum<'a>,
+    index: u32,
+    expression: &Statement,
+) -> Result<BasicValueEnum<'a>, CompileError> {
+    let value = match aggregate {
+        BasicValueEnum::StructValue(value) => builder.build_extract_value(value, index, ""),
+        BasicValueEnum::ArrayValue(value) => builder.build_extract_value(value, index, ""),
+        _ => None,
+    };
+    value.ok_or_else(|| {
+        CompileError::codegen_error(
+            format!("Cannot access element {} of {:?}", index, aggregate),
+            expression.get_location(),
+        )
+    })
+}
@@ -0,0 +1,209 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+
+/// The intrinsic_generator declares the llvm-intrinsics and runtime functions used by the generated code
+/// # responsibilities
+/// - declares all supported intrinsics and associates them with their name (e.g. `llvm.fshl.i16`)
+///   so they can be looked up like any other implementation
+/// - generates the default allocator hooks used by `__NEW` and `__DELETE`
+/// - removes the declarations that were not used after all POUs were generated
+use super::llvm::Llvm;
+use crate::{
+    codegen::llvm_index::LlvmTypedIndex,
+    compile_error::CompileError,
+    index::Index,
+    typesystem::{DataTypeInformation, ALLOC_HOOK_FN, FREE_HOOK_FN},
+};
+use inkwell::{
+    context::Context,
+    module::{Linkage, Module},
+    targets::TargetData,
+    types::{FunctionType, IntType},
+    values::{BasicValue, FunctionValue},
+    AddressSpace,
+};
+
+/// the integer widths the int-intrinsics are declared for
+const INTRINSIC_INT_WIDTHS: [u32; 4] = [8, 16, 32, 64];
+
+/// intrinsics of the form `iN (iN, iN, iN)`
+const TERNARY_INT_INTRINSICS: [&str; 2] = ["llvm.fshl", "llvm.fshr"];
+
+/// intrinsics of the form `{ iN, i1 } (iN, iN)` returning the result and whether it overflowed
+pub const OVERFLOW_INT_INTRINSICS: [&str; 6] = [
+    "llvm.sadd.with.overflow",
+    "llvm.uadd.with.overflow",
+    "llvm.ssub.with.overflow",
+    "llvm.usub.with.overflow",
+    "llvm.smul.with.overflow",
+    "llvm.umul.with.overflow",
+];
+
+/// the intrinsic that aborts the program, used by the generated runtime checks
+pub const TRAP_INTRINSIC: &str = "llvm.trap";
+
+/// the C functions the default allocator hooks are implemented with
+const MALLOC_FN: &str = "malloc";
+const FREE_FN: &str = "free";
+
+/// returns the name of the given intrinsic overloaded for the given int-type
+/// (e.g. `llvm.fshl` for an i16 becomes `llvm.fshl.i16`)
+pub fn get_int_intrinsic_name(intrinsic: &str, int_type: IntType) -> String {
+    format!("{}.i{}", intrinsic, int_type.get_bit_width())
+}
+
+/// returns the names and signatures of all supported intrinsics
+fn get_intrinsic_declarations(context: &Context) -> Vec<(String, FunctionType)> {
+    let mut declarations = Vec::new();
+    for width in INTRINSIC_INT_WIDTHS.iter() {
+        let int_type = context.custom_width_int_type(*width);
+        for intrinsic in TERNARY_INT_INTRINSICS.iter() {
+            declarations.push((
+                get_int_intrinsic_name(intrinsic, int_type),
+                int_type.fn_type(&[int_type.into(), int_type.into(), int_type.into()], false),
+            ));
+        }
+        let result_type =
+            context.struct_type(&[int_type.into(), context.bool_type().into()], false);
+        for intrinsic in OVERFLOW_INT_INTRINSICS.iter() {
+            declarations.push((
+                get_int_intrinsic_name(intrinsic, int_type),
+                result_type.fn_type(&[int_type.into(), int_type.into()], false),
+            ));
+        }
+    }
+    declarations.push((
+        TRAP_INTRINSIC.to_string(),
+        context.void_type().fn_type(&[], false),
+    ));
+    declarations
+}
+
+/// declares all supported intrinsics in the given module
+/// Returns a Typed index containing the associated intrinsics.
+pub fn generate_intrinsic_stubs<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+) -> Result<LlvmTypedIndex<'ink>, CompileError> {
+    let mut llvm_index = LlvmTypedIndex::new();
+    for (name, function_type) in get_intrinsic_declarations(llvm.context) {
+        let function = module.add_function(&name, function_type, None);
+        llvm_index.associate_implementation(&name, function)?;
+    }
+    Ok(llvm_index)
+}
+
+/// generates weak default implementations of the allocator hooks that call malloc and free.
+/// Hooks declared by the compiled sources (e.g. as @EXTERNAL functions) are used instead, a
+/// strong definition linked with the generated code replaces the defaults as well.
+/// The size passed to malloc is a `size_t` of the given target.
+/// Returns a Typed index containing the generated hooks.
+pub fn generate_allocator_hooks<'ink>(
+    module: &Module<'ink>,
+    llvm: &Llvm<'ink>,
+    index: &Index,
+    target_data: &TargetData,
+) -> Result<LlvmTypedIndex<'ink>, CompileError> {
+    let mut llvm_index = LlvmTypedIndex::new();
+    let context = llvm.context;
+    let builder = &llvm.builder;
+    let byte_pointer_type = context.i8_type().ptr_type(AddressSpace::Generic);
+
+    if index.find_implementation(ALLOC_HOOK_FN).is_some() {
+        validate_alloc_hook_input(index, target_data)?;
+    } else {
+        let size_type = context.ptr_sized_int_type(target_data, None);
+        let malloc_type = byte_pointer_type.fn_type(&[size_type.into()], false);
+        let malloc = module
+            .get_function(MALLOC_FN)
+            .unwrap_or_else(|| module.add_function(MALLOC_FN, malloc_type, None));
+        let hook = module.add_function(ALLOC_HOOK_FN, malloc_type, Some(Linkage::WeakAny));
+        builder.position_at_end(context.append_basic_block(hook, "entry"));
+        let size = hook.get_first_param().unwrap();
+        let memory = builder
+            .build_call(malloc, &[size], "memory")
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+        builder.build_return(Some(&memory));
+        llvm_index.associate_implementation(ALLOC_HOOK_FN, hook)?;
+    }
+
+    if index.find_implementation(FREE_HOOK_FN).is_none() {
+        let free_type = context
+            .void_type()
+            .fn_type(&[byte_pointer_type.into()], false);
+        let free = module
+            .get_function(FREE_FN)
+            .unwrap_or_else(|| module.add_function(FREE_FN, free_type, None));
+        let hook = module.add_function(FREE_HOOK_FN, free_type, Some(Linkage::WeakAny));
+        builder.position_at_end(context.append_basic_block(hook, "entry"));
+        let pointer = hook.get_first_param().unwrap();
+        builder.build_call(free, &[pointer], "");
+        builder.build_return(None);
+        llvm_index.associate_implementation(FREE_HOOK_FN, hook)?;
+    }
+    Ok(llvm_index)
+}
+
+/// returns an error if the size-input of a user-declared allocator hook is not a pointer-sized integer
+/// (the target's `size_t`), the hook would be called with a size of the wrong width
+fn validate_alloc_hook_input(index: &Index, target_data: &TargetData) -> Result<(), CompileError> {
+    if let Some(input) = index.find_input_parameter(ALLOC_HOOK_FN, 0) {
+        let pointer_size = target_data.get_pointer_byte_size(None) * 8;
+        let input_type = index.get_type_information(input.get_type_name())?;
+        let is_pointer_sized = matches!(
+            index.find_effective_type(&input_type),
+            Some(DataTypeInformation::Integer { size, .. }) if *size == pointer_size
+        );
+        if !is_pointer_sized {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "The size-input of {} must be a {}-bit integer on this target, but found {}",
+                    ALLOC_HOOK_FN,
+                    pointer_size,
+                    input_type.get_name()
+                ),
+                input.source_location.clone(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// returns true if the given function is never referenced
+fn is_unused(function: FunctionValue) -> bool {
+    function
+        .as_global_value()
+        .as_pointer_value()
+        .get_first_use()
+        .is_none()
+}
+
+/// removes the declarations of all intrinsics that are never called in the given module
+/// and the default allocator hooks that are never called
+pub fn remove_unused_intrinsics(module: &Module, context: &Context) {
+    for (name, _) in get_intrinsic_declarations(context) {
+        if let Some(function) = module.get_function(&name) {
+            if is_unused(function) {
+                // the declaration has no uses, so no dangling references remain
+                unsafe { function.delete() };
+            }
+        }
+    }
+
+    for (hook_name, c_function_name) in [(ALLOC_HOOK_FN, MALLOC_FN), (FREE_HOOK_FN, FREE_FN)].iter()
+    {
+        let unused_default_hook = module
+            .get_function(hook_name)
+            .filter(|it| it.get_linkage() == Linkage::WeakAny && is_unused(*it));
+        if let Some(hook) = unused_default_hook {
+            unsafe { hook.delete() };
+            let unused_declaration = module
+                .get_function(c_function_name)
+                .filter(|it| it.count_basic_blocks() == 0 && is_unused(*it));
+            if let Some(declaration) = unused_declaration {
+                unsafe { declaration.delete() };
+            }
+        }
+    }
+}
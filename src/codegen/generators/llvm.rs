@@ -10,7 +10,7 @@ use inkwell::{
     builder::Builder,
     context::Context,
     module::{Linkage, Module},
-    types::{BasicType, BasicTypeEnum, StringRadix},
+    types::{BasicType, BasicTypeEnum, StringRadix, StructType},
     values::{BasicValue, BasicValueEnum, GlobalValue, IntValue, PointerValue},
     AddressSpace,
 };
@@ -67,7 +67,7 @@ impl<'a> Llvm<'a> {
     }
 
     /// sets a const-zero initializer for the given global_value according to the given type
-    /// sets a const_zero initializer if the given variable_type is either an int_type or a struct_type
+    /// sets a const_zero initializer if the given variable_type is either an int_type, a struct_type or a pointer_type
     ///
     /// - `global_value` the value to set the initializer on
     /// - `variable_type` the data_type of the variable to initialize
@@ -79,6 +79,8 @@ impl<'a> Llvm<'a> {
             global_value.set_initializer(&variable_type.into_int_type().const_zero());
         } else if variable_type.is_struct_type() {
             global_value.set_initializer(&variable_type.into_struct_type().const_zero());
+        } else if variable_type.is_pointer_type() {
+            global_value.set_initializer(&variable_type.into_pointer_type().const_null());
         }
     }
 
@@ -129,10 +131,49 @@ impl<'a> Llvm<'a> {
     /// - `lvalue` the pointer and it's datatype
     /// - `name` the name of the temporary variable
     pub fn load_pointer(&self, lvalue: &TypeAndPointer<'a, '_>, name: &str) -> TypeAndValue<'a> {
-        (
-            lvalue.get_type_information().clone(),
-            self.builder.build_load(lvalue.ptr_value, name),
-        )
+        let value = if let Some(bit_offset) = lvalue.bit_offset {
+            let byte = self
+                .builder
+                .build_load(lvalue.ptr_value, "")
+                .into_int_value();
+            self.extract_bit(byte, bit_offset, name).into()
+        } else {
+            self.builder.build_load(lvalue.ptr_value, name)
+        };
+        (lvalue.get_type_information().clone(), value)
+    }
+
+    /// stores the given value behind the given pointer. A bit field only replaces its own bit
+    /// of the byte it is stored in
+    ///
+    /// - `lvalue` the pointer and it's datatype
+    /// - `value` the value to store, it must already be of the pointer's type
+    pub fn store_pointer(&self, lvalue: &TypeAndPointer<'a, '_>, value: BasicValueEnum<'a>) {
+        if let Some(bit_offset) = lvalue.bit_offset {
+            let builder = &self.builder;
+            let byte_type = self.context.i8_type();
+            let byte = builder.build_load(lvalue.ptr_value, "").into_int_value();
+            let mask = byte_type.const_int(!(1_u64 << bit_offset) & 0xFF, false);
+            let cleared = builder.build_and(byte, mask, "");
+            let bit = builder.build_int_z_extend(value.into_int_value(), byte_type, "");
+            let bit =
+                builder.build_left_shift(bit, byte_type.const_int(bit_offset as u64, false), "");
+            builder.build_store(lvalue.ptr_value, builder.build_or(cleared, bit, ""));
+        } else {
+            self.builder.build_store(lvalue.ptr_value, value);
+        }
+    }
+
+    /// returns the bit at the given offset of the given byte as an i1
+    pub fn extract_bit(&self, byte: IntValue<'a>, bit_offset: u32, name: &str) -> IntValue<'a> {
+        let shifted = self.builder.build_right_shift(
+            byte,
+            byte.get_type().const_int(bit_offset as u64, false),
+            false,
+            "",
+        );
+        self.builder
+            .build_int_truncate(shifted, self.context.bool_type(), name)
     }
 
     /// creates a placeholder datatype for a struct with the given name
@@ -142,6 +183,26 @@ impl<'a> Llvm<'a> {
         self.context.opaque_struct_type(name)
     }
 
+    /// returns the fat pointer type of a variable-length array. It holds a pointer to the
+    /// first element followed by the lower and upper bound of every dimension
+    /// (`{ inner_type*, [2 x dimensions x i32] }`)
+    ///
+    /// - `inner_type` the type of the array's elements
+    /// - `dimensions` the number of the array's dimensions
+    pub fn get_variable_length_array_type(
+        &self,
+        inner_type: BasicTypeEnum<'a>,
+        dimensions: u32,
+    ) -> StructType<'a> {
+        self.context.struct_type(
+            &[
+                inner_type.ptr_type(AddressSpace::Generic).into(),
+                self.i32_type().array_type(2 * dimensions).into(),
+            ],
+            false,
+        )
+    }
+
     /// returns the i32_type
     pub fn i32_type(&self) -> inkwell::types::IntType<'a> {
         self.context.i32_type()
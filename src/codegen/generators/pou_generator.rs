@@ -3,26 +3,28 @@ use super::{
     expression_generator::ExpressionCodeGenerator,
     llvm::Llvm,
     statement_generator::{FunctionContext, StatementCodeGenerator},
+    struct_generator,
 };
-use crate::codegen::llvm_index::LlvmTypedIndex;
+use crate::codegen::{llvm_index::LlvmTypedIndex, CodeGenOptions};
 
 /// The pou_generator contains functions to generate the code for POUs (PROGRAM, FUNCTION, FUNCTION_BLOCK)
 /// # responsibilities
 /// - generates a struct-datatype for the POU's members
 /// - generates a function for the pou
 /// - declares a global instance if the POU is a PROGRAM
-use crate::index::{ImplementationIndexEntry, VariableIndexEntry};
+use crate::index::{ImplementationIndexEntry, VariableIndexEntry, VariableType};
 use crate::typesystem::*;
 use crate::{
-    ast::{Implementation, PouType, SourceRange, Statement},
+    ast::{CallingConvention, Implementation, PouType, SourceRange, Statement},
     compile_error::CompileError,
     index::Index,
 };
 use inkwell::types::StructType;
 use inkwell::{
+    attributes::{Attribute, AttributeLoc},
     module::Module,
-    types::{BasicTypeEnum, FunctionType},
-    values::{BasicValueEnum, FunctionValue},
+    types::{BasicType, BasicTypeEnum, FunctionType},
+    values::{BasicValueEnum, FunctionValue, PointerValue},
     AddressSpace,
 };
 
@@ -30,6 +32,20 @@ pub struct PouGenerator<'ink, 'cg> {
     llvm: Llvm<'ink>,
     index: &'cg Index,
     llvm_index: &'cg LlvmTypedIndex<'ink>,
+    options: CodeGenOptions,
+}
+
+/// returns true if a value of the given type is not passed by value but through a pointer.
+/// Such results are written into a buffer allocated by the caller, a pointer to the buffer is passed
+/// as the function's first parameter (sret). C-calling-convention inputs of such a type are passed as pointers.
+pub fn is_passed_by_reference(index: &Index, data_type: &DataTypeInformation) -> bool {
+    matches!(
+        index.find_effective_type(data_type),
+        Some(DataTypeInformation::Struct { .. })
+            | Some(DataTypeInformation::Union { .. })
+            | Some(DataTypeInformation::Array { .. })
+            | Some(DataTypeInformation::String { .. })
+    )
 }
 
 /// Creates opaque implementations for all callable items in the index
@@ -41,7 +57,7 @@ pub fn generate_implementation_stubs<'ink>(
     types_index: &LlvmTypedIndex<'ink>,
 ) -> Result<LlvmTypedIndex<'ink>, CompileError> {
     let mut llvm_index = LlvmTypedIndex::new();
-    let pou_generator = PouGenerator::new(llvm, index, &types_index);
+    let pou_generator = PouGenerator::new(llvm, index, &types_index, CodeGenOptions::default());
     for (name, implementation) in index.get_implementations() {
         let curr_f = pou_generator.generate_implementation_stub(implementation, module)?;
         llvm_index.associate_implementation(name, curr_f)?;
@@ -58,11 +74,13 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         llvm: Llvm<'ink>,
         index: &'cg Index,
         llvm_index: &'cg LlvmTypedIndex<'ink>,
+        options: CodeGenOptions,
     ) -> PouGenerator<'ink, 'cg> {
         PouGenerator {
             llvm,
             index,
             llvm_index,
+            options,
         }
     }
 
@@ -80,22 +98,114 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
             .map(|it| it.into_struct_type())?;
         let return_type: Option<&DataType> =
             global_index.find_return_type(implementation.get_type_name());
+        let returns_by_reference = return_type
+            .map(|it| is_passed_by_reference(global_index, it.get_type_information()))
+            .unwrap_or(false);
         let return_type = return_type
             .map(DataType::get_name)
             .map(|it| self.llvm_index.get_associated_type(it).unwrap());
-        let parameters = vec![instance_struct_type.ptr_type(AddressSpace::Generic).into()];
+        let mut parameters = match implementation.get_calling_convention() {
+            CallingConvention::C => self.create_native_parameter_types(pou_name)?,
+            CallingConvention::Default => {
+                vec![instance_struct_type.ptr_type(AddressSpace::Generic).into()]
+            }
+        };
         let variadic = global_index
             .find_type_information(implementation.get_type_name())
             .map(|it| it.is_variadic())
             .unwrap_or(false);
 
-        let function_declaration =
-            self.create_llvm_function_type(parameters, variadic, return_type)?;
+        let function_declaration = match return_type {
+            Some(return_type) if returns_by_reference => {
+                //the caller passes a pointer to the buffer that receives the result as the first parameter
+                parameters.insert(0, return_type.ptr_type(AddressSpace::Generic).into());
+                self.create_llvm_function_type(parameters, variadic, None)?
+            }
+            _ => self.create_llvm_function_type(parameters, variadic, return_type)?,
+        };
 
         let curr_f = module.add_function(pou_name, function_declaration, None);
+        if returns_by_reference {
+            let sret = self
+                .llvm
+                .context
+                .create_enum_attribute(Attribute::get_named_enum_kind_id("sret"), 0);
+            curr_f.add_attribute(AttributeLoc::Param(0), sret);
+        }
         Ok(curr_f)
     }
 
+    /// returns the parameter-types of a function with the C calling convention
+    ///
+    /// VAR_INPUTs are passed as native values (strings and arrays as pointers),
+    /// VAR_IN_OUTs and VAR_OUTPUTs are passed as pointers.
+    /// returns an error for struct inputs and results, their native layout depends on the target's ABI
+    fn create_native_parameter_types(
+        &self,
+        pou_name: &str,
+    ) -> Result<Vec<BasicTypeEnum<'ink>>, CompileError> {
+        let mut parameters = vec![];
+        for member in self.index.find_local_members(pou_name) {
+            let llvm_type = self
+                .llvm_index
+                .get_associated_type(member.get_type_name())?;
+            if member.is_return() || member.get_variable_type() == VariableType::Input {
+                self.validate_native_value(pou_name, member)?;
+            }
+            let parameter_type = match member.get_variable_type() {
+                VariableType::Input => {
+                    let data_type = self.index.get_type_information(member.get_type_name())?;
+                    if is_passed_by_reference(self.index, &data_type) {
+                        llvm_type.ptr_type(AddressSpace::Generic).into()
+                    } else {
+                        llvm_type
+                    }
+                }
+                //the member of a VAR_IN_OUT already is a pointer
+                VariableType::InOut => llvm_type,
+                VariableType::Output => llvm_type.ptr_type(AddressSpace::Generic).into(),
+                _ => continue,
+            };
+            parameters.push(parameter_type);
+        }
+        Ok(parameters)
+    }
+
+    /// returns an error if the given input or result is a struct or union
+    ///
+    /// C passes these by value in registers or on the stack depending on the target's ABI,
+    /// which is not supported. They can be passed as a VAR_IN_OUT or VAR_OUTPUT instead.
+    fn validate_native_value(
+        &self,
+        pou_name: &str,
+        member: &VariableIndexEntry,
+    ) -> Result<(), CompileError> {
+        let data_type = self.index.get_type_information(member.get_type_name())?;
+        if matches!(
+            self.index.find_effective_type(&data_type),
+            Some(DataTypeInformation::Struct { .. }) | Some(DataTypeInformation::Union { .. })
+        ) {
+            let message = if member.is_return() {
+                format!(
+                    "{} cannot return the struct {} with the C calling convention, use a VAR_OUTPUT instead",
+                    pou_name,
+                    member.get_type_name()
+                )
+            } else {
+                format!(
+                    "The struct {} cannot be passed by value with the C calling convention, declare {} as VAR_IN_OUT instead",
+                    member.get_type_name(),
+                    member.get_name()
+                )
+            };
+            return Err(CompileError::codegen_error(
+                message,
+                member.source_location.clone(),
+            ));
+        }
+        Ok(())
+    }
+
     /// generates a function for the given pou
     pub fn generate_implementation(
         &self,
@@ -128,11 +238,13 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
             &implementation.type_name,
             current_function,
             &pou_members,
+            implementation.calling_convention,
         )?;
 
         let function_context = FunctionContext {
             linking_context: implementation.into(),
             function: current_function,
+            options: self.options.clone(),
         };
         {
             let statement_gen = StatementCodeGenerator::new(
@@ -179,6 +291,9 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
             Some(enum_type) if enum_type.is_array_type() => {
                 Ok(enum_type.into_array_type().fn_type(params, is_var_args))
             }
+            Some(enum_type) if enum_type.is_pointer_type() => {
+                Ok(enum_type.into_pointer_type().fn_type(params, is_var_args))
+            }
             None => Ok(self.llvm.context.void_type().fn_type(params, is_var_args)),
             _ => Err(CompileError::codegen_error(
                 format!("Unsupported return type {:?}", return_type),
@@ -188,38 +303,95 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
     }
 
     /// generates a load-statement for the given member
+    ///
+    /// functions with the C calling convention receive their parameters as individual arguments.
+    /// These are copied into a local instance-struct, so the body can access them like the members of
+    /// any other function. VAR_OUTPUTs are written directly to the pointers passed by the caller.
     fn generate_local_variable_accessors(
         &self,
         index: &mut LlvmTypedIndex<'ink>,
         type_name: &str,
         current_function: FunctionValue<'ink>,
         members: &[&VariableIndexEntry],
+        calling_convention: CallingConvention,
     ) -> Result<(), CompileError> {
+        let returns_by_reference = self
+            .index
+            .find_return_type(type_name)
+            .map(|it| is_passed_by_reference(self.index, it.get_type_information()))
+            .unwrap_or(false);
+        //the native parameters follow the optional pointer to the result
+        let mut native_parameters = current_function
+            .get_params()
+            .into_iter()
+            .skip(if returns_by_reference { 1 } else { 0 });
+        let instance = match calling_convention {
+            CallingConvention::C => {
+                let instance_type = self.llvm_index.get_associated_type(type_name)?;
+                Some(self.llvm.create_local_variable(
+                    &struct_generator::get_pou_instance_variable_name(type_name),
+                    &instance_type,
+                ))
+            }
+            CallingConvention::Default => None,
+        };
+
         //Generate reference to parameter
         for (i, m) in members.iter().enumerate() {
             let parameter_name = m.get_name();
 
             let (name, variable) = if m.is_return() {
-                let return_type = index.get_associated_type(m.get_type_name())?;
-                (
-                    type_name,
-                    self.llvm.create_local_variable(type_name, &return_type),
-                )
+                let data_type = self.index.get_type_information(m.get_type_name())?;
+                if is_passed_by_reference(self.index, &data_type) {
+                    //the result is written directly into the caller's buffer
+                    let out_pointer = current_function
+                        .get_first_param()
+                        .map(BasicValueEnum::into_pointer_value)
+                        .ok_or_else(|| CompileError::MissingFunctionError {
+                            location: m.source_location.clone(),
+                        })?;
+                    (type_name, out_pointer)
+                } else {
+                    let return_type = index.get_associated_type(m.get_type_name())?;
+                    (
+                        type_name,
+                        self.llvm.create_local_variable(type_name, &return_type),
+                    )
+                }
             } else {
-                let ptr_value = current_function
-                    .get_first_param()
-                    .map(BasicValueEnum::into_pointer_value)
+                //the instance is passed as the last parameter, after an optional pointer to the result
+                let ptr_value = instance
+                    .or_else(|| {
+                        current_function
+                            .get_last_param()
+                            .map(BasicValueEnum::into_pointer_value)
+                    })
                     .ok_or_else(|| CompileError::MissingFunctionError {
                         location: m.source_location.clone(),
                     })?;
-
-                (
-                    parameter_name,
+                let member = || {
                     self.llvm
                         .builder
                         .build_struct_gep(ptr_value, i as u32, &parameter_name)
-                        .unwrap(),
-                )
+                        .unwrap()
+                };
+
+                let is_parameter = matches!(
+                    m.get_variable_type(),
+                    VariableType::Input | VariableType::InOut | VariableType::Output
+                );
+                let variable = if instance.is_some() && is_parameter {
+                    let parameter = native_parameters.next().ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("Missing parameter {} of {}", parameter_name, type_name),
+                            m.source_location.clone(),
+                        )
+                    })?;
+                    self.copy_native_parameter(m, parameter, member)?
+                } else {
+                    member()
+                };
+                (parameter_name, variable)
             };
             index.associate_loaded_local_variable(type_name, name, variable)?;
         }
@@ -227,6 +399,38 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         Ok(())
     }
 
+    /// copies a native parameter of a function with the C calling convention into the given member
+    /// of the local instance and returns the pointer to access the parameter with
+    fn copy_native_parameter(
+        &self,
+        member: &VariableIndexEntry,
+        parameter: BasicValueEnum<'ink>,
+        member_pointer: impl Fn() -> PointerValue<'ink>,
+    ) -> Result<PointerValue<'ink>, CompileError> {
+        let builder = &self.llvm.builder;
+        match member.get_variable_type() {
+            //outputs are written directly to the caller's variable
+            VariableType::Output => Ok(parameter.into_pointer_value()),
+            VariableType::Input => {
+                let data_type = self.index.get_type_information(member.get_type_name())?;
+                //strings and arrays are passed as pointers (structs are rejected by the function's stub)
+                let value = if is_passed_by_reference(self.index, &data_type) {
+                    builder.build_load(parameter.into_pointer_value(), "")
+                } else {
+                    parameter
+                };
+                let pointer = member_pointer();
+                builder.build_store(pointer, value);
+                Ok(pointer)
+            }
+            _ => {
+                let pointer = member_pointer();
+                builder.build_store(pointer, parameter);
+                Ok(pointer)
+            }
+        }
+    }
+
     /// generates assignment statements for initialized variables in the VAR-block
     ///
     /// - `blocks` - all declaration blocks of the current pou
@@ -262,6 +466,16 @@ impl<'ink, 'cg> PouGenerator<'ink, 'cg> {
         location: Option<SourceRange>,
     ) -> Result<(), CompileError> {
         match pou_type {
+            PouType::Function
+                if self
+                    .index
+                    .find_return_type(function_context.linking_context.get_call_name())
+                    .map(|it| is_passed_by_reference(self.index, it.get_type_information()))
+                    .unwrap_or(false) =>
+            {
+                //the result was already written into the caller's buffer
+                self.llvm.builder.build_return(None);
+            }
             PouType::Function => {
                 let reference = Statement::Reference {
                     name: function_context.linking_context.get_call_name().into(),
@@ -1,8 +1,11 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use std::ops::Range;
 
-use super::{expression_generator::ExpressionCodeGenerator, llvm::Llvm};
-use crate::codegen::LlvmTypedIndex;
+use super::{
+    expression_generator::{is_aggregate_literal, ExpressionCodeGenerator},
+    llvm::Llvm,
+};
+use crate::codegen::{CodeGenOptions, LlvmTypedIndex, TypeAndPointer};
 use crate::typesystem::{RANGE_CHECK_LS_FN, RANGE_CHECK_LU_FN, RANGE_CHECK_S_FN, RANGE_CHECK_U_FN};
 use crate::{ast::SourceRange, codegen::llvm_typesystem::cast_if_needed};
 use crate::{
@@ -15,6 +18,7 @@ use crate::{
 };
 use inkwell::{
     basic_block::BasicBlock,
+    types::BasicType,
     values::{BasicValueEnum, FunctionValue},
     IntPredicate,
 };
@@ -25,6 +29,8 @@ pub struct FunctionContext<'a> {
     pub linking_context: ImplementationIndexEntry,
     /// the llvm function to generate statements into
     pub function: FunctionValue<'a>,
+    /// the options used to generate the code
+    pub options: CodeGenOptions,
 }
 
 /// the StatementCodeGenerator is used to generate statements (For, If, etc.) or expressions (references, literals, etc.)
@@ -86,6 +92,10 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             Statement::Assignment { left, right } => {
                 self.generate_assignment_statement(left, right)?;
             }
+            Statement::RefAssignment { left, right } => {
+                self.create_expr_generator()
+                    .generate_ref_assignment(left, right)?;
+            }
             Statement::ForLoopStatement {
                 start,
                 end,
@@ -137,7 +147,11 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
         right_statement: &Statement,
     ) -> Result<(), CompileError> {
         let exp_gen = self.create_expr_generator();
+        exp_gen.validate_writable(left_statement)?;
         let left = exp_gen.generate_element_pointer(left_statement)?;
+        if exp_gen.is_aggregate_type(left.get_type_information()) {
+            return self.generate_aggregate_assignment(&exp_gen, &left, right_statement);
+        }
         // if the lhs-type is a subrange type we may need to generate a check-call
         // e.g. x := y,  ==> x := CheckSignedInt(y);
         let range_checked_right_side =
@@ -162,15 +176,98 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             exp_gen.generate_expression(right_statement)?
         };
 
-        let cast_value = cast_if_needed(
-            self.llvm,
-            self.index,
-            &left.get_type_information(),
-            right,
-            &right_type,
-            right_statement,
-        )?;
-        self.llvm.builder.build_store(left.ptr_value, cast_value);
+        let cast_value = match left.get_type_information() {
+            //integers (e.g. 0) are assigned to pointers as addresses
+            DataTypeInformation::Pointer {
+                auto_deref: false, ..
+            } if right_type.is_int() => self
+                .llvm
+                .builder
+                .build_int_to_ptr(
+                    right.into_int_value(),
+                    left.ptr_value
+                        .get_type()
+                        .get_element_type()
+                        .into_pointer_type(),
+                    "",
+                )
+                .into(),
+            left_type => cast_if_needed(
+                self.llvm,
+                self.index,
+                left_type,
+                right,
+                &right_type,
+                right_statement,
+            )?,
+        };
+        self.llvm.store_pointer(&left, cast_value);
+        Ok(())
+    }
+
+    /// generates the assignment of a whole struct or array. If the right side is a variable
+    /// it is copied using memmove (both sides may be the same variable, e.g. `a := a`), otherwise
+    /// its value is stored into the left side
+    ///
+    /// - `exp_gen` the expression generator used to generate the right side
+    /// - `left` the pointer to the assigned struct or array
+    /// - `right_statement` the assigned value
+    fn generate_aggregate_assignment(
+        &self,
+        exp_gen: &ExpressionCodeGenerator<'a, 'b>,
+        left: &TypeAndPointer<'a, '_>,
+        right_statement: &Statement,
+    ) -> Result<(), CompileError> {
+        let left_type = left.get_type_information();
+        let check_types = |right_type: &DataTypeInformation| {
+            if exp_gen.is_same_aggregate_type(left_type, right_type) {
+                Ok(())
+            } else {
+                Err(CompileError::casting_error(
+                    right_type.get_name(),
+                    left_type.get_name(),
+                    right_statement.get_location(),
+                ))
+            }
+        };
+
+        match right_statement {
+            Statement::Reference { .. }
+            | Statement::QualifiedReference { .. }
+            | Statement::ArrayAccess { .. } => {
+                let right = exp_gen.generate_element_pointer(right_statement)?;
+                check_types(right.get_type_information())?;
+                let size = self
+                    .llvm_index
+                    .get_associated_type(
+                        self.index
+                            .find_effective_type(left_type)
+                            .unwrap_or(left_type)
+                            .get_name(),
+                    )?
+                    .size_of()
+                    .ok_or_else(|| {
+                        CompileError::codegen_error(
+                            format!("Cannot determine the size of {}", left_type.get_name()),
+                            right_statement.get_location(),
+                        )
+                    })?;
+                self.llvm
+                    .builder
+                    .build_memmove(left.ptr_value, 1, right.ptr_value, 1, size)
+                    .map_err(|err| {
+                        CompileError::codegen_error(err.to_string(), right_statement.get_location())
+                    })?;
+            }
+            _ if is_aggregate_literal(right_statement) => {
+                exp_gen.generate_aggregate_literal(left.ptr_value, left_type, right_statement)?;
+            }
+            _ => {
+                let (right_type, right) = exp_gen.generate_expression(right_statement)?;
+                check_types(&right_type)?;
+                self.llvm.builder.build_store(left.ptr_value, right);
+            }
+        }
         Ok(())
     }
 
@@ -268,10 +365,8 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
             "tmpVar",
         );
 
-        let ptr = expression_generator
-            .generate_element_pointer(counter)?
-            .ptr_value;
-        builder.build_store(ptr, next);
+        let counter_pointer = expression_generator.generate_element_pointer(counter)?;
+        self.llvm.store_pointer(&counter_pointer, next.into());
 
         //Loop back
         builder.build_unconditional_branch(condition_check);
@@ -399,7 +494,8 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
                 selector_val,
                 start_val,
                 &type_info,
-            );
+                start,
+            )?;
             lower_bound_condition
         };
 
@@ -414,7 +510,8 @@ impl<'a, 'b> StatementCodeGenerator<'a, 'b> {
                 selector_val,
                 end_val,
                 &type_info,
-            );
+                end,
+            )?;
             upper_bound_condition
         };
         self.llvm.builder.build_conditional_branch(
@@ -3,6 +3,7 @@ use super::{expression_generator::ExpressionCodeGenerator, llvm::Llvm};
 use crate::index::Index;
 use crate::{
     codegen::llvm_index::LlvmTypedIndex, compile_error::CompileError, index::VariableIndexEntry,
+    typesystem::DataTypeInformation,
 };
 use inkwell::{
     types::{BasicTypeEnum, StructType},
@@ -16,6 +17,16 @@ pub struct StructGenerator<'a, 'b> {
     llvm_index: &'b LlvmTypedIndex<'a>,
 }
 
+/// a field of a generated struct type
+pub enum StructField<'b> {
+    /// a field holding a single member
+    Member(&'b VariableIndexEntry),
+    /// a byte shared by up to 8 consecutive BIT members of a packed struct, the first member is stored in the lowest bit
+    Bits(Vec<&'b VariableIndexEntry>),
+    /// the given number of padding bytes
+    Padding(u32),
+}
+
 ///
 /// a touple (name, data_type, initializer) describing the declaration of a variable.
 ///
@@ -36,50 +47,65 @@ impl<'a, 'b> StructGenerator<'a, 'b> {
         }
     }
 
-    /// generates a new StructType with the given members
+    /// generates a new StructType with the given fields
     ///
-    /// - `member_variables` the member variables in the order of their declaration
+    /// - `fields` the fields of the struct in the order of their declaration
     /// - `name` the name of the StructType
+    /// - `packed` whether llvm must not insert any padding between the fields
     pub fn generate_struct_type(
         &mut self,
-        member_variables: &[&VariableIndexEntry],
+        fields: &[StructField],
         name: &str,
+        packed: bool,
     ) -> Result<(StructTypeAndValue<'a>, Vec<(String, BasicValueEnum<'a>)>), CompileError> {
         let struct_type = self
             .llvm_index
             .get_associated_type(name)
             .map(BasicTypeEnum::into_struct_type)?;
 
-        let mut members = Vec::new();
-        for member in member_variables {
-            members.push(self.create_llvm_variable_declaration_elements(member)?);
-        }
-
-        let member_types: Vec<BasicTypeEnum> = members.iter().map(|(_, t, _)| *t).collect();
-        struct_type.set_body(member_types.as_slice(), false);
-
+        let mut field_types = Vec::new();
+        let mut field_values = Vec::new();
         //vec(member_name, initial_value)
-        let struct_fields_values = members
-            .iter()
-            .map(|(name, basic_type, initializer)| {
-                initializer
-                    .map(|it| (name, it))
-                    .unwrap_or_else(|| (name, get_default_for(*basic_type)))
-            })
-            .collect::<Vec<(&String, BasicValueEnum)>>();
-
-        let initial_value = struct_type.const_named_struct(
-            struct_fields_values
-                .iter()
-                .map(|(_, it)| *it)
-                .collect::<Vec<BasicValueEnum<'a>>>()
-                .as_slice(),
-        );
+        let mut member_values = Vec::new();
+        for field in fields {
+            match field {
+                StructField::Member(member) => {
+                    let (name, basic_type, initializer) =
+                        self.create_llvm_variable_declaration_elements(member)?;
+                    let value = initializer.unwrap_or_else(|| get_default_for(basic_type));
+                    field_types.push(basic_type);
+                    field_values.push(value);
+                    member_values.push((name, value));
+                }
+                StructField::Bits(members) => {
+                    let byte_type = self.llvm.context.i8_type();
+                    let mut bits = 0;
+                    for (bit, member) in members.iter().enumerate() {
+                        let (name, basic_type, initializer) =
+                            self.create_llvm_variable_declaration_elements(member)?;
+                        let value = initializer.unwrap_or_else(|| get_default_for(basic_type));
+                        if value
+                            .into_int_value()
+                            .get_zero_extended_constant()
+                            .map_or(false, |it| it & 1 == 1)
+                        {
+                            bits |= 1 << bit;
+                        }
+                        member_values.push((name, value));
+                    }
+                    field_types.push(byte_type.into());
+                    field_values.push(byte_type.const_int(bits, false).into());
+                }
+                StructField::Padding(bytes) => {
+                    let padding_type = self.llvm.context.i8_type().array_type(*bytes);
+                    field_types.push(padding_type.into());
+                    field_values.push(padding_type.const_zero().into());
+                }
+            }
+        }
 
-        let member_values = struct_fields_values
-            .iter()
-            .map(|(name, value)| (name.to_string(), *value))
-            .collect();
+        struct_type.set_body(field_types.as_slice(), packed);
+        let initial_value = struct_type.const_named_struct(field_values.as_slice());
 
         Ok(((struct_type, initial_value.into()), member_values))
     }
@@ -96,8 +122,20 @@ impl<'a, 'b> StructGenerator<'a, 'b> {
         //                         //&variable.data_type.get_name().ok_or_else(|| error_type_not_associated(type_name, &variable.location))?;
 
         let variable_type = self.index.get_type_information(type_name)?;
+        if let DataTypeInformation::VariableLengthArray { .. } = variable_type {
+            if !variable.is_inout() && !variable.is_constant() {
+                return Err(CompileError::codegen_error(
+                    format!(
+                        "Variable-length array {} must be declared as VAR_IN_OUT or VAR_INPUT CONSTANT",
+                        variable.get_name()
+                    ),
+                    variable.source_location.clone(),
+                ));
+            }
+        }
         let initializer = match &variable.initial_value {
-            Some(statement) => {
+            //a constant input points to its initial value only if the caller omits it
+            Some(statement) if !variable.is_constant() => {
                 let exp_gen = ExpressionCodeGenerator::new_context_free(
                     self.llvm,
                     self.index,
@@ -108,7 +146,7 @@ impl<'a, 'b> StructGenerator<'a, 'b> {
                     .generate_expression(statement)
                     .map(|(_, value)| Some(value))?
             }
-            None => self.llvm_index.find_associated_initial_value(type_name),
+            _ => self.llvm_index.find_associated_initial_value(type_name),
         };
 
         Ok((
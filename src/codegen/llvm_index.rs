@@ -4,13 +4,24 @@ use inkwell::types::BasicTypeEnum;
 use inkwell::values::{BasicValueEnum, FunctionValue, GlobalValue, PointerValue};
 use std::collections::HashMap;
 
+/// the position of a struct member inside of its llvm struct type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemberLocation {
+    /// the index of the struct field that stores the member
+    pub field: u32,
+    /// the bit inside of the field's byte for BIT members sharing a byte, None for all other members
+    pub bit: Option<u32>,
+}
+
 /// Index view containing declared values for the current context
 /// Parent Index is the a fallback lookup index for values not declared locally
+/// All names are looked up case-insensitive
 #[derive(Debug, Clone)]
 pub struct LlvmTypedIndex<'ink> {
     parent_index: Option<&'ink LlvmTypedIndex<'ink>>,
     type_associations: HashMap<String, BasicTypeEnum<'ink>>,
     initial_value_associations: HashMap<String, BasicValueEnum<'ink>>,
+    member_locations: HashMap<String, MemberLocation>,
     loaded_variable_associations: HashMap<String, PointerValue<'ink>>,
     implementations: HashMap<String, FunctionValue<'ink>>,
 }
@@ -21,6 +32,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
             parent_index: None,
             type_associations: HashMap::new(),
             initial_value_associations: HashMap::new(),
+            member_locations: HashMap::new(),
             loaded_variable_associations: HashMap::new(),
             implementations: HashMap::new(),
         }
@@ -31,6 +43,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
             parent_index: Some(parent),
             type_associations: HashMap::new(),
             initial_value_associations: HashMap::new(),
+            member_locations: HashMap::new(),
             loaded_variable_associations: HashMap::new(),
             implementations: HashMap::new(),
         }
@@ -46,6 +59,9 @@ impl<'ink> LlvmTypedIndex<'ink> {
         for (name, assocication) in other.initial_value_associations.drain() {
             self.initial_value_associations.insert(name, assocication);
         }
+        for (name, location) in other.member_locations.drain() {
+            self.member_locations.insert(name, location);
+        }
         for (name, assocication) in other.loaded_variable_associations.drain() {
             self.loaded_variable_associations.insert(name, assocication);
         }
@@ -60,7 +76,8 @@ impl<'ink> LlvmTypedIndex<'ink> {
         type_name: &str,
         target_type: BasicTypeEnum<'ink>,
     ) -> Result<(), CompileError> {
-        self.type_associations.insert(type_name.into(), target_type);
+        self.type_associations
+            .insert(type_name.to_lowercase(), target_type);
         Ok(())
     }
 
@@ -70,7 +87,18 @@ impl<'ink> LlvmTypedIndex<'ink> {
         initial_value: BasicValueEnum<'ink>,
     ) -> Result<(), CompileError> {
         self.initial_value_associations
-            .insert(type_name.into(), initial_value);
+            .insert(type_name.to_lowercase(), initial_value);
+        Ok(())
+    }
+
+    /// associates the position of the given member (e.g. `MyStruct.a`) in its llvm struct type
+    pub fn associate_member_location(
+        &mut self,
+        qualified_name: &str,
+        location: MemberLocation,
+    ) -> Result<(), CompileError> {
+        self.member_locations
+            .insert(qualified_name.to_lowercase(), location);
         Ok(())
     }
 
@@ -82,16 +110,19 @@ impl<'ink> LlvmTypedIndex<'ink> {
     ) -> Result<(), CompileError> {
         let qualified_name = format!("{}.{}", container_name, variable_name);
         self.loaded_variable_associations
-            .insert(qualified_name, target_value);
+            .insert(qualified_name.to_lowercase(), target_value);
         Ok(())
     }
 
     pub fn find_associated_type(&self, type_name: &str) -> Option<BasicTypeEnum<'ink>> {
-        self.type_associations.get(type_name).copied().or_else(|| {
-            self.parent_index
-                .map(|it| it.find_associated_type(type_name))
-                .flatten()
-        })
+        self.type_associations
+            .get(&type_name.to_lowercase())
+            .copied()
+            .or_else(|| {
+                self.parent_index
+                    .map(|it| it.find_associated_type(type_name))
+                    .flatten()
+            })
     }
 
     pub fn get_associated_type(
@@ -104,7 +135,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
 
     pub fn find_associated_initial_value(&self, type_name: &str) -> Option<BasicValueEnum<'ink>> {
         self.initial_value_associations
-            .get(type_name)
+            .get(&type_name.to_lowercase())
             .copied()
             .or_else(|| {
                 self.parent_index
@@ -113,13 +144,24 @@ impl<'ink> LlvmTypedIndex<'ink> {
             })
     }
 
+    pub fn find_member_location(&self, qualified_name: &str) -> Option<MemberLocation> {
+        self.member_locations
+            .get(&qualified_name.to_lowercase())
+            .copied()
+            .or_else(|| {
+                self.parent_index
+                    .map(|it| it.find_member_location(qualified_name))
+                    .flatten()
+            })
+    }
+
     pub fn associate_global(
         &mut self,
         variable_name: &str,
         global_variable: GlobalValue<'ink>,
     ) -> Result<(), CompileError> {
         self.initial_value_associations.insert(
-            variable_name.into(),
+            variable_name.to_lowercase(),
             global_variable.as_pointer_value().into(),
         );
         Ok(())
@@ -131,7 +173,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
         function_value: FunctionValue<'ink>,
     ) -> Result<(), CompileError> {
         self.implementations
-            .insert(callable_name.into(), function_value);
+            .insert(callable_name.to_lowercase(), function_value);
         Ok(())
     }
 
@@ -140,7 +182,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
         callable_name: &str,
     ) -> Option<FunctionValue<'ink>> {
         self.implementations
-            .get(callable_name)
+            .get(&callable_name.to_lowercase())
             .copied()
             .or_else(|| {
                 self.parent_index
@@ -154,7 +196,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
         qualified_name: &str,
     ) -> Option<BasicValueEnum<'ink>> {
         self.initial_value_associations
-            .get(qualified_name)
+            .get(&qualified_name.to_lowercase())
             .copied()
             .or_else(|| {
                 self.parent_index
@@ -169,7 +211,7 @@ impl<'ink> LlvmTypedIndex<'ink> {
     ) -> Option<PointerValue<'ink>> {
         let result = self
             .loaded_variable_associations
-            .get(qualified_name)
+            .get(&qualified_name.to_lowercase())
             .copied()
             .or_else(|| {
                 self.parent_index
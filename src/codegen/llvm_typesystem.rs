@@ -150,6 +150,44 @@ fn create_llvm_extend_int_value<'a>(
     }
 }
 
+/// converts between the short and the long form of a date and time type (e.g. TIME and LTIME)
+/// by scaling the value to the target's resolution. returns None if no such conversion is needed
+fn scale_date_and_time_if_needed<'ctx>(
+    llvm: &Llvm<'ctx>,
+    target_type: &DataTypeInformation,
+    value: BasicValueEnum<'ctx>,
+    value_type: &DataTypeInformation,
+) -> Option<BasicValueEnum<'ctx>> {
+    let target_date_type = target_type.get_date_and_time_type()?;
+    let value_date_type = value_type.get_date_and_time_type()?;
+    if target_date_type.get_short_form() != value_date_type.get_short_form() {
+        return None;
+    }
+    let target_resolution = target_date_type.get_resolution();
+    let value_resolution = value_date_type.get_resolution();
+    if target_resolution < value_resolution {
+        let factor = llvm
+            .i64_type()
+            .const_int(value_resolution / target_resolution, false);
+        Some(
+            llvm.builder
+                .build_int_mul(value.into_int_value(), factor, "")
+                .into(),
+        )
+    } else if target_resolution > value_resolution {
+        let divisor = llvm
+            .i64_type()
+            .const_int(target_resolution / value_resolution, false);
+        Some(
+            llvm.builder
+                .build_int_signed_div(value.into_int_value(), divisor, "")
+                .into(),
+        )
+    } else {
+        None
+    }
+}
+
 pub fn cast_if_needed<'ctx>(
     llvm: &Llvm<'ctx>,
     index: &Index,
@@ -171,6 +209,9 @@ pub fn cast_if_needed<'ctx>(
             SourceRange::undefined(),
         )
     })?;
+    if let Some(value) = scale_date_and_time_if_needed(llvm, target_type, value, value_type) {
+        return Ok(value);
+    }
     match target_type {
         DataTypeInformation::Integer {
             signed,
@@ -305,6 +346,22 @@ pub fn cast_if_needed<'ctx>(
                 location_context.get_location(),
             )),
         },
+        DataTypeInformation::Pointer {
+            inner_type_name,
+            auto_deref: false,
+            ..
+        } => match value_type {
+            DataTypeInformation::Pointer {
+                inner_type_name: value_inner_type_name,
+                auto_deref: false,
+                ..
+            } if inner_type_name.eq_ignore_ascii_case(value_inner_type_name) => Ok(value),
+            _ => Err(CompileError::casting_error(
+                &value_type.get_name(),
+                &target_type.get_name(),
+                location_context.get_location(),
+            )),
+        },
         _ => Ok(value),
     }
 }
@@ -11,7 +11,9 @@ macro_rules! codegen_wihout_unwrap {
 
         let context = inkwell::context::Context::create();
         crate::ast::pre_process(&mut ast);
-        let index = crate::index::visitor::visit(&ast);
+        let generics = crate::generics::extract_generic_functions(&mut ast);
+        let mut index = crate::index::visitor::visit(&ast);
+        crate::generics::instantiate_generic_functions(&generics, &mut ast, &mut index).unwrap();
         let code_generator = crate::codegen::CodeGen::new(&context, "main");
         code_generator.generate(ast, &index)
     }};
@@ -25,10 +27,24 @@ macro_rules! codegen {
 
         let context = inkwell::context::Context::create();
         crate::ast::pre_process(&mut ast);
-        let index = crate::index::visitor::visit(&ast);
+        let generics = crate::generics::extract_generic_functions(&mut ast);
+        let mut index = crate::index::visitor::visit(&ast);
+        crate::generics::instantiate_generic_functions(&generics, &mut ast, &mut index).unwrap();
         let code_generator = crate::codegen::CodeGen::new(&context, "main");
         code_generator.generate(ast, &index).unwrap()
     }};
+    ($code:tt, $options:expr) => {{
+        let lexer = crate::lexer::lex($code);
+        let (mut ast, ..) = crate::parser::parse(lexer).unwrap();
+
+        let context = inkwell::context::Context::create();
+        crate::ast::pre_process(&mut ast);
+        let generics = crate::generics::extract_generic_functions(&mut ast);
+        let mut index = crate::index::visitor::visit(&ast);
+        crate::generics::instantiate_generic_functions(&generics, &mut ast, &mut index).unwrap();
+        let code_generator = crate::codegen::CodeGen::new_with_options(&context, "main", $options);
+        code_generator.generate(ast, &index).unwrap()
+    }};
 }
 
 #[macro_export]
@@ -129,6 +129,44 @@ fn global_variable_reference_is_generated() {
     assert_eq!(function, expected)
 }
 
+#[test]
+fn global_variable_list_members_are_referenced_by_their_qualified_name() {
+    let function = codegen!(
+        r"
+    VAR_GLOBAL Gvl {attribute 'qualified_only'}
+        x : INT;
+    END_VAR
+    VAR_GLOBAL Other
+        x : INT;
+    END_VAR
+    PROGRAM prg
+    VAR
+      x : INT;
+    END_VAR
+    Gvl.x := 20;
+    x := Other.x;
+    END_PROGRAM
+    "
+    );
+
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i16", "x")],
+        "void",
+        "",
+        r"
+@Gvl.x = global i16 0
+@Other.x = global i16 0", //global vars
+        r"store i16 20, i16* @Gvl.x, align 2
+  %load_ = load i16, i16* @Other.x, align 2
+  store i16 %load_, i16* %x, align 2
+  ret void
+", //body
+    );
+
+    assert_eq!(function, expected)
+}
+
 #[test]
 fn empty_program_with_name_generates_void_function() {
     let result = codegen!("PROGRAM prg END_PROGRAM");
@@ -546,6 +584,137 @@ fn date_comparisons() {
     assert_eq!(result, expected);
 }
 
+#[test]
+fn date_and_time_additions_and_subtractions() {
+    let result = codegen!(
+        r#"PROGRAM prg
+        VAR
+          a : TIME;
+          b : TIME;
+          c : DATE_AND_TIME;
+          d : DT;
+          e : DT;
+          f : TOD;
+        END_VAR
+
+          a + b;
+          c + T#1s;
+          d - e;
+          f - TOD#12:00:00;
+        END_PROGRAM"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[
+            ("i64", "a"),
+            ("i64", "b"),
+            ("i64", "c"),
+            ("i64", "d"),
+            ("i64", "e"),
+            ("i64", "f"),
+        ],
+        "void",
+        "",
+        "",
+        r#"%load_a = load i64, i64* %a, align 4
+  %load_b = load i64, i64* %b, align 4
+  %tmpVar = add i64 %load_a, %load_b
+  %load_c = load i64, i64* %c, align 4
+  %tmpVar1 = add i64 %load_c, 1000
+  %load_d = load i64, i64* %d, align 4
+  %load_e = load i64, i64* %e, align 4
+  %tmpVar2 = sub i64 %load_d, %load_e
+  %tmpVar3 = mul i64 %tmpVar2, 1000000
+  %load_f = load i64, i64* %f, align 4
+  %tmpVar4 = sub i64 %load_f, 43200000
+  %tmpVar5 = mul i64 %tmpVar4, 1000000
+  ret void
+"#,
+    );
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn time_multiplied_and_divided_by_numbers() {
+    let result = codegen!(
+        r#"PROGRAM prg
+        VAR
+          a : TIME;
+          b : INT;
+          c : TIME;
+          d : REAL;
+        END_VAR
+
+          a * b;
+          c / d;
+        END_PROGRAM"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i64", "a"), ("i16", "b"), ("i64", "c"), ("float", "d")],
+        "void",
+        "",
+        "",
+        r#"%load_a = load i64, i64* %a, align 4
+  %load_b = load i16, i16* %b, align 2
+  %1 = sext i16 %load_b to i64
+  %tmpVar = mul i64 %load_a, %1
+  %load_c = load i64, i64* %c, align 4
+  %load_d = load float, float* %d, align 4
+  %2 = sitofp i64 %load_c to double
+  %3 = fpext float %load_d to double
+  %tmpVar1 = fdiv double %2, %3
+  %4 = fptosi double %tmpVar1 to i64
+  ret void
+"#,
+    );
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn long_date_and_time_literals_and_conversions() {
+    let result = codegen!(
+        r#"PROGRAM prg
+        VAR
+          a : LTIME;
+          b : LDT;
+          c : DT;
+          d : LTOD;
+        END_VAR
+
+          a := LTIME#1s5ns;
+          b := LDT#1970-01-01-00:00:01.000000001;
+          c := b;
+          b := c;
+          d + a;
+        END_PROGRAM"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i64", "a"), ("i64", "b"), ("i64", "c"), ("i64", "d")],
+        "void",
+        "",
+        "",
+        r#"store i64 1000000005, i64* %a, align 4
+  store i64 1000000001, i64* %b, align 4
+  %load_b = load i64, i64* %b, align 4
+  %1 = sdiv i64 %load_b, 1000000
+  store i64 %1, i64* %c, align 4
+  %load_c = load i64, i64* %c, align 4
+  %2 = mul i64 %load_c, 1000000
+  store i64 %2, i64* %b, align 4
+  %load_d = load i64, i64* %d, align 4
+  %load_a = load i64, i64* %a, align 4
+  %tmpVar = add i64 %load_d, %load_a
+  ret void
+"#,
+    );
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn program_with_string_assignment() {
     let result = codegen!(
@@ -959,6 +1128,160 @@ END_PROGRAM
     assert_eq!(result, expected);
 }
 
+#[test]
+fn program_with_bitwise_operations_on_words() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+x : WORD;
+y : WORD;
+END_VAR
+x AND y;
+x OR y;
+x XOR y;
+NOT x;
+END_PROGRAM
+"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i16", "x"), ("i16", "y")],
+        "void",
+        "",
+        "",
+        r#"%load_x = load i16, i16* %x, align 2
+  %load_y = load i16, i16* %y, align 2
+  %tmpVar = and i16 %load_x, %load_y
+  %load_x1 = load i16, i16* %x, align 2
+  %load_y2 = load i16, i16* %y, align 2
+  %tmpVar3 = or i16 %load_x1, %load_y2
+  %load_x4 = load i16, i16* %x, align 2
+  %load_y5 = load i16, i16* %y, align 2
+  %tmpVar6 = xor i16 %load_x4, %load_y5
+  %load_x7 = load i16, i16* %x, align 2
+  %tmpVar8 = xor i16 %load_x7, -1
+  ret void
+"#,
+    );
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn program_with_shift_and_rotate_builtins() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+x : WORD;
+n : INT;
+END_VAR
+x := SHL(x, 2);
+x := SHR(IN := x, N := n);
+x := ROL(x, 3);
+END_PROGRAM
+"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i16", "x"), ("i16", "n")],
+        "void",
+        "",
+        "",
+        r#"%load_x = load i16, i16* %x, align 2
+  %tmpVar = shl i16 %load_x, 2
+  store i16 %tmpVar, i16* %x, align 2
+  %load_x1 = load i16, i16* %x, align 2
+  %load_n = load i16, i16* %n, align 2
+  %tmpVar2 = lshr i16 %load_x1, %load_n
+  %1 = icmp ult i16 %load_n, 16
+  %tmpVar3 = select i1 %1, i16 %tmpVar2, i16 0
+  store i16 %tmpVar3, i16* %x, align 2
+  %load_x4 = load i16, i16* %x, align 2
+  %tmpVar5 = call i16 @llvm.fshl.i16(i16 %load_x4, i16 %load_x4, i16 3)
+  store i16 %tmpVar5, i16* %x, align 2
+  ret void
+"#,
+    ) + r#"
+; Function Attrs: nounwind readnone speculatable willreturn
+declare i16 @llvm.fshl.i16(i16, i16, i16) #0
+
+attributes #0 = { nounwind readnone speculatable willreturn }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn rotate_builtin_uses_the_width_of_the_value() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+x : BYTE;
+END_VAR
+x := ROR(x, 1);
+END_PROGRAM
+"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i8", "x")],
+        "void",
+        "",
+        "",
+        r#"%load_x = load i8, i8* %x, align 1
+  %tmpVar = call i8 @llvm.fshr.i8(i8 %load_x, i8 %load_x, i8 1)
+  store i8 %tmpVar, i8* %x, align 1
+  ret void
+"#,
+    ) + r#"
+; Function Attrs: nounwind readnone speculatable willreturn
+declare i8 @llvm.fshr.i8(i8, i8, i8) #0
+
+attributes #0 = { nounwind readnone speculatable willreturn }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn selection_builtins_are_generated_as_selects() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+a : DINT;
+b : DINT;
+g : BOOL;
+END_VAR
+a := SEL(g, a, b);
+a := MAX(a, b, 7);
+END_PROGRAM
+"#
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i32", "a"), ("i32", "b"), ("i1", "g")],
+        "void",
+        "",
+        "",
+        r#"%load_g = load i1, i1* %g, align 1
+  %load_a = load i32, i32* %a, align 4
+  %load_b = load i32, i32* %b, align 4
+  %tmpVar = select i1 %load_g, i32 %load_b, i32 %load_a
+  store i32 %tmpVar, i32* %a, align 4
+  %load_a1 = load i32, i32* %a, align 4
+  %load_b2 = load i32, i32* %b, align 4
+  %1 = icmp sgt i32 %load_b2, %load_a1
+  %tmpVar3 = select i1 %1, i32 %load_b2, i32 %load_a1
+  %2 = icmp sgt i32 7, %tmpVar3
+  %tmpVar4 = select i1 %2, i32 7, i32 %tmpVar3
+  store i32 %tmpVar4, i32* %a, align 4
+  ret void
+"#,
+    );
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn program_with_negated_expressions_generates_void_function_and_struct_and_body() {
     let result = codegen!(
@@ -2586,6 +2909,77 @@ continue:                                         ; preds = %output
     assert_eq!(result, expected);
 }
 
+#[test]
+fn omitted_inputs_of_a_function_call_get_their_initial_values() {
+    let result = codegen!(
+        "
+        FUNCTION foo : DINT
+        VAR_INPUT
+          a : DINT;
+          b : DINT := 5;
+        END_VAR
+        foo := a + b;
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+        x : DINT;
+        END_VAR
+        x := foo(a := 2);
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { i32 }
+%foo_interface = type { i32, i32 }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define i32 @foo(%foo_interface* %0) {
+entry:
+  %a = getelementptr inbounds %foo_interface, %foo_interface* %0, i32 0, i32 0
+  %b = getelementptr inbounds %foo_interface, %foo_interface* %0, i32 0, i32 1
+  %foo = alloca i32, align 4
+  %load_a = load i32, i32* %a, align 4
+  %load_b = load i32, i32* %b, align 4
+  %tmpVar = add i32 %load_a, %load_b
+  store i32 %tmpVar, i32* %foo, align 4
+  %foo_ret = load i32, i32* %foo, align 4
+  ret i32 %foo_ret
+}
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %foo_instance = alloca %foo_interface, align 8
+  br label %input
+
+input:                                            ; preds = %entry
+  %1 = getelementptr inbounds %foo_interface, %foo_interface* %foo_instance, i32 0, i32 1
+  store i32 5, i32* %1, align 4
+  %2 = getelementptr inbounds %foo_interface, %foo_interface* %foo_instance, i32 0, i32 0
+  store i32 2, i32* %2, align 4
+  br label %call
+
+call:                                             ; preds = %input
+  %call1 = call i32 @foo(%foo_interface* %foo_instance)
+  br label %output
+
+output:                                           ; preds = %call
+  br label %continue
+
+continue:                                         ; preds = %output
+  store i32 %call1, i32* %x, align 4
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn program_with_two_explicit_parameters_called_in_program() {
     let result = codegen!(
@@ -3078,23 +3472,29 @@ continue:                                         ; preds = %output
 }
 
 #[test]
-fn function_block_qualified_instance_call() {
+fn function_block_instances_in_arrays_are_called_through_their_elements() {
     let result = codegen!(
         "
-        FUNCTION_BLOCK foo
+        FUNCTION_BLOCK motor
+        VAR_INPUT
+          enable : BOOL;
+        END_VAR
+        VAR_OUTPUT
+          running : BOOL;
+        END_VAR
         VAR
-          bar_inst : bar;
+          speed : DINT := 10;
         END_VAR
-        END_FUNCTION_BLOCK
-
-        FUNCTION_BLOCK bar
+          running := enable;
         END_FUNCTION_BLOCK
 
         PROGRAM prg
         VAR
-          foo_inst : foo;
+          motors : ARRAY[1..2] OF motor;
+          r : BOOL;
         END_VAR
-          foo_inst.bar_inst();
+          motors[2](enable := TRUE);
+          r := motors[2].running;
         END_PROGRAM
       "
     );
@@ -3102,7 +3502,78 @@ fn function_block_qualified_instance_call() {
     let expected = r#"; ModuleID = 'main'
 source_filename = "main"
 
-%prg_interface = type { %foo_interface }
+%prg_interface = type { [2 x %motor_interface], i1 }
+%motor_interface = type { i1, i1, i32 }
+
+@prg_instance = global %prg_interface { [2 x %motor_interface] [%motor_interface { i1 false, i1 false, i32 10 }, %motor_interface { i1 false, i1 false, i32 10 }], i1 false }
+
+define void @motor(%motor_interface* %0) {
+entry:
+  %enable = getelementptr inbounds %motor_interface, %motor_interface* %0, i32 0, i32 0
+  %running = getelementptr inbounds %motor_interface, %motor_interface* %0, i32 0, i32 1
+  %speed = getelementptr inbounds %motor_interface, %motor_interface* %0, i32 0, i32 2
+  %load_enable = load i1, i1* %enable, align 1
+  store i1 %load_enable, i1* %running, align 1
+  ret void
+}
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %motors = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %r = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %tmpVar = getelementptr inbounds [2 x %motor_interface], [2 x %motor_interface]* %motors, i32 0, i32 1
+  br label %input
+
+input:                                            ; preds = %entry
+  %1 = getelementptr inbounds %motor_interface, %motor_interface* %tmpVar, i32 0, i32 0
+  store i1 true, i1* %1, align 1
+  br label %call
+
+call:                                             ; preds = %input
+  call void @motor(%motor_interface* %tmpVar)
+  br label %output
+
+output:                                           ; preds = %call
+  br label %continue
+
+continue:                                         ; preds = %output
+  %tmpVar1 = getelementptr inbounds [2 x %motor_interface], [2 x %motor_interface]* %motors, i32 0, i32 1
+  %running = getelementptr inbounds %motor_interface, %motor_interface* %tmpVar1, i32 0, i32 1
+  %load_running = load i1, i1* %running, align 1
+  store i1 %load_running, i1* %r, align 1
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn function_block_qualified_instance_call() {
+    let result = codegen!(
+        "
+        FUNCTION_BLOCK foo
+        VAR
+          bar_inst : bar;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK bar
+        END_FUNCTION_BLOCK
+
+        PROGRAM prg
+        VAR
+          foo_inst : foo;
+        END_VAR
+          foo_inst.bar_inst();
+        END_PROGRAM
+      "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { %foo_interface }
 %foo_interface = type { %bar_interface }
 %bar_interface = type {}
 
@@ -3704,6 +4175,47 @@ fn multidim_array_declaration() {
     assert_eq!(result, expected);
 }
 
+#[test]
+fn variable_length_array_access_and_bounds() {
+    let result = codegen!(
+        "
+        PROGRAM prg
+            VAR_IN_OUT
+                arr : ARRAY[*] OF DINT;
+            END_VAR
+            VAR
+                x : DINT;
+            END_VAR
+            x := UPPER_BOUND(arr, 1);
+            x := arr[x];
+        END_PROGRAM
+        "
+    );
+
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("{ i32*, [2 x i32] }", "arr"), ("i32", "x")],
+        "void",
+        "",
+        "",
+        r#"%1 = getelementptr inbounds { i32*, [2 x i32] }, { i32*, [2 x i32] }* %arr, i32 0, i32 1, i32 1
+  %2 = load i32, i32* %1, align 4
+  store i32 %2, i32* %x, align 4
+  %3 = getelementptr inbounds { i32*, [2 x i32] }, { i32*, [2 x i32] }* %arr, i32 0, i32 1, i32 0
+  %4 = load i32, i32* %3, align 4
+  %load_x = load i32, i32* %x, align 4
+  %5 = sub i32 %load_x, %4
+  %6 = getelementptr inbounds { i32*, [2 x i32] }, { i32*, [2 x i32] }* %arr, i32 0, i32 0
+  %7 = load i32*, i32** %6, align 8
+  %tmpVar = getelementptr inbounds i32, i32* %7, i32 %5
+  %load_tmpVar = load i32, i32* %tmpVar, align 4
+  store i32 %load_tmpVar, i32* %x, align 4
+  ret void
+"#,
+    );
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn multidim_array_access() {
     let result = codegen!(
@@ -4377,6 +4889,70 @@ source_filename = "main"
 "#;
     assert_eq!(expected, result);
 }
+#[test]
+fn function_block_instances_are_initialized_with_nested_initializers() {
+    let result = codegen!(
+        "
+        PROGRAM prg
+        VAR
+            t1 : timer := (PT := T#500ms);
+            s : sequence := (first := (PT := T#1s), steps := 3);
+        END_VAR
+        END_PROGRAM
+
+        FUNCTION_BLOCK sequence
+        VAR
+            first : timer;
+            second : timer := (PT := T#2s);
+            steps : DINT;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        FUNCTION_BLOCK timer
+        VAR_INPUT
+            PT : TIME;
+        END_VAR
+        VAR_OUTPUT
+            Q : BOOL;
+        END_VAR
+        END_FUNCTION_BLOCK
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { %timer_interface, %sequence_interface }
+%timer_interface = type { i64, i1 }
+%sequence_interface = type { %timer_interface, %timer_interface, i32 }
+
+@prg_instance = global %prg_interface { %timer_interface { i64 500000000, i1 false }, %sequence_interface { %timer_interface { i64 1000000000, i1 false }, %timer_interface { i64 2000000000, i1 false }, i32 3 } }
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %t1 = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %s = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  ret void
+}
+
+define void @sequence(%sequence_interface* %0) {
+entry:
+  %first = getelementptr inbounds %sequence_interface, %sequence_interface* %0, i32 0, i32 0
+  %second = getelementptr inbounds %sequence_interface, %sequence_interface* %0, i32 0, i32 1
+  %steps = getelementptr inbounds %sequence_interface, %sequence_interface* %0, i32 0, i32 2
+  ret void
+}
+
+define void @timer(%timer_interface* %0) {
+entry:
+  %PT = getelementptr inbounds %timer_interface, %timer_interface* %0, i32 0, i32 0
+  %Q = getelementptr inbounds %timer_interface, %timer_interface* %0, i32 0, i32 1
+  ret void
+}
+"#;
+    assert_eq!(expected, result);
+}
+
 #[test]
 fn sub_range_type_calls_check_function_missing() {
     let source = "
@@ -4506,3 +5082,937 @@ continue:                                         ; preds = %output
 "#;
     assert_eq!(expected, result);
 }
+
+#[test]
+fn array_accesses_are_passed_to_check_bounds_if_declared() {
+    let source = "
+            FUNCTION CheckBounds : DINT
+            VAR_INPUT
+              index, lower, upper : DINT;
+            END_VAR
+            CheckBounds := index;
+            END_FUNCTION
+
+            PROGRAM main
+            VAR
+              arr : ARRAY[1..5] OF INT;
+              i : DINT;
+            END_VAR
+            arr[i] := 3;
+            END_PROGRAM
+           ";
+    let result = codegen!(source);
+
+    // we expect arr[CheckBounds(i, 1, 5)] := 3;
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%main_interface = type { [5 x i16], i32 }
+%CheckBounds_interface = type { i32, i32, i32 }
+
+@main_instance = global %main_interface zeroinitializer
+
+define i32 @CheckBounds(%CheckBounds_interface* %0) {
+entry:
+  %index = getelementptr inbounds %CheckBounds_interface, %CheckBounds_interface* %0, i32 0, i32 0
+  %lower = getelementptr inbounds %CheckBounds_interface, %CheckBounds_interface* %0, i32 0, i32 1
+  %upper = getelementptr inbounds %CheckBounds_interface, %CheckBounds_interface* %0, i32 0, i32 2
+  %CheckBounds = alloca i32, align 4
+  %load_index = load i32, i32* %index, align 4
+  store i32 %load_index, i32* %CheckBounds, align 4
+  %CheckBounds_ret = load i32, i32* %CheckBounds, align 4
+  ret i32 %CheckBounds_ret
+}
+
+define void @main(%main_interface* %0) {
+entry:
+  %arr = getelementptr inbounds %main_interface, %main_interface* %0, i32 0, i32 0
+  %i = getelementptr inbounds %main_interface, %main_interface* %0, i32 0, i32 1
+  %load_i = load i32, i32* %i, align 4
+  %CheckBounds_instance = alloca %CheckBounds_interface, align 8
+  %1 = getelementptr inbounds %CheckBounds_interface, %CheckBounds_interface* %CheckBounds_instance, i32 0, i32 0
+  store i32 %load_i, i32* %1, align 4
+  %2 = getelementptr inbounds %CheckBounds_interface, %CheckBounds_interface* %CheckBounds_instance, i32 0, i32 1
+  store i32 1, i32* %2, align 4
+  %3 = getelementptr inbounds %CheckBounds_interface, %CheckBounds_interface* %CheckBounds_instance, i32 0, i32 2
+  store i32 5, i32* %3, align 4
+  %call = call i32 @CheckBounds(%CheckBounds_interface* %CheckBounds_instance)
+  %4 = sub i32 %call, 1
+  %tmpVar = getelementptr inbounds [5 x i16], [5 x i16]* %arr, i32 0, i32 %4
+  store i16 3, i16* %tmpVar, align 2
+  ret void
+}
+"#;
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn array_accesses_trap_if_out_of_bounds_with_bounds_checks() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+  arr : ARRAY[0..3] OF INT;
+  i : INT;
+END_VAR
+arr[i] := 3;
+END_PROGRAM
+"#,
+        crate::codegen::CodeGenOptions {
+            bounds_checks: true,
+            ..Default::default()
+        }
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("[4 x i16]", "arr"), ("i16", "i")],
+        "void",
+        "",
+        "",
+        r#"%load_i = load i16, i16* %i, align 2
+  %1 = sext i16 %load_i to i32
+  %2 = icmp slt i32 %1, 0
+  %3 = icmp sgt i32 %1, 3
+  %4 = or i1 %2, %3
+  br i1 %4, label %out_of_bounds, label %in_bounds
+
+out_of_bounds:                                    ; preds = %entry
+  call void @llvm.trap()
+  unreachable
+
+in_bounds:                                        ; preds = %entry
+  %tmpVar = getelementptr inbounds [4 x i16], [4 x i16]* %arr, i32 0, i32 %1
+  store i16 3, i16* %tmpVar, align 2
+  ret void
+"#,
+    ) + r#"
+; Function Attrs: cold noreturn nounwind
+declare void @llvm.trap() #0
+
+attributes #0 = { cold noreturn nounwind }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn division_divisors_are_passed_to_check_div_dint_if_declared() {
+    let source = "
+            FUNCTION CheckDivDInt : DINT
+            VAR_INPUT
+              divisor : DINT;
+            END_VAR
+            CheckDivDInt := divisor;
+            END_FUNCTION
+
+            PROGRAM main
+            VAR
+              a, b : DINT;
+            END_VAR
+            a := a / b;
+            END_PROGRAM
+           ";
+    let result = codegen!(source);
+
+    // we expect a := a / CheckDivDInt(b);
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%main_interface = type { i32, i32 }
+%CheckDivDInt_interface = type { i32 }
+
+@main_instance = global %main_interface zeroinitializer
+
+define i32 @CheckDivDInt(%CheckDivDInt_interface* %0) {
+entry:
+  %divisor = getelementptr inbounds %CheckDivDInt_interface, %CheckDivDInt_interface* %0, i32 0, i32 0
+  %CheckDivDInt = alloca i32, align 4
+  %load_divisor = load i32, i32* %divisor, align 4
+  store i32 %load_divisor, i32* %CheckDivDInt, align 4
+  %CheckDivDInt_ret = load i32, i32* %CheckDivDInt, align 4
+  ret i32 %CheckDivDInt_ret
+}
+
+define void @main(%main_interface* %0) {
+entry:
+  %a = getelementptr inbounds %main_interface, %main_interface* %0, i32 0, i32 0
+  %b = getelementptr inbounds %main_interface, %main_interface* %0, i32 0, i32 1
+  %load_a = load i32, i32* %a, align 4
+  %load_b = load i32, i32* %b, align 4
+  %CheckDivDInt_instance = alloca %CheckDivDInt_interface, align 8
+  %1 = getelementptr inbounds %CheckDivDInt_interface, %CheckDivDInt_interface* %CheckDivDInt_instance, i32 0, i32 0
+  store i32 %load_b, i32* %1, align 4
+  %call = call i32 @CheckDivDInt(%CheckDivDInt_interface* %CheckDivDInt_instance)
+  %tmpVar = sdiv i32 %load_a, %call
+  store i32 %tmpVar, i32* %a, align 4
+  ret void
+}
+"#;
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn overflowing_additions_call_the_trap_handler_with_overflow_checks() {
+    let result = codegen!(
+        r#"@EXTERNAL FUNCTION on_trap : DINT
+VAR_INPUT
+  from_offset, to_offset : DINT;
+END_VAR
+END_FUNCTION
+
+PROGRAM prg
+VAR
+  a, b : DINT;
+END_VAR
+a := a + b;
+END_PROGRAM
+"#,
+        crate::codegen::CodeGenOptions {
+            overflow_checks: true,
+            trap_handler: Some("on_trap".to_string()),
+            ..Default::default()
+        }
+    );
+
+    // the handler is called with the start- and end-offset of `a + b`
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { i32, i32 }
+%on_trap_interface = type { i32, i32 }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+declare i32 @on_trap(%on_trap_interface*)
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %a = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %b = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %load_a = load i32, i32* %a, align 4
+  %load_b = load i32, i32* %b, align 4
+  %1 = call { i32, i1 } @llvm.sadd.with.overflow.i32(i32 %load_a, i32 %load_b)
+  %tmpVar = extractvalue { i32, i1 } %1, 0
+  %2 = extractvalue { i32, i1 } %1, 1
+  br i1 %2, label %overflow, label %no_overflow
+
+overflow:                                         ; preds = %entry
+  %on_trap_instance = alloca %on_trap_interface, align 8
+  %3 = getelementptr inbounds %on_trap_interface, %on_trap_interface* %on_trap_instance, i32 0, i32 0
+  store i32 143, i32* %3, align 4
+  %4 = getelementptr inbounds %on_trap_interface, %on_trap_interface* %on_trap_instance, i32 0, i32 1
+  store i32 148, i32* %4, align 4
+  %call = call i32 @on_trap(%on_trap_interface* %on_trap_instance)
+  call void @llvm.trap()
+  unreachable
+
+no_overflow:                                      ; preds = %entry
+  store i32 %tmpVar, i32* %a, align 4
+  ret void
+}
+
+; Function Attrs: nounwind readnone speculatable willreturn
+declare { i32, i1 } @llvm.sadd.with.overflow.i32(i32, i32) #0
+
+; Function Attrs: cold noreturn nounwind
+declare void @llvm.trap() #1
+
+attributes #0 = { nounwind readnone speculatable willreturn }
+attributes #1 = { cold noreturn nounwind }
+"#;
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn unsigned_multiplications_are_checked_for_unsigned_overflows() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+  x : UDINT;
+  y : UDINT;
+END_VAR
+x := x * y;
+END_PROGRAM
+"#,
+        crate::codegen::CodeGenOptions {
+            overflow_checks: true,
+            ..Default::default()
+        }
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i32", "x"), ("i32", "y")],
+        "void",
+        "",
+        "",
+        r#"%load_x = load i32, i32* %x, align 4
+  %load_y = load i32, i32* %y, align 4
+  %1 = call { i32, i1 } @llvm.umul.with.overflow.i32(i32 %load_x, i32 %load_y)
+  %tmpVar = extractvalue { i32, i1 } %1, 0
+  %2 = extractvalue { i32, i1 } %1, 1
+  br i1 %2, label %overflow, label %no_overflow
+
+overflow:                                         ; preds = %entry
+  call void @llvm.trap()
+  unreachable
+
+no_overflow:                                      ; preds = %entry
+  store i32 %tmpVar, i32* %x, align 4
+  ret void
+"#,
+    ) + r#"
+; Function Attrs: nounwind readnone speculatable willreturn
+declare { i32, i1 } @llvm.umul.with.overflow.i32(i32, i32) #0
+
+; Function Attrs: cold noreturn nounwind
+declare void @llvm.trap() #1
+
+attributes #0 = { nounwind readnone speculatable willreturn }
+attributes #1 = { cold noreturn nounwind }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn promoted_operations_are_checked_for_overflows_in_their_declared_type() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+  x : INT;
+  y : SINT;
+END_VAR
+x := x + y;
+END_PROGRAM
+"#,
+        crate::codegen::CodeGenOptions {
+            overflow_checks: true,
+            ..Default::default()
+        }
+    );
+    // the operands are promoted to DINT but an overflow of INT is detected
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i16", "x"), ("i8", "y")],
+        "void",
+        "",
+        "",
+        r#"%load_x = load i16, i16* %x, align 2
+  %load_y = load i8, i8* %y, align 1
+  %1 = sext i16 %load_x to i32
+  %2 = sext i8 %load_y to i32
+  %3 = trunc i32 %1 to i16
+  %4 = trunc i32 %2 to i16
+  %5 = call { i16, i1 } @llvm.sadd.with.overflow.i16(i16 %3, i16 %4)
+  %tmpVar = extractvalue { i16, i1 } %5, 0
+  %6 = extractvalue { i16, i1 } %5, 1
+  br i1 %6, label %overflow, label %no_overflow
+
+overflow:                                         ; preds = %entry
+  call void @llvm.trap()
+  unreachable
+
+no_overflow:                                      ; preds = %entry
+  %7 = sext i16 %tmpVar to i32
+  %8 = trunc i32 %7 to i16
+  store i16 %8, i16* %x, align 2
+  ret void
+"#,
+    ) + r#"
+; Function Attrs: nounwind readnone speculatable willreturn
+declare { i16, i1 } @llvm.sadd.with.overflow.i16(i16, i16) #0
+
+; Function Attrs: cold noreturn nounwind
+declare void @llvm.trap() #1
+
+attributes #0 = { nounwind readnone speculatable willreturn }
+attributes #1 = { cold noreturn nounwind }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn zero_divisors_and_overflowing_divisions_trap_with_overflow_checks() {
+    let result = codegen!(
+        r#"PROGRAM prg
+VAR
+  a, b : DINT;
+END_VAR
+a := a / b;
+END_PROGRAM
+"#,
+        crate::codegen::CodeGenOptions {
+            overflow_checks: true,
+            ..Default::default()
+        }
+    );
+    let expected = generate_program_boiler_plate(
+        "prg",
+        &[("i32", "a"), ("i32", "b")],
+        "void",
+        "",
+        "",
+        r#"%load_a = load i32, i32* %a, align 4
+  %load_b = load i32, i32* %b, align 4
+  %1 = icmp eq i32 %load_b, 0
+  br i1 %1, label %division_by_zero, label %nonzero_divisor
+
+division_by_zero:                                 ; preds = %entry
+  call void @llvm.trap()
+  unreachable
+
+nonzero_divisor:                                  ; preds = %entry
+  %2 = icmp eq i32 %load_a, -2147483648
+  %3 = icmp eq i32 %load_b, -1
+  %4 = and i1 %2, %3
+  br i1 %4, label %overflow, label %no_overflow
+
+overflow:                                         ; preds = %nonzero_divisor
+  call void @llvm.trap()
+  unreachable
+
+no_overflow:                                      ; preds = %nonzero_divisor
+  %tmpVar = sdiv i32 %load_a, %load_b
+  store i32 %tmpVar, i32* %a, align 4
+  ret void
+"#,
+    ) + r#"
+; Function Attrs: cold noreturn nounwind
+declare void @llvm.trap() #0
+
+attributes #0 = { cold noreturn nounwind }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn whole_structs_are_copied_and_compared_element_wise() {
+    let result = codegen!(
+        "
+        TYPE MyStruct: STRUCT
+          a: DINT;
+          b: DINT;
+        END_STRUCT
+        END_TYPE
+
+        PROGRAM MainProg
+        VAR
+          p1, p2 : MyStruct;
+          same : BOOL;
+        END_VAR
+          p1 := p2;
+          same := p1 = p2;
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%MainProg_interface = type { %MyStruct, %MyStruct, i1 }
+%MyStruct = type { i32, i32 }
+
+@MainProg_instance = global %MainProg_interface zeroinitializer
+
+define void @MainProg(%MainProg_interface* %0) {
+entry:
+  %p1 = getelementptr inbounds %MainProg_interface, %MainProg_interface* %0, i32 0, i32 0
+  %p2 = getelementptr inbounds %MainProg_interface, %MainProg_interface* %0, i32 0, i32 1
+  %same = getelementptr inbounds %MainProg_interface, %MainProg_interface* %0, i32 0, i32 2
+  %1 = bitcast %MyStruct* %p1 to i8*
+  %2 = bitcast %MyStruct* %p2 to i8*
+  call void @llvm.memmove.p0i8.p0i8.i64(i8* align 1 %1, i8* align 1 %2, i64 ptrtoint (%MyStruct* getelementptr (%MyStruct, %MyStruct* null, i32 1) to i64), i1 false)
+  %load_p1 = load %MyStruct, %MyStruct* %p1, align 4
+  %load_p2 = load %MyStruct, %MyStruct* %p2, align 4
+  %3 = extractvalue %MyStruct %load_p1, 0
+  %4 = extractvalue %MyStruct %load_p2, 0
+  %5 = icmp eq i32 %3, %4
+  %6 = extractvalue %MyStruct %load_p1, 1
+  %7 = extractvalue %MyStruct %load_p2, 1
+  %8 = icmp eq i32 %6, %7
+  %9 = and i1 %5, %8
+  store i1 %9, i1* %same, align 1
+  ret void
+}
+
+; Function Attrs: argmemonly nounwind willreturn
+declare void @llvm.memmove.p0i8.p0i8.i64(i8* nocapture writeonly, i8* nocapture readonly, i64, i1 immarg) #0
+
+attributes #0 = { argmemonly nounwind willreturn }
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn aggregate_literals_are_generated_at_runtime() {
+    let result = codegen!(
+        "
+        TYPE Point: STRUCT
+          x: REAL;
+          y: REAL;
+        END_STRUCT
+        END_TYPE
+
+        PROGRAM prg
+        VAR
+          pos : Point;
+          a : REAL;
+          buf : ARRAY[0..3] OF DINT;
+          n : DINT;
+        END_VAR
+          pos := (x := 1.0, y := a + 2.0);
+          buf := [0, 1, n];
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { %Point, float, [4 x i32], i32 }
+%Point = type { float, float }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %pos = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %a = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %buf = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 2
+  %n = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 3
+  %1 = alloca %Point, align 8
+  %x = getelementptr inbounds %Point, %Point* %1, i32 0, i32 0
+  store float 1.000000e+00, float* %x, align 4
+  %y = getelementptr inbounds %Point, %Point* %1, i32 0, i32 1
+  %load_a = load float, float* %a, align 4
+  %tmpVar = fadd float %load_a, 2.000000e+00
+  store float %tmpVar, float* %y, align 4
+  %2 = load %Point, %Point* %1, align 4
+  store %Point %2, %Point* %pos, align 4
+  %3 = alloca [4 x i32], align 4
+  store [4 x i32] zeroinitializer, [4 x i32]* %3, align 4
+  %4 = getelementptr inbounds [4 x i32], [4 x i32]* %3, i32 0, i32 0
+  store i32 0, i32* %4, align 4
+  %5 = getelementptr inbounds [4 x i32], [4 x i32]* %3, i32 0, i32 1
+  store i32 1, i32* %5, align 4
+  %6 = getelementptr inbounds [4 x i32], [4 x i32]* %3, i32 0, i32 2
+  %load_n = load i32, i32* %n, align 4
+  store i32 %load_n, i32* %6, align 4
+  %7 = load [4 x i32], [4 x i32]* %3, align 4
+  store [4 x i32] %7, [4 x i32]* %buf, align 4
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn strings_are_returned_through_a_buffer_allocated_by_the_caller() {
+    let result = codegen!(
+        "
+        FUNCTION greet : STRING
+        greet := 'hello';
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+          s : STRING;
+        END_VAR
+        s := greet();
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { [81 x i8] }
+%greet_interface = type {}
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @greet([81 x i8]* sret %0, %greet_interface* %1) {
+entry:
+  store [6 x i8] c"hello\00", [81 x i8]* %0, align 1
+  ret void
+}
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %s = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %greet_instance = alloca %greet_interface, align 8
+  br label %input
+
+input:                                            ; preds = %entry
+  br label %call
+
+call:                                             ; preds = %input
+  %greet_result = alloca [81 x i8], align 1
+  call void @greet([81 x i8]* %greet_result, %greet_interface* %greet_instance)
+  br label %output
+
+output:                                           ; preds = %call
+  br label %continue
+
+continue:                                         ; preds = %output
+  %call_result = load [81 x i8], [81 x i8]* %greet_result, align 1
+  store [81 x i8] %call_result, [81 x i8]* %s, align 1
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn external_functions_with_the_c_calling_convention_get_native_arguments() {
+    let result = codegen!(
+        "
+        {attribute 'calling_convention' := 'C'}
+        @EXTERNAL FUNCTION scale : DINT
+        VAR_INPUT
+          value : DINT;
+          factor : REAL;
+        END_VAR
+        VAR_IN_OUT
+          total : DINT;
+        END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+          sum : DINT;
+          result : DINT;
+        END_VAR
+        result := scale(2, 1.5, sum);
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { i32, i32 }
+%scale_interface = type { i32, float, i32* }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+declare i32 @scale(i32, float, i32*)
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %sum = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %result = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %scale_instance = alloca %scale_interface, align 8
+  br label %input
+
+input:                                            ; preds = %entry
+  %1 = getelementptr inbounds %scale_interface, %scale_interface* %scale_instance, i32 0, i32 0
+  store i32 2, i32* %1, align 4
+  %2 = getelementptr inbounds %scale_interface, %scale_interface* %scale_instance, i32 0, i32 1
+  store float 1.500000e+00, float* %2, align 4
+  %3 = getelementptr inbounds %scale_interface, %scale_interface* %scale_instance, i32 0, i32 2
+  store i32* %sum, i32** %3, align 8
+  br label %call
+
+call:                                             ; preds = %input
+  %value = getelementptr inbounds %scale_interface, %scale_interface* %scale_instance, i32 0, i32 0
+  %load_value = load i32, i32* %value, align 4
+  %factor = getelementptr inbounds %scale_interface, %scale_interface* %scale_instance, i32 0, i32 1
+  %load_factor = load float, float* %factor, align 4
+  %total = getelementptr inbounds %scale_interface, %scale_interface* %scale_instance, i32 0, i32 2
+  %load_total = load i32*, i32** %total, align 8
+  %call1 = call i32 @scale(i32 %load_value, float %load_factor, i32* %load_total)
+  br label %output
+
+output:                                           ; preds = %call
+  br label %continue
+
+continue:                                         ; preds = %output
+  store i32 %call1, i32* %result, align 4
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn functions_with_the_c_calling_convention_copy_their_arguments_into_a_local_instance() {
+    let result = codegen!(
+        "
+        {attribute 'calling_convention' := 'C'}
+        FUNCTION add_to : DINT
+        VAR_INPUT
+          a : DINT;
+        END_VAR
+        VAR_IN_OUT
+          total : DINT;
+        END_VAR
+        VAR_OUTPUT
+          doubled : DINT;
+        END_VAR
+        total := total + a;
+        doubled := a * 2;
+        add_to := total;
+        END_FUNCTION
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%add_to_interface = type { i32, i32*, i32 }
+
+define i32 @add_to(i32 %0, i32* %1, i32* %2) {
+entry:
+  %add_to_instance = alloca %add_to_interface, align 8
+  %a = getelementptr inbounds %add_to_interface, %add_to_interface* %add_to_instance, i32 0, i32 0
+  store i32 %0, i32* %a, align 4
+  %total = getelementptr inbounds %add_to_interface, %add_to_interface* %add_to_instance, i32 0, i32 1
+  store i32* %1, i32** %total, align 8
+  %add_to = alloca i32, align 4
+  %deref = load i32*, i32** %total, align 8
+  %deref1 = load i32*, i32** %total, align 8
+  %load_total = load i32, i32* %deref1, align 4
+  %load_a = load i32, i32* %a, align 4
+  %tmpVar = add i32 %load_total, %load_a
+  store i32 %tmpVar, i32* %deref, align 4
+  %load_a2 = load i32, i32* %a, align 4
+  %tmpVar3 = mul i32 %load_a2, 2
+  store i32 %tmpVar3, i32* %2, align 4
+  %deref4 = load i32*, i32** %total, align 8
+  %load_total5 = load i32, i32* %deref4, align 4
+  store i32 %load_total5, i32* %add_to, align 4
+  %add_to_ret = load i32, i32* %add_to, align 4
+  ret i32 %add_to_ret
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn generic_functions_are_generated_for_the_types_they_are_called_with() {
+    let result = codegen!(
+        "
+        FUNCTION twice : ANY_INT
+        VAR_INPUT
+          value : ANY_INT;
+        END_VAR
+        twice := value + value;
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+          x : DINT;
+        END_VAR
+        x := twice(x);
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { i32 }
+%twice__DINT_interface = type { i32 }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %twice__DINT_instance = alloca %twice__DINT_interface, align 8
+  br label %input
+
+input:                                            ; preds = %entry
+  %1 = getelementptr inbounds %twice__DINT_interface, %twice__DINT_interface* %twice__DINT_instance, i32 0, i32 0
+  %load_x = load i32, i32* %x, align 4
+  store i32 %load_x, i32* %1, align 4
+  br label %call
+
+call:                                             ; preds = %input
+  %call1 = call i32 @twice__DINT(%twice__DINT_interface* %twice__DINT_instance)
+  br label %output
+
+output:                                           ; preds = %call
+  br label %continue
+
+continue:                                         ; preds = %output
+  store i32 %call1, i32* %x, align 4
+  ret void
+}
+
+define i32 @twice__DINT(%twice__DINT_interface* %0) {
+entry:
+  %value = getelementptr inbounds %twice__DINT_interface, %twice__DINT_interface* %0, i32 0, i32 0
+  %twice__DINT = alloca i32, align 4
+  %load_value = load i32, i32* %value, align 4
+  %load_value1 = load i32, i32* %value, align 4
+  %tmpVar = add i32 %load_value, %load_value1
+  store i32 %tmpVar, i32* %twice__DINT, align 4
+  %twice__DINT_ret = load i32, i32* %twice__DINT, align 4
+  ret i32 %twice__DINT_ret
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn union_members_share_the_union_storage() {
+    let result = codegen!(
+        "
+        TYPE Bytes : UNION
+          a : DWORD;
+          b : ARRAY[0..3] OF BYTE;
+        END_UNION
+        END_TYPE
+
+        TYPE Padded : UNION
+          l : LINT;
+          s : ARRAY[0..11] OF BYTE;
+        END_UNION
+        END_TYPE
+
+        PROGRAM prg
+        VAR
+          u : Bytes;
+          p : Padded;
+          x : BYTE;
+        END_VAR
+          u.a := 16909060;
+          x := u.b[0];
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { %Bytes, %Padded, i8 }
+%Bytes = type { i32 }
+%Padded = type { i64, [8 x i8] }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %u = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %p = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 2
+  %a = bitcast %Bytes* %u to i32*
+  store i32 16909060, i32* %a, align 4
+  %b = bitcast %Bytes* %u to [4 x i8]*
+  %tmpVar = getelementptr inbounds [4 x i8], [4 x i8]* %b, i32 0, i32 0
+  %load_tmpVar = load i8, i8* %tmpVar, align 1
+  store i8 %load_tmpVar, i8* %x, align 1
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn packed_structs_are_padded_explicitly_and_share_bytes_between_bits() {
+    let result = codegen!(
+        "
+        {attribute 'pack_mode' := '2'}
+        TYPE Packed : STRUCT
+          a : BYTE;
+          b : DINT;
+          on : BIT;
+          ready : BIT := TRUE;
+          c : BYTE;
+        END_STRUCT
+        END_TYPE
+
+        PROGRAM prg
+        VAR
+          p : Packed;
+          x : BOOL;
+        END_VAR
+          p.on := x;
+          x := p.ready;
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { %Packed, i1 }
+%Packed = type <{ i8, [1 x i8], i32, i8, i8 }>
+
+@prg_instance = global %prg_interface { %Packed <{ i8 0, [1 x i8] zeroinitializer, i32 0, i8 2, i8 0 }>, i1 false }
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %p = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  %on = getelementptr inbounds %Packed, %Packed* %p, i32 0, i32 3
+  %load_x = load i1, i1* %x, align 1
+  %1 = load i8, i8* %on, align 1
+  %2 = and i8 %1, -2
+  %3 = zext i1 %load_x to i8
+  %4 = shl i8 %3, 0
+  %5 = or i8 %2, %4
+  store i8 %5, i8* %on, align 1
+  %ready = getelementptr inbounds %Packed, %Packed* %p, i32 0, i32 3
+  %6 = load i8, i8* %ready, align 1
+  %7 = lshr i8 %6, 1
+  %load_ = trunc i8 %7 to i1
+  store i1 %load_, i1* %x, align 1
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn pointers_are_assigned_and_dereferenced() {
+    let result = codegen!(
+        "
+        TYPE IntPtr : POINTER TO DINT; END_TYPE
+
+        PROGRAM prg
+        VAR
+          p : IntPtr;
+          x : DINT;
+        END_VAR
+          p := 0;
+          x := p^;
+          p^ := 5;
+        END_PROGRAM
+        "
+    );
+
+    let expected = r#"; ModuleID = 'main'
+source_filename = "main"
+
+%prg_interface = type { i32*, i32 }
+
+@prg_instance = global %prg_interface zeroinitializer
+
+define void @prg(%prg_interface* %0) {
+entry:
+  %p = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 0
+  %x = getelementptr inbounds %prg_interface, %prg_interface* %0, i32 0, i32 1
+  store i32* null, i32** %p, align 8
+  %deref = load i32*, i32** %p, align 8
+  %load_ = load i32, i32* %deref, align 4
+  store i32 %load_, i32* %x, align 4
+  %deref1 = load i32*, i32** %p, align 8
+  store i32 5, i32* %deref1, align 4
+  ret void
+}
+"#;
+
+    assert_eq!(result, expected);
+}
@@ -176,3 +176,694 @@ fn invalid_struct_access_in_array_access_should_be_reported_with_line_number() {
         panic!("expected code-gen error but got none")
     }
 }
+
+#[test]
+fn shift_of_non_bit_value_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg 
+            VAR
+                x : REAL;
+            END_VAR
+            x := SHL(x, 2);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "SHL expects an ANY_BIT value, but found REAL".into(),
+                (105..106).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn shift_of_integer_value_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg 
+            VAR
+                x : INT;
+            END_VAR
+            x := SHL(x, 2);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "SHL expects an ANY_BIT value, but found INT".into(),
+                (104..105).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn incompatible_inputs_of_max_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg 
+            VAR
+                x : INT;
+                s : STRING;
+            END_VAR
+            x := MAX(x, s);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "MAX expects inputs of the same type, but found INT and String".into(),
+                (135..136).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn addition_of_two_dates_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg 
+            VAR
+                a : DATE_AND_TIME;
+                b : DATE_AND_TIME;
+            END_VAR
+            a + b;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "invalid types, cannot generate DATE_AND_TIME + DATE_AND_TIME".into(),
+                (140..145).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn adding_a_time_to_a_date_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg 
+            VAR
+                t : TIME;
+                d : DATE;
+            END_VAR
+            t := d + t;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "invalid types, cannot generate DATE + TIME".into(),
+                (127..132).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn division_of_a_number_by_a_duration_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                t : TIME;
+                i : INT;
+            END_VAR
+            t := i / t;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "invalid types, cannot generate INT / TIME".into(),
+                (125..130).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn mismatched_variable_length_array_argument_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION foo : DINT
+            VAR_IN_OUT
+                arr : ARRAY[*] OF DINT;
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            VAR
+                x : ARRAY[0..2] OF INT;
+            END_VAR
+            foo(x);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Expected an array of DINT with 1 dimension(s) but found __prg_x".into(),
+                (246..247).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn assigning_structs_of_different_types_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        TYPE Point : STRUCT
+            x : DINT;
+            y : DINT;
+        END_STRUCT
+        END_TYPE
+
+        TYPE Size : STRUCT
+            width : DINT;
+            height : DINT;
+        END_STRUCT
+        END_TYPE
+
+        PROGRAM prg
+            VAR
+                p : Point;
+                s : Size;
+            END_VAR
+            p := s;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::casting_error("Size", "Point", (353..354).into()),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn comparing_arrays_of_different_lengths_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                a : ARRAY[0..2] OF DINT;
+                b : ARRAY[0..3] OF DINT;
+                x : BOOL;
+            END_VAR
+            x := a = b;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Cannot compare __prg_a with __prg_b".into(),
+                (182..187).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn mixing_formal_and_non_formal_arguments_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION foo : DINT
+            VAR_INPUT
+                a : DINT;
+                b : DINT;
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            foo(a := 1, 2);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Cannot mix formal and non-formal arguments in call to foo".into(),
+                (189..190).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn too_many_arguments_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION foo : DINT
+            VAR_INPUT
+                a : DINT;
+                b : DINT;
+            END_VAR
+        END_FUNCTION
+
+        PROGRAM prg
+            foo(1, 2, 3);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Too many arguments in call to foo, expected at most 2 but found 3".into(),
+                (187..188).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn calling_an_array_element_that_is_no_function_block_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                numbers : ARRAY[1..3] OF DINT;
+            END_VAR
+            numbers[2]();
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error("DINT is not callable".into(), (116..125).into()),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn array_literals_with_too_many_elements_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                buf : ARRAY[0..1] OF DINT;
+                n : DINT;
+            END_VAR
+            buf := [n, n, n];
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Expected at most 2 elements for Array __prg_buf, but found 3.".into(),
+                (145..154).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn dereferencing_a_non_pointer_should_be_reported_with_line_number() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                x : INT;
+            END_VAR
+            x^ := 2;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Cannot dereference INT, it is not a pointer".into(),
+                (94..95).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn assigning_to_a_constant_input_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        TYPE Recipe : STRUCT
+            a : INT;
+        END_STRUCT END_TYPE
+
+        FUNCTION_BLOCK fb
+        VAR_INPUT CONSTANT
+            recipe : Recipe;
+        END_VAR
+            recipe.a := 1;
+        END_FUNCTION_BLOCK
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Cannot assign to the constant input recipe".into(),
+                (190..196).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn passing_a_constant_input_to_an_inout_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION inc : INT
+        VAR_IN_OUT
+            x : INT;
+        END_VAR
+            x := x + 1;
+        END_FUNCTION
+
+        FUNCTION_BLOCK fb
+        VAR_INPUT {attribute 'by_ref'}
+            x : INT;
+        END_VAR
+            inc(x);
+        END_FUNCTION_BLOCK
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Cannot assign to the constant input x".into(),
+                (248..249).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn omitting_a_constant_input_of_a_function_block_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION_BLOCK fb
+        VAR_INPUT CONSTANT
+            x : INT;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        PROGRAM prg
+        VAR
+            inst : fb;
+        END_VAR
+            inst(x := 1);
+            inst();
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Missing argument for the constant input x in call to fb".into(),
+                (228..232).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn accessing_a_constant_input_from_outside_of_its_function_block_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION_BLOCK fb
+        VAR_INPUT CONSTANT
+            x : INT;
+        END_VAR
+        END_FUNCTION_BLOCK
+
+        PROGRAM prg
+        VAR
+            inst : fb;
+            y : INT;
+        END_VAR
+            inst(x := 1);
+            y := inst.x;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "The constant input fb.x can only be accessed inside of fb".into(),
+                (259..260).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn ref_assignment_to_a_non_reference_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                x : INT;
+                y : INT;
+            END_VAR
+            x REF= y;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Expected a REFERENCE but found INT".into(),
+                (119..120).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn ref_assignment_of_a_variable_with_another_type_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        PROGRAM prg
+            VAR
+                r : REFERENCE TO INT;
+                y : DINT;
+            END_VAR
+            r REF= y;
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::casting_error("DINT", "INT", (140..141).into()),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn ref_assignment_of_a_constant_input_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        FUNCTION_BLOCK fb
+        VAR_INPUT CONSTANT
+            x : INT;
+        END_VAR
+        VAR
+            r : REFERENCE TO INT;
+        END_VAR
+            r REF= x;
+            r := 5;
+        END_FUNCTION_BLOCK
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "Cannot assign to the constant input x".into(),
+                (172..173).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn new_of_a_variable_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        TYPE IntPtr : POINTER TO INT; END_TYPE
+
+        PROGRAM prg
+            VAR
+                x : INT;
+                p : IntPtr;
+            END_VAR
+            p := __NEW(x);
+        END_PROGRAM
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "__NEW expects the name of a type as its first argument".into(),
+                (181..182).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn allocator_hooks_with_a_narrower_size_input_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        TYPE BytePtr : POINTER TO BYTE; END_TYPE
+
+        {attribute 'calling_convention' := 'C'}
+        @EXTERNAL FUNCTION __rusty_alloc : BytePtr
+        VAR_INPUT
+            size : DINT;
+        END_VAR
+        END_FUNCTION
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "The size-input of __rusty_alloc must be a 64-bit integer on this target, but found DINT"
+                    .into(),
+                (180..184).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn struct_inputs_of_functions_with_the_c_calling_convention_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        TYPE Point : STRUCT x : DINT; y : DINT; END_STRUCT END_TYPE
+
+        {attribute 'calling_convention' := 'C'}
+        @EXTERNAL FUNCTION length : LREAL
+        VAR_INPUT
+            point : Point;
+        END_VAR
+        END_FUNCTION
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "The struct Point cannot be passed by value with the C calling convention, declare point as VAR_IN_OUT instead"
+                    .into(),
+                (190..195).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
+
+#[test]
+fn struct_results_of_functions_with_the_c_calling_convention_should_be_reported() {
+    let result = codegen_wihout_unwrap!(
+        "
+        TYPE Point : STRUCT x : DINT; y : DINT; END_STRUCT END_TYPE
+
+        {attribute 'calling_convention' := 'C'}
+        FUNCTION origin : Point
+        END_FUNCTION
+        "
+    );
+    if let Err(msg) = result {
+        assert_eq!(
+            CompileError::codegen_error(
+                "origin cannot return the struct Point with the C calling convention, use a VAR_OUTPUT instead"
+                    .into(),
+                (170..170).into()
+            ),
+            msg
+        );
+    } else {
+        panic!("expected code-gen error but got none")
+    }
+}
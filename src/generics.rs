@@ -0,0 +1,612 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+
+/// Monomorphization of generic functions
+/// # responsibilities
+/// - removes the FUNCTIONs using a generic type class (`ANY_NUM`, `ANY_INT`, `ANY_REAL`, `ANY_BIT`,
+///   `ANY_STRING`) from the compiled sources, they are never generated themselves
+/// - resolves the concrete argument types of every call to a generic function and creates one
+///   implementation per combination of concrete types (e.g. `scale__DINT`)
+/// - redirects the calls to the matching implementation
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    ast::{
+        CompilationUnit, ConditionalBlock, DataTypeDeclaration, Implementation, Operator, Pou,
+        PouType, SourceRange, Statement, Variable, VariableBlockType,
+    },
+    compile_error::CompileError,
+    index::Index,
+    typesystem::{
+        find_type_class, fits_into_int_type, get_bigger_type, is_in_type_class, DataTypeInformation,
+    },
+};
+
+/// removes all generic functions and their implementations from the given unit and returns them
+pub fn extract_generic_functions(unit: &mut CompilationUnit) -> CompilationUnit {
+    let generic_names: HashSet<String> = unit
+        .units
+        .iter()
+        .filter(|it| it.pou_type == PouType::Function && !get_type_classes(it).is_empty())
+        .map(|it| it.name.to_lowercase())
+        .collect();
+
+    let (units, pous): (Vec<_>, Vec<_>) = unit
+        .units
+        .drain(..)
+        .partition(|it| generic_names.contains(&it.name.to_lowercase()));
+    unit.units = pous;
+    let (implementations, pou_implementations): (Vec<_>, Vec<_>) = unit
+        .implementations
+        .drain(..)
+        .partition(|it| generic_names.contains(&it.type_name.to_lowercase()));
+    unit.implementations = pou_implementations;
+
+    CompilationUnit {
+        units,
+        implementations,
+        ..CompilationUnit::default()
+    }
+}
+
+/// creates an implementation of the given generic functions for every combination of concrete types
+/// they are called with and redirects the calls to them. The created implementations are added to the
+/// given unit and index, calls inside of them are resolved as well.
+///
+/// # Arguments
+/// * `generics` - the generic functions extracted from the compiled sources
+/// * `unit` - the compilation unit of the compiled sources
+/// * `index` - the index of the compiled sources
+pub fn instantiate_generic_functions(
+    generics: &CompilationUnit,
+    unit: &mut CompilationUnit,
+    index: &mut Index,
+) -> Result<(), CompileError> {
+    if generics.units.is_empty() {
+        return Ok(());
+    }
+
+    //maps the name of every created instance to the name of its generic function
+    let mut instances = HashMap::new();
+    let mut next = 0;
+    while next < unit.implementations.len() {
+        let implementation = &mut unit.implementations[next];
+        let mut resolver = GenericResolver {
+            generics,
+            index,
+            renamed_function: instances
+                .get(&implementation.type_name)
+                .map(|generic: &String| (generic.clone(), implementation.type_name.clone())),
+            pou_name: implementation.type_name.clone(),
+            instances: &mut instances,
+            new_instances: vec![],
+        };
+        for statement in implementation.statements.iter_mut() {
+            resolver.visit(statement)?;
+        }
+
+        let new_instances = resolver.new_instances;
+        for (pou, implementation) in new_instances {
+            let instance = CompilationUnit {
+                units: vec![pou],
+                implementations: vec![implementation],
+                ..CompilationUnit::default()
+            };
+            index.import(crate::index::visitor::visit(&instance));
+            unit.import(instance);
+        }
+        next += 1;
+    }
+    Ok(())
+}
+
+/// returns the generic type classes used by the given pou's variables and return type
+/// in the order of their first use
+fn get_type_classes(pou: &Pou) -> Vec<&'static str> {
+    let mut type_classes = vec![];
+    let used_types = pou
+        .variable_blocks
+        .iter()
+        .flat_map(|it| it.variables.iter())
+        .map(|it| &it.data_type)
+        .chain(pou.return_type.iter())
+        .filter_map(DataTypeDeclaration::get_name);
+    for type_class in used_types.filter_map(find_type_class) {
+        if !type_classes.contains(&type_class) {
+            type_classes.push(type_class);
+        }
+    }
+    type_classes
+}
+
+struct GenericResolver<'a> {
+    generics: &'a CompilationUnit,
+    index: &'a Index,
+    /// the pou whose statements are resolved
+    pou_name: String,
+    /// the generic function's name and the instance's name if the resolved pou is an instance,
+    /// references to the function's return variable are renamed accordingly
+    renamed_function: Option<(String, String)>,
+    /// the names of all created instances and their generic functions
+    instances: &'a mut HashMap<String, String>,
+    /// the instances created while resolving this pou
+    new_instances: Vec<(Pou, Implementation)>,
+}
+
+impl<'a> GenericResolver<'a> {
+    /// redirects all calls to generic functions in the given statement and returns the name
+    /// of the statement's type if it is known
+    fn visit(&mut self, statement: &mut Statement) -> Result<Option<String>, CompileError> {
+        let type_name = match statement {
+            Statement::LiteralInteger { value, .. } => {
+                //literals that do not fit into a DINT need a LINT instance
+                let fits_into_dint = self
+                    .index
+                    .find_type_information("DINT")
+                    .map_or(true, |it| fits_into_int_type(value, &it));
+                Some(if fits_into_dint { "DINT" } else { "LINT" }.to_string())
+            }
+            Statement::LiteralReal { .. } => Some("REAL".to_string()),
+            Statement::LiteralBool { .. } => Some("BOOL".to_string()),
+            Statement::LiteralString { is_wide, .. } => {
+                Some(if *is_wide { "WSTRING" } else { "STRING" }.to_string())
+            }
+            Statement::Reference { name, .. } => {
+                if let Some((generic, instance)) = &self.renamed_function {
+                    if name.eq_ignore_ascii_case(generic) {
+                        *name = instance.clone();
+                    }
+                }
+                self.index
+                    .find_variable(Some(self.pou_name.as_str()), &[name.clone()])
+                    .map(|it| self.dereference(it.get_type_name()))
+            }
+            Statement::QualifiedReference { elements } => {
+                let mut container: Option<String> = None;
+                for (position, element) in elements.iter_mut().enumerate() {
+                    container = match (position, container) {
+                        (0, _) => self
+                            .visit(element)?
+                            .or_else(|| self.find_pou_type(element))
+                            .or_else(|| self.find_global_variable_list(element)),
+                        (_, Some(container)) => self.visit_member(&container, element)?,
+                        (_, None) => None,
+                    };
+                }
+                container
+            }
+            Statement::ArrayAccess { reference, access } => {
+                self.visit(access)?;
+                self.visit(reference)?
+                    .and_then(|it| self.get_element_type(&it))
+            }
+            Statement::PointerAccess { reference, .. } => self
+                .visit(reference)?
+                .and_then(|it| self.get_element_type(&it)),
+            Statement::BinaryExpression {
+                operator,
+                left,
+                right,
+            } => {
+                let left = self.visit(left)?;
+                let right = self.visit(right)?;
+                match operator {
+                    Operator::Equal
+                    | Operator::NotEqual
+                    | Operator::Less
+                    | Operator::Greater
+                    | Operator::LessOrEqual
+                    | Operator::GreaterOrEqual => Some("BOOL".to_string()),
+                    _ => self.get_bigger_type(left, right),
+                }
+            }
+            Statement::UnaryExpression { value, .. } => self.visit(value)?,
+            Statement::CallStatement {
+                operator,
+                parameters,
+                location,
+            } => self.visit_call(operator, parameters, location)?,
+            Statement::ExpressionList { expressions } => {
+                self.visit_all(expressions)?;
+                None
+            }
+            Statement::Assignment { left, right }
+            | Statement::OutputAssignment { left, right }
+            | Statement::RefAssignment { left, right } => {
+                self.visit(left)?;
+                self.visit(right)?;
+                None
+            }
+            Statement::RangeStatement { start, end } => {
+                self.visit(start)?;
+                self.visit(end)?;
+                None
+            }
+            Statement::MultipliedStatement { element, .. } => {
+                self.visit(element)?;
+                None
+            }
+            Statement::LiteralArray {
+                elements: Some(elements),
+                ..
+            } => {
+                self.visit(elements)?;
+                None
+            }
+            Statement::CaseCondition { condition } => {
+                self.visit(condition)?;
+                None
+            }
+            Statement::IfStatement {
+                blocks, else_block, ..
+            } => {
+                self.visit_conditional_blocks(blocks)?;
+                self.visit_all(else_block)?;
+                None
+            }
+            Statement::CaseStatement {
+                selector,
+                case_blocks,
+                else_block,
+                ..
+            } => {
+                self.visit(selector)?;
+                self.visit_conditional_blocks(case_blocks)?;
+                self.visit_all(else_block)?;
+                None
+            }
+            Statement::ForLoopStatement {
+                counter,
+                start,
+                end,
+                by_step,
+                body,
+                ..
+            } => {
+                self.visit(counter)?;
+                self.visit(start)?;
+                self.visit(end)?;
+                if let Some(by_step) = by_step {
+                    self.visit(by_step)?;
+                }
+                self.visit_all(body)?;
+                None
+            }
+            Statement::WhileLoopStatement {
+                condition, body, ..
+            }
+            | Statement::RepeatLoopStatement {
+                condition, body, ..
+            } => {
+                self.visit(condition)?;
+                self.visit_all(body)?;
+                None
+            }
+            _ => None,
+        };
+        Ok(type_name)
+    }
+
+    fn visit_all(&mut self, statements: &mut [Statement]) -> Result<(), CompileError> {
+        for statement in statements {
+            self.visit(statement)?;
+        }
+        Ok(())
+    }
+
+    fn visit_conditional_blocks(
+        &mut self,
+        blocks: &mut [ConditionalBlock],
+    ) -> Result<(), CompileError> {
+        for block in blocks {
+            self.visit(&mut block.condition)?;
+            self.visit_all(&mut block.body)?;
+        }
+        Ok(())
+    }
+
+    /// resolves the type of the given member-access of a qualified reference (`container.member`)
+    /// where the container is a struct type or a global variable list
+    fn visit_member(
+        &mut self,
+        container: &str,
+        member: &mut Statement,
+    ) -> Result<Option<String>, CompileError> {
+        match member {
+            Statement::Reference { name, .. } => Ok(self
+                .index
+                .find_member(container, name)
+                .or_else(|| self.index.find_global_list_member(container, name))
+                .map(|it| self.dereference(it.get_type_name()))),
+            Statement::ArrayAccess { reference, access } => {
+                self.visit(access)?;
+                Ok(self
+                    .visit_member(container, reference)?
+                    .and_then(|it| self.get_element_type(&it)))
+            }
+            Statement::PointerAccess { reference, .. } => Ok(self
+                .visit_member(container, reference)?
+                .and_then(|it| self.get_element_type(&it))),
+            _ => self.visit(member),
+        }
+    }
+
+    /// resolves the arguments of a call and redirects it if it calls a generic function.
+    /// Returns the name of the call's return type
+    fn visit_call(
+        &mut self,
+        operator: &mut Statement,
+        parameters: &mut Option<Statement>,
+        location: &SourceRange,
+    ) -> Result<Option<String>, CompileError> {
+        let mut arguments = vec![];
+        match parameters {
+            Some(Statement::ExpressionList { expressions }) => {
+                for argument in expressions {
+                    arguments.push(self.visit_argument(argument)?);
+                }
+            }
+            Some(argument) => arguments.push(self.visit_argument(argument)?),
+            None => {}
+        }
+
+        let name = match operator {
+            Statement::Reference { name, .. } => name,
+            _ => return Ok(None),
+        };
+        let generics = self.generics;
+        match generics
+            .units
+            .iter()
+            .find(|it| it.name.eq_ignore_ascii_case(name))
+        {
+            Some(generic) => {
+                let (instance_name, return_type) =
+                    self.instantiate(generic, &arguments, location)?;
+                *name = instance_name;
+                Ok(return_type)
+            }
+            None => Ok(self
+                .index
+                .find_return_type(name)
+                .map(|it| it.get_name().to_string())),
+        }
+    }
+
+    /// resolves an argument of a call and returns the name of the assigned parameter
+    /// (if it is passed explicitly) and the type of the argument
+    fn visit_argument(
+        &mut self,
+        argument: &mut Statement,
+    ) -> Result<(Option<String>, Option<String>), CompileError> {
+        match argument {
+            Statement::Assignment { left, right } | Statement::OutputAssignment { left, right } => {
+                let parameter = if let Statement::Reference { name, .. } = left.as_ref() {
+                    Some(name.clone())
+                } else {
+                    None
+                };
+                Ok((parameter, self.visit(right)?))
+            }
+            _ => Ok((None, self.visit(argument)?)),
+        }
+    }
+
+    /// binds the generic type classes of the given function to the types of the given arguments and
+    /// creates the matching instance if it does not exist yet.
+    /// Returns the name of the instance and of its return type
+    fn instantiate(
+        &mut self,
+        generic: &Pou,
+        arguments: &[(Option<String>, Option<String>)],
+        location: &SourceRange,
+    ) -> Result<(String, Option<String>), CompileError> {
+        let variables: Vec<&Variable> = generic
+            .variable_blocks
+            .iter()
+            .flat_map(|it| it.variables.iter())
+            .collect();
+        let parameters: Vec<&Variable> = generic
+            .variable_blocks
+            .iter()
+            .filter(|it| it.variable_block_type != VariableBlockType::Local)
+            .flat_map(|it| it.variables.iter())
+            .collect();
+
+        let mut bindings: HashMap<&str, String> = HashMap::new();
+        for (position, (parameter_name, argument_type)) in arguments.iter().enumerate() {
+            let parameter = match parameter_name {
+                Some(parameter_name) => parameters
+                    .iter()
+                    .find(|it| it.name.eq_ignore_ascii_case(parameter_name)),
+                None => variables.get(position),
+            };
+            let type_class = parameter
+                .and_then(|it| it.data_type.get_name())
+                .and_then(find_type_class);
+            if let (Some(type_class), Some(argument_type)) = (type_class, argument_type) {
+                let is_valid = self
+                    .index
+                    .find_type_information(argument_type)
+                    .map(|it| {
+                        self.index
+                            .find_effective_type(&it)
+                            .map(|it| is_in_type_class(type_class, it))
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(false);
+                if !is_valid {
+                    return Err(CompileError::codegen_error(
+                        format!(
+                            "Invalid type {} for {} parameter {} of {}",
+                            argument_type,
+                            type_class,
+                            parameter.map(|it| it.name.as_str()).unwrap_or_default(),
+                            generic.name
+                        ),
+                        location.clone(),
+                    ));
+                }
+                let bound_type = match bindings.remove(type_class) {
+                    Some(bound_type) => self
+                        .get_bigger_type(Some(bound_type), Some(argument_type.clone()))
+                        .unwrap_or_default(),
+                    None => argument_type.clone(),
+                };
+                bindings.insert(type_class, bound_type);
+            }
+        }
+
+        let mut bound_types = vec![];
+        for type_class in get_type_classes(generic) {
+            let bound_type = bindings.get(type_class).ok_or_else(|| {
+                CompileError::codegen_error(
+                    format!(
+                        "Cannot resolve the type of {} in call to {}",
+                        type_class, generic.name
+                    ),
+                    location.clone(),
+                )
+            })?;
+            bound_types.push(bound_type.as_str());
+        }
+        let instance_name = format!("{}__{}", generic.name, bound_types.join("_"));
+
+        let substitute = |declaration: &mut DataTypeDeclaration| {
+            if let DataTypeDeclaration::DataTypeReference { referenced_type } = declaration {
+                if let Some(bound_type) =
+                    find_type_class(referenced_type).and_then(|it| bindings.get(it))
+                {
+                    *referenced_type = bound_type.clone();
+                }
+            }
+        };
+        let mut return_type = generic.return_type.clone();
+        if let Some(return_type) = return_type.as_mut() {
+            substitute(return_type);
+        }
+
+        if !self.instances.contains_key(&instance_name) {
+            self.instances
+                .insert(instance_name.clone(), generic.name.clone());
+            let mut pou = generic.clone();
+            pou.name = instance_name.clone();
+            pou.return_type = return_type.clone();
+            pou.variable_blocks
+                .iter_mut()
+                .flat_map(|it| it.variables.iter_mut())
+                .for_each(|it| substitute(&mut it.data_type));
+            for implementation in self
+                .generics
+                .implementations
+                .iter()
+                .filter(|it| it.type_name.eq_ignore_ascii_case(&generic.name))
+            {
+                let mut implementation = implementation.clone();
+                implementation.name = instance_name.clone();
+                implementation.type_name = instance_name.clone();
+                self.new_instances.push((pou.clone(), implementation));
+            }
+        }
+
+        Ok((
+            instance_name,
+            return_type
+                .as_ref()
+                .and_then(DataTypeDeclaration::get_name)
+                .map(str::to_string),
+        ))
+    }
+
+    /// returns the type of a program or function block referenced by the first element of
+    /// a qualified reference (e.g. `prg.x`)
+    fn find_pou_type(&self, reference: &Statement) -> Option<String> {
+        match reference {
+            Statement::Reference { name, .. } => self.index.find_type(name).and_then(|it| {
+                if let DataTypeInformation::Struct { .. } = it.get_type_information() {
+                    Some(it.get_name().to_string())
+                } else {
+                    None
+                }
+            }),
+            _ => None,
+        }
+    }
+
+    /// returns the name of a global variable list referenced by the first element of
+    /// a qualified reference (e.g. `Gvl.x`)
+    fn find_global_variable_list(&self, reference: &Statement) -> Option<String> {
+        match reference {
+            Statement::Reference { name, .. } => self
+                .index
+                .find_global_variable_list(name)
+                .map(|it| it.get_name().to_string()),
+            _ => None,
+        }
+    }
+
+    /// returns the type a VAR_IN_OUT's pointer points to or the given type otherwise
+    fn dereference(&self, type_name: &str) -> String {
+        match self.index.find_type_information(type_name) {
+            Some(DataTypeInformation::Pointer {
+                inner_type_name,
+                auto_deref: true,
+                ..
+            }) => self.get_declared_name(&inner_type_name),
+            _ => self.get_declared_name(type_name),
+        }
+    }
+
+    /// returns the given type's name as it is spelled in its declaration, so the instances
+    /// created for `dint` and `DINT` arguments are the same
+    fn get_declared_name(&self, type_name: &str) -> String {
+        self.index
+            .find_type(type_name)
+            .map_or(type_name, |it| it.get_name())
+            .to_string()
+    }
+
+    /// returns the element-type of the given array type or the type the given pointer points to
+    fn get_element_type(&self, type_name: &str) -> Option<String> {
+        let data_type = self.index.find_type_information(type_name)?;
+        match self.index.find_effective_type(&data_type)? {
+            DataTypeInformation::Array {
+                inner_type_name, ..
+            }
+            | DataTypeInformation::VariableLengthArray {
+                inner_type_name, ..
+            }
+            | DataTypeInformation::Pointer {
+                inner_type_name,
+                auto_deref: false,
+                ..
+            } => Some(self.get_declared_name(inner_type_name)),
+            _ => None,
+        }
+    }
+
+    /// returns the type a binary expression of the given types results in
+    fn get_bigger_type(&self, left: Option<String>, right: Option<String>) -> Option<String> {
+        match (left, right) {
+            (Some(left), Some(right)) => {
+                let left_type = self.index.find_type_information(&left);
+                let right_type = self.index.find_type_information(&right);
+                match (left_type, right_type) {
+                    (Some(left_type), Some(right_type))
+                        if left_type.is_numerical() && right_type.is_numerical() =>
+                    {
+                        Some(
+                            get_bigger_type(&left_type, &right_type)
+                                .get_name()
+                                .to_string(),
+                        )
+                    }
+                    _ => Some(left),
+                }
+            }
+            (left, right) => left.or(right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
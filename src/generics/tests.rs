@@ -0,0 +1,185 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use crate::{
+    ast::{CompilationUnit, Statement},
+    compile_error::CompileError,
+    index::visitor::visit,
+    lexer::lex,
+    parser::parse,
+};
+
+use super::{extract_generic_functions, instantiate_generic_functions};
+
+fn instantiate(source: &str) -> Result<CompilationUnit, CompileError> {
+    let (mut unit, diagnostics) = parse(lex(source)).unwrap();
+    assert_eq!(Vec::<crate::Diagnostic>::new(), diagnostics);
+    crate::ast::pre_process(&mut unit);
+    let generics = extract_generic_functions(&mut unit);
+    let mut index = visit(&unit);
+    instantiate_generic_functions(&generics, &mut unit, &mut index)?;
+    Ok(unit)
+}
+
+fn get_called_names(statements: &[Statement]) -> Vec<String> {
+    statements
+        .iter()
+        .filter_map(|it| match it {
+            Statement::Assignment { right, .. } => match right.as_ref() {
+                Statement::CallStatement { operator, .. } => match operator.as_ref() {
+                    Statement::Reference { name, .. } => Some(name.clone()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+const SCALE: &str = "
+    FUNCTION scale : ANY_NUM
+    VAR_INPUT
+        value : ANY_NUM;
+        factor : ANY_NUM;
+    END_VAR
+        scale := value * factor;
+    END_FUNCTION
+    ";
+
+#[test]
+fn generic_functions_are_removed_from_the_unit() {
+    let (mut unit, _) = parse(lex(&format!("{} PROGRAM prg END_PROGRAM", SCALE))).unwrap();
+    let generics = extract_generic_functions(&mut unit);
+
+    let names: Vec<&str> = unit.units.iter().map(|it| it.name.as_str()).collect();
+    assert_eq!(vec!["prg"], names);
+    let generic_names: Vec<&str> = generics.units.iter().map(|it| it.name.as_str()).collect();
+    assert_eq!(vec!["scale"], generic_names);
+    assert_eq!("scale", generics.implementations[0].type_name);
+}
+
+#[test]
+fn one_instance_is_created_per_argument_type() {
+    let unit = instantiate(&format!(
+        "{}
+        PROGRAM prg
+        VAR
+            i : INT;
+            d : DINT;
+            r : LREAL;
+        END_VAR
+            i := scale(i, i);
+            d := scale(d, 2);
+            r := scale(r, 2);
+            d := scale(d, d);
+        END_PROGRAM
+        ",
+        SCALE
+    ))
+    .unwrap();
+
+    let names: Vec<&str> = unit.units.iter().map(|it| it.name.as_str()).collect();
+    assert_eq!(
+        vec!["prg", "scale__INT", "scale__DINT", "scale__LREAL"],
+        names
+    );
+    assert_eq!(
+        vec!["scale__INT", "scale__DINT", "scale__LREAL", "scale__DINT"],
+        get_called_names(&unit.implementations[0].statements)
+    );
+    //the return variable is renamed with the function
+    assert_eq!(
+        format!("{:?}", unit.implementations[1].statements[0]),
+        "Assignment { left: Reference { name: \"scale__INT\" }, right: BinaryExpression { \
+         operator: Multiplication, left: Reference { name: \"value\" }, right: Reference { name: \"factor\" } } }"
+    );
+}
+
+#[test]
+fn generic_calls_inside_instances_are_resolved() {
+    let unit = instantiate(
+        "
+        FUNCTION twice : ANY_INT
+        VAR_INPUT
+            value : ANY_INT;
+        END_VAR
+            twice := value + value;
+        END_FUNCTION
+
+        FUNCTION quad : ANY_INT
+        VAR_INPUT
+            value : ANY_INT;
+        END_VAR
+            quad := twice(twice(value));
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            s : SINT;
+        END_VAR
+            s := quad(value := s);
+        END_PROGRAM
+        ",
+    )
+    .unwrap();
+
+    let names: Vec<&str> = unit.units.iter().map(|it| it.name.as_str()).collect();
+    assert_eq!(vec!["prg", "quad__SINT", "twice__SINT"], names);
+}
+
+#[test]
+fn literals_that_do_not_fit_into_a_dint_create_a_lint_instance() {
+    let unit = instantiate(
+        "
+        FUNCTION twice : ANY_INT
+        VAR_INPUT
+            value : ANY_INT;
+        END_VAR
+            twice := value + value;
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            d : DINT;
+            l : LINT;
+        END_VAR
+            d := twice(5);
+            l := twice(3000000000);
+        END_PROGRAM
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec!["twice__DINT", "twice__LINT"],
+        get_called_names(&unit.implementations[0].statements)
+    );
+}
+
+#[test]
+fn arguments_outside_of_the_type_class_are_reported() {
+    let result = instantiate(
+        "
+        FUNCTION twice : ANY_INT
+        VAR_INPUT
+            value : ANY_INT;
+        END_VAR
+            twice := value + value;
+        END_FUNCTION
+
+        PROGRAM prg
+        VAR
+            r : REAL;
+        END_VAR
+            r := twice(r);
+        END_PROGRAM
+        ",
+    );
+
+    assert_eq!(
+        Err(CompileError::codegen_error(
+            "Invalid type REAL for ANY_INT parameter value of twice".into(),
+            (242..250).into()
+        )),
+        result
+    );
+}
@@ -2,7 +2,7 @@
 use indexmap::IndexMap;
 
 use crate::{
-    ast::{Implementation, SourceRange, Statement},
+    ast::{CallingConvention, Implementation, SourceRange, Statement},
     compile_error::CompileError,
     typesystem::*,
 };
@@ -25,6 +25,8 @@ pub struct MemberInfo<'b> {
     variable_name: &'b str,
     variable_linkage: VariableType,
     variable_type_name: &'b str,
+    /// whether the variable is a read-only input passed by reference
+    is_constant: bool,
 }
 
 impl VariableIndexEntry {
@@ -51,6 +53,19 @@ impl VariableIndexEntry {
     pub fn is_local(&self) -> bool {
         self.information.variable_type == VariableType::Local
     }
+
+    pub fn is_inout(&self) -> bool {
+        self.information.variable_type == VariableType::InOut
+    }
+
+    /// returns true for the read-only inputs of a `VAR_INPUT CONSTANT` block
+    pub fn is_constant(&self) -> bool {
+        self.information.is_constant
+    }
+
+    pub fn get_variable_type(&self) -> VariableType {
+        self.information.variable_type
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -74,6 +89,26 @@ pub struct VariableInformation {
     qualifier: Option<String>,
     /// Location in the qualifier defautls to 0 (Single variables)
     location: u32,
+    /// whether the variable is a read-only input passed by reference
+    is_constant: bool,
+}
+
+/// a named VAR_GLOBAL block whose variables can be accessed as `ListName.variable`
+#[derive(Debug, Clone)]
+pub struct GlobalVariableListEntry {
+    name: String,
+    /// whether the list's variables can only be accessed qualified with the list's name
+    qualified_only: bool,
+}
+
+impl GlobalVariableListEntry {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_qualified_only(&self) -> bool {
+        self.qualified_only
+    }
 }
 
 #[derive(Debug)]
@@ -88,6 +123,7 @@ pub enum DataTypeType {
 pub struct ImplementationIndexEntry {
     call_name: String,
     type_name: String,
+    calling_convention: CallingConvention,
 }
 
 impl ImplementationIndexEntry {
@@ -97,6 +133,9 @@ impl ImplementationIndexEntry {
     pub fn get_type_name(&self) -> &str {
         &self.type_name
     }
+    pub fn get_calling_convention(&self) -> CallingConvention {
+        self.calling_convention
+    }
 }
 
 impl From<&Implementation> for ImplementationIndexEntry {
@@ -104,6 +143,7 @@ impl From<&Implementation> for ImplementationIndexEntry {
         ImplementationIndexEntry {
             call_name: implementation.name.clone(),
             type_name: implementation.type_name.clone(),
+            calling_convention: implementation.calling_convention,
         }
     }
 }
@@ -112,12 +152,17 @@ impl From<&Implementation> for ImplementationIndexEntry {
 ///
 /// The index contains information about all referencable elements.
 ///
-///
+/// Identifiers are case-insensitive, so all maps are keyed by lowercase names while the
+/// entries keep the names as they were declared.
 #[derive(Debug)]
 pub struct Index {
-    /// all global variables
+    /// all global variables, the variables of global variable lists are registered with their
+    /// qualified name (`List.variable`)
     global_variables: IndexMap<String, VariableIndexEntry>,
 
+    /// all global variable lists (named VAR_GLOBAL blocks)
+    global_variable_lists: IndexMap<String, GlobalVariableListEntry>,
+
     /// all local variables, grouped by the POU's name
     member_variables: IndexMap<String, IndexMap<String, VariableIndexEntry>>,
 
@@ -134,6 +179,7 @@ impl Index {
     pub fn new() -> Index {
         Index {
             global_variables: IndexMap::new(),
+            global_variable_lists: IndexMap::new(),
             member_variables: IndexMap::new(),
             types: IndexMap::new(),
             implementations: IndexMap::new(),
@@ -147,12 +193,14 @@ impl Index {
 
     /// imports all entries from the given index into the current index
     ///
-    /// imports all global_variables, member_variables, types and implementations
+    /// imports all global_variables, global_variable_lists, member_variables, types and implementations
     /// # Arguments
     /// - `other` the other index. The elements are drained from the given index and moved
     /// into the current one
     pub fn import(&mut self, other: Index) {
         self.global_variables.extend(other.global_variables);
+        self.global_variable_lists
+            .extend(other.global_variable_lists);
         self.member_variables.extend(other.member_variables);
         self.types.extend(other.types);
         self.implementations.extend(other.implementations);
@@ -163,18 +211,40 @@ impl Index {
     }
 
     pub fn find_global_variable(&self, name: &str) -> Option<&VariableIndexEntry> {
-        self.global_variables.get(name)
+        self.global_variables.get(&name.to_lowercase()).or_else(|| {
+            //the variables of lists that are not qualified_only can be accessed without the list's name
+            self.global_variable_lists
+                .values()
+                .filter(|it| !it.qualified_only)
+                .find_map(|it| self.find_global_list_member(&it.name, name))
+        })
+    }
+
+    pub fn find_global_variable_list(&self, name: &str) -> Option<&GlobalVariableListEntry> {
+        self.global_variable_lists.get(&name.to_lowercase())
+    }
+
+    /// returns the variable `list_name.variable_name` of the given global variable list
+    pub fn find_global_list_member(
+        &self,
+        list_name: &str,
+        variable_name: &str,
+    ) -> Option<&VariableIndexEntry> {
+        self.find_global_variable_list(list_name).and_then(|list| {
+            self.global_variables
+                .get(&format!("{}.{}", list.name, variable_name).to_lowercase())
+        })
     }
 
     pub fn find_member(&self, pou_name: &str, variable_name: &str) -> Option<&VariableIndexEntry> {
         self.member_variables
-            .get(pou_name)
-            .and_then(|map| map.get(variable_name))
+            .get(&pou_name.to_lowercase())
+            .and_then(|map| map.get(&variable_name.to_lowercase()))
     }
 
     pub fn find_local_members(&self, container_name: &str) -> Vec<&VariableIndexEntry> {
         self.member_variables
-            .get(container_name)
+            .get(&container_name.to_lowercase())
             .map(|it| it.values().collect())
             .unwrap_or_else(Vec::new)
     }
@@ -182,7 +252,7 @@ impl Index {
     /// Returns true if the current index is a VAR_INPUT, VAR_IN_OUT or VAR_OUTPUT that is not a variadic argument
     pub fn is_declared_parameter(&self, pou_name: &str, index: u32) -> bool {
         self.member_variables
-            .get(pou_name)
+            .get(&pou_name.to_lowercase())
             .and_then(|map| {
                 map.values()
                     .filter(|item| {
@@ -195,12 +265,34 @@ impl Index {
             .is_some()
     }
 
+    /// Returns the VAR_INPUT at the given index
     pub fn find_input_parameter(&self, pou_name: &str, index: u32) -> Option<&VariableIndexEntry> {
-        self.member_variables.get(pou_name).and_then(|map| {
-            map.values()
-                .filter(|item| item.information.variable_type == VariableType::Input)
-                .find(|item| item.information.location == index)
-        })
+        self.member_variables
+            .get(&pou_name.to_lowercase())
+            .and_then(|map| {
+                map.values()
+                    .filter(|item| item.information.variable_type == VariableType::Input)
+                    .find(|item| item.information.location == index)
+            })
+    }
+
+    /// Returns the VAR_INPUT or VAR_IN_OUT at the given index, the parameters a non-formal
+    /// argument can be passed to
+    pub fn find_positional_parameter(
+        &self,
+        pou_name: &str,
+        index: u32,
+    ) -> Option<&VariableIndexEntry> {
+        self.member_variables
+            .get(&pou_name.to_lowercase())
+            .and_then(|map| {
+                map.values()
+                    .filter(|item| {
+                        item.information.variable_type == VariableType::Input
+                            || item.information.variable_type == VariableType::InOut
+                    })
+                    .find(|item| item.information.location == index)
+            })
     }
 
     pub fn find_variable(
@@ -220,7 +312,13 @@ impl Index {
                 .or_else(|| self.find_global_variable(first_var)),
             None => self.find_global_variable(first_var),
         };
-        for segment in segments.iter().skip(1) {
+        let mut segments = &segments[1..];
+        //`List.variable` accesses a variable of a global variable list
+        if let (None, Some(variable)) = (result, segments.first()) {
+            result = self.find_global_list_member(first_var, variable);
+            segments = &segments[1..];
+        }
+        for segment in segments {
             result = match result {
                 Some(context) => self.find_member(&context.information.data_type_name, &segment),
                 None => None,
@@ -230,7 +328,7 @@ impl Index {
     }
 
     pub fn find_type(&self, type_name: &str) -> Option<&DataType> {
-        self.types.get(type_name)
+        self.types.get(&type_name.to_lowercase())
     }
 
     pub fn get_type(&self, type_name: &str) -> Result<&DataType, CompileError> {
@@ -260,7 +358,7 @@ impl Index {
     }
 
     pub fn find_return_variable(&self, pou_name: &str) -> Option<&VariableIndexEntry> {
-        let members = self.member_variables.get(pou_name); //.ok_or_else(||CompileError::unknown_type(pou_name, 0..0))?;
+        let members = self.member_variables.get(&pou_name.to_lowercase()); //.ok_or_else(||CompileError::unknown_type(pou_name, 0..0))?;
         if let Some(members) = members {
             for (_, variable) in members {
                 if variable.information.variable_type == VariableType::Return {
@@ -301,18 +399,24 @@ impl Index {
         &self.implementations
     }
 
-    pub fn register_implementation(&mut self, call_name: &str, type_name: &str) {
+    pub fn register_implementation(
+        &mut self,
+        call_name: &str,
+        type_name: &str,
+        calling_convention: CallingConvention,
+    ) {
         self.implementations.insert(
-            call_name.into(),
+            call_name.to_lowercase(),
             ImplementationIndexEntry {
                 call_name: call_name.into(),
                 type_name: type_name.into(),
+                calling_convention,
             },
         );
     }
 
     pub fn find_implementation(&self, call_name: &str) -> Option<&ImplementationIndexEntry> {
-        self.implementations.get(call_name)
+        self.implementations.get(&call_name.to_lowercase())
     }
 
     /// registers a member-variable of a container to be accessed in a qualified name.
@@ -339,7 +443,7 @@ impl Index {
 
         let members = self
             .member_variables
-            .entry(container_name.into())
+            .entry(container_name.to_lowercase())
             .or_insert_with(IndexMap::new);
 
         let qualified_name = format!("{}.{}", container_name, variable_name);
@@ -354,9 +458,10 @@ impl Index {
                 data_type_name: variable_type_name.into(),
                 qualifier: Some(container_name.into()),
                 location,
+                is_constant: member_info.is_constant,
             },
         };
-        members.insert(variable_name.into(), entry);
+        members.insert(variable_name.to_lowercase(), entry);
     }
 
     pub fn register_global_variable(
@@ -396,9 +501,23 @@ impl Index {
                 data_type_name: type_name.into(),
                 qualifier: None,
                 location: 0,
+                is_constant: false,
             },
         };
-        self.global_variables.insert(association_name.into(), entry);
+        self.global_variables
+            .insert(association_name.to_lowercase(), entry);
+    }
+
+    /// registers a global variable list, its variables are registered as global variables
+    /// named `list_name.variable`
+    pub fn register_global_variable_list(&mut self, list_name: &str, qualified_only: bool) {
+        self.global_variable_lists.insert(
+            list_name.to_lowercase(),
+            GlobalVariableListEntry {
+                name: list_name.into(),
+                qualified_only,
+            },
+        );
     }
 
     pub fn print_global_variables(&self) {
@@ -416,7 +535,7 @@ impl Index {
             initial_value,
             information,
         };
-        self.types.insert(type_name.into(), index_entry);
+        self.types.insert(type_name.to_lowercase(), index_entry);
     }
 
     pub fn find_callable_instance_variable(
@@ -182,6 +182,31 @@ fn function_with_typed_varargs_param_marked() {
     );
 }
 
+#[test]
+fn positional_parameters_include_inouts() {
+    let index = index!(
+        r#"
+        FUNCTION foo : INT
+        VAR_INPUT
+            a : INT;
+        END_VAR
+        VAR_IN_OUT
+            b : INT;
+        END_VAR
+        VAR_OUTPUT
+            c : INT;
+        END_VAR
+        END_FUNCTION
+    "#
+    );
+
+    assert_eq!("a", index.find_input_parameter("foo", 0).unwrap().name);
+    assert_eq!(None, index.find_input_parameter("foo", 1));
+    assert_eq!("a", index.find_positional_parameter("foo", 0).unwrap().name);
+    assert_eq!("b", index.find_positional_parameter("foo", 1).unwrap().name);
+    assert_eq!(None, index.find_positional_parameter("foo", 2));
+}
+
 #[test]
 fn pous_are_indexed() {
     let index = index!(
@@ -624,7 +649,8 @@ fn pre_processing_generates_inline_structs_global() {
                 },
                 location: (54..55).into(),
                 initializer: None,
-            }]
+            }],
+            pack_mode: None,
         },
         new_struct_type
     );
@@ -703,7 +729,8 @@ fn pre_processing_generates_inline_structs() {
                 },
                 location: (67..68).into(),
                 initializer: None,
-            }]
+            }],
+            pack_mode: None,
         },
         new_struct_type
     );
@@ -879,6 +906,7 @@ fn pre_processing_nested_array_in_struct() {
                 location: SourceRange::undefined(),
                 initializer: None,
             }],
+            pack_mode: None,
         },
         initializer: None,
     };
@@ -1016,7 +1044,7 @@ fn sub_range_boundaries_are_registered_at_the_index() {
     let index = index!(src);
 
     // THEN I expect the index to contain the defined range-information for the given type
-    let my_int = &index.get_types().get("MyInt").unwrap().information;
+    let my_int = &index.find_type("MyInt").unwrap().information;
     let expected = &DataTypeInformation::SubRange {
         name: "MyInt".to_string(),
         referenced_type: "INT".to_string(),
@@ -1032,7 +1060,7 @@ fn sub_range_boundaries_are_registered_at_the_index() {
     assert_eq!(format!("{:?}", expected), format!("{:?}", my_int));
 
     // THEN I expect the index to contain the defined range-information for the given type
-    let my_int = &index.get_types().get("MyAliasInt").unwrap().information;
+    let my_int = &index.find_type("MyAliasInt").unwrap().information;
     let expected = &DataTypeInformation::Alias {
         name: "MyAliasInt".to_string(),
         referenced_type: "MyInt".to_string(),
@@ -1040,3 +1068,161 @@ fn sub_range_boundaries_are_registered_at_the_index() {
 
     assert_eq!(format!("{:?}", expected), format!("{:?}", my_int));
 }
+
+#[test]
+fn variable_length_arrays_are_passed_as_fat_pointers() {
+    // GIVEN a function with a variable-length VAR_IN_OUT array
+    let index = index!(
+        "
+        FUNCTION sum : REAL
+        VAR_IN_OUT
+            values : ARRAY[*, *] OF REAL;
+        END_VAR
+        END_FUNCTION
+        "
+    );
+
+    // THEN I expect the array to be registered as a variable-length array with 2 dimensions
+    let values_type = &index.find_type("__sum_values").unwrap().information;
+    assert_eq!(
+        &DataTypeInformation::VariableLengthArray {
+            name: "__sum_values".to_string(),
+            inner_type_name: "REAL".to_string(),
+            dimensions: 2,
+        },
+        values_type
+    );
+
+    // AND I expect the parameter to use the array's fat pointer instead of a pointer to it
+    let values = index.find_member("sum", "values").unwrap();
+    assert_eq!("__sum_values", values.get_type_name());
+}
+
+#[test]
+fn identifiers_are_found_case_insensitive() {
+    // GIVEN a program, a type and a global variable declared in mixed case
+    let index = index!(
+        "
+        TYPE MyStruct : STRUCT
+            Field : INT;
+        END_STRUCT
+        END_TYPE
+
+        VAR_GLOBAL
+            GlobalVar : MyStruct;
+        END_VAR
+
+        PROGRAM MainProg
+        VAR
+            LocalVar : INT;
+        END_VAR
+        END_PROGRAM
+        "
+    );
+
+    // THEN I expect them to be found using any spelling
+    let global = index.find_global_variable("GLOBALVAR").unwrap();
+    let local = index.find_member("mainprog", "LOCALVAR").unwrap();
+    let field = index.find_member("MYSTRUCT", "field").unwrap();
+    let data_type = index.find_type("mystruct").unwrap();
+    let implementation = index.find_implementation("MAINPROG").unwrap();
+
+    // AND I expect the entries to keep the declared names
+    assert_eq!("GlobalVar", global.get_name());
+    assert_eq!("LocalVar", local.get_name());
+    assert_eq!("MainProg.LocalVar", local.get_qualified_name());
+    assert_eq!("Field", field.get_name());
+    assert_eq!("MyStruct", data_type.get_name());
+    assert_eq!("MainProg", implementation.get_call_name());
+}
+
+#[test]
+fn global_variable_list_members_are_found_qualified() {
+    // GIVEN a qualified_only list and a list that can be accessed unqualified
+    let index = index!(
+        "
+        VAR_GLOBAL Gvl_Io {attribute 'qualified_only'}
+            x : INT;
+        END_VAR
+
+        VAR_GLOBAL Gvl_Hmi
+            x : BOOL;
+            y : INT;
+        END_VAR
+        "
+    );
+
+    // THEN I expect the lists' variables to be found with their list's name
+    let io_x = index
+        .find_variable(None, &["Gvl_Io".to_string(), "x".to_string()])
+        .unwrap();
+    assert_eq!("Gvl_Io.x", io_x.get_qualified_name());
+    assert_eq!("INT", io_x.get_type_name());
+    let hmi_x = index
+        .find_variable(None, &["gvl_hmi".to_string(), "X".to_string()])
+        .unwrap();
+    assert_eq!("Gvl_Hmi.x", hmi_x.get_qualified_name());
+    assert_eq!("BOOL", hmi_x.get_type_name());
+
+    // AND I expect only the variables of lists that are not qualified_only to be found unqualified
+    assert_eq!(
+        "Gvl_Hmi.y",
+        index
+            .find_global_variable("y")
+            .unwrap()
+            .get_qualified_name()
+    );
+    assert_eq!(
+        "Gvl_Hmi.x",
+        index
+            .find_global_variable("x")
+            .unwrap()
+            .get_qualified_name()
+    );
+    assert_eq!(
+        true,
+        index
+            .find_global_variable_list("GVL_IO")
+            .unwrap()
+            .is_qualified_only()
+    );
+}
+
+#[test]
+fn constant_inputs_are_registered_as_pointers() {
+    // GIVEN a function block with a constant input and a regular input
+    let index = index!(
+        "
+        TYPE Recipe : STRUCT
+            a : INT;
+        END_STRUCT END_TYPE
+
+        FUNCTION_BLOCK fb
+        VAR_INPUT CONSTANT
+            recipe : Recipe;
+        END_VAR
+        VAR_INPUT
+            copy : Recipe;
+        END_VAR
+        END_FUNCTION_BLOCK
+        "
+    );
+
+    // THEN I expect the constant input to be passed as a pointer to its declared type
+    let recipe = index.find_member("fb", "recipe").unwrap();
+    assert!(recipe.is_constant());
+    assert_eq!("pointer_to_Recipe", recipe.get_type_name());
+    assert_eq!(
+        Some(DataTypeInformation::Pointer {
+            name: "pointer_to_Recipe".to_string(),
+            inner_type_name: "Recipe".to_string(),
+            auto_deref: true,
+        }),
+        index.find_type_information("pointer_to_Recipe")
+    );
+
+    // AND the regular input to be passed by value
+    let copy = index.find_member("fb", "copy").unwrap();
+    assert!(!copy.is_constant());
+    assert_eq!("Recipe", copy.get_type_name());
+}
@@ -1,9 +1,9 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use super::VariableType;
 use crate::ast::{
-    self, evaluate_constant_int, get_array_dimensions, CompilationUnit, DataType,
-    DataTypeDeclaration, Implementation, Pou, PouType, SourceRange, Statement, UserTypeDeclaration,
-    Variable, VariableBlock, VariableBlockType,
+    self, evaluate_constant_int, get_array_dimensions, is_variable_length_array, CompilationUnit,
+    DataType, DataTypeDeclaration, Implementation, Pou, PouType, SourceRange, Statement,
+    UserTypeDeclaration, Variable, VariableBlock, VariableBlockType,
 };
 use crate::index::{Index, MemberInfo};
 use crate::typesystem::*;
@@ -77,8 +77,8 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
             }
             member_names.push(var.name.clone());
 
-            let type_name = if block_type == VariableType::InOut {
-                //register a pointer type for the var_in_out
+            let type_name = if block_type == VariableType::InOut || block.constant {
+                //register a pointer type for the var_in_out or the constant input
                 register_inout_pointer_type_for(index, var)
             } else {
                 var.data_type.get_name().unwrap().to_string()
@@ -90,6 +90,7 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
                     variable_name: &var.name,
                     variable_linkage: block_type,
                     variable_type_name: &type_name,
+                    is_constant: block.constant,
                 },
                 var.initializer.clone(),
                 var.location.clone(),
@@ -109,6 +110,7 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
                 variable_name: &pou.name,
                 variable_linkage: VariableType::Return,
                 variable_type_name: return_type.get_name().unwrap(),
+                is_constant: false,
             },
             None,
             source_location,
@@ -123,12 +125,17 @@ pub fn visit_pou(index: &mut Index, pou: &Pou) {
             name: interface_name,
             member_names,
             varargs,
+            pack_mode: None,
         },
     );
 }
 
 fn visit_implementation(index: &mut Index, implementation: &Implementation) {
-    index.register_implementation(&implementation.name, &implementation.type_name);
+    index.register_implementation(
+        &implementation.name,
+        &implementation.type_name,
+        implementation.calling_convention,
+    );
     //if we are registing an action, also register a datatype for it
     if implementation.pou_type == PouType::Action {
         index.register_type(
@@ -144,6 +151,13 @@ fn visit_implementation(index: &mut Index, implementation: &Implementation) {
 
 fn register_inout_pointer_type_for(index: &mut Index, var: &Variable) -> String {
     let inner_type_name = var.data_type.get_name().unwrap().to_string();
+    //variable-length arrays are passed as fat pointers already
+    if let Some(DataTypeInformation::VariableLengthArray { .. }) = index
+        .find_type(&inner_type_name)
+        .map(|it| it.get_type_information())
+    {
+        return inner_type_name;
+    }
     //get unique name
     let type_name = format!("pointer_to_{}", inner_type_name);
 
@@ -162,9 +176,18 @@ fn register_inout_pointer_type_for(index: &mut Index, var: &Variable) -> String
 }
 
 fn visit_global_var_block(index: &mut Index, block: &VariableBlock) {
+    if let Some(list_name) = &block.name {
+        index.register_global_variable_list(list_name, block.qualified_only);
+    }
     for var in &block.variables {
+        //the variables of global variable lists are registered with their qualified name, so
+        //different lists can declare variables with the same name
+        let name = match &block.name {
+            Some(list_name) => format!("{}.{}", list_name, var.name),
+            None => var.name.clone(),
+        };
         index.register_global_variable(
-            &var.name,
+            &name,
             var.data_type.get_name().unwrap(),
             var.initializer.clone(),
             var.location.clone(),
@@ -186,16 +209,26 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
     let data_type = &type_declatation.data_type;
     //names should not be empty
     match data_type {
-        DataType::StructType { name, variables } => {
+        DataType::StructType {
+            name, variables, ..
+        }
+        | DataType::UnionType { name, variables } => {
             let struct_name = name.as_ref().unwrap();
 
             let member_names: Vec<String> =
                 variables.iter().map(|it| it.name.to_string()).collect();
 
-            let information = DataTypeInformation::Struct {
-                name: name.clone().unwrap(),
-                member_names,
-                varargs: None,
+            let information = match data_type {
+                DataType::StructType { pack_mode, .. } => DataTypeInformation::Struct {
+                    name: name.clone().unwrap(),
+                    member_names,
+                    varargs: None,
+                    pack_mode: *pack_mode,
+                },
+                _ => DataTypeInformation::Union {
+                    name: name.clone().unwrap(),
+                    member_names,
+                },
             };
             index.register_type(
                 name.as_ref().unwrap(),
@@ -220,6 +253,7 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
                         variable_name: &var.name,
                         variable_linkage: VariableType::Local,
                         variable_type_name: var.data_type.get_name().unwrap(),
+                        is_constant: false,
                     },
                     var.initializer.clone(),
                     var.location.clone(),
@@ -280,12 +314,19 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
             referenced_type,
             bounds,
         } => {
-            let dimensions = get_array_dimensions(&bounds).unwrap();
             let referenced_type_name = referenced_type.get_name().unwrap();
-            let information = DataTypeInformation::Array {
-                name: name.as_ref().unwrap().clone(),
-                inner_type_name: referenced_type_name.to_string(),
-                dimensions,
+            let information = if is_variable_length_array(bounds) {
+                DataTypeInformation::VariableLengthArray {
+                    name: name.as_ref().unwrap().clone(),
+                    inner_type_name: referenced_type_name.to_string(),
+                    dimensions: bounds.get_as_list().len() as u32,
+                }
+            } else {
+                DataTypeInformation::Array {
+                    name: name.as_ref().unwrap().clone(),
+                    inner_type_name: referenced_type_name.to_string(),
+                    dimensions: get_array_dimensions(&bounds).unwrap(),
+                }
             };
             index.register_type(
                 name.as_ref().unwrap(),
@@ -318,6 +359,22 @@ fn visit_data_type(index: &mut Index, type_declatation: &UserTypeDeclaration) {
                 information,
             )
         }
+        DataType::PointerType {
+            name,
+            referenced_type,
+            auto_deref,
+        } => {
+            let information = DataTypeInformation::Pointer {
+                name: name.as_ref().unwrap().clone(),
+                inner_type_name: referenced_type.get_name().unwrap().to_string(),
+                auto_deref: *auto_deref,
+            };
+            index.register_type(
+                name.as_ref().unwrap(),
+                type_declatation.initializer.clone(),
+                information,
+            )
+        }
         DataType::VarArgs { .. } => {} //Varargs are not indexed
     };
 }
@@ -7,6 +7,10 @@ use logos::Logos;
 use crate::ast::SourceRange;
 use crate::Diagnostic;
 
+use self::conditional_compilation::ConditionalCompilation;
+pub use self::conditional_compilation::Symbols;
+
+mod conditional_compilation;
 #[cfg(test)]
 mod tests;
 
@@ -20,10 +24,17 @@ pub struct ParseSession<'a> {
     /// the range of the `last_token`
     pub last_range: Range<usize>,
     pub parse_progress: usize,
+    /// the open conditional compilation regions (e.g. `{IF defined(X)}`)
+    conditionals: ConditionalCompilation,
 }
 
 impl<'a> ParseSession<'a> {
     pub fn new(l: Lexer<'a, Token>) -> ParseSession<'a> {
+        ParseSession::with_symbols(l, Symbols::new())
+    }
+
+    /// creates a ParseSession that evaluates conditional compilation pragmas against the given symbols
+    pub fn with_symbols(l: Lexer<'a, Token>, symbols: Symbols) -> ParseSession<'a> {
         let mut lexer = ParseSession {
             lexer: l,
             token: Token::KeywordBy,
@@ -32,6 +43,7 @@ impl<'a> ParseSession<'a> {
             last_token: Token::End,
             last_range: 0..0,
             parse_progress: 0,
+            conditionals: ConditionalCompilation::new(symbols),
         };
         lexer.advance();
         lexer
@@ -84,11 +96,35 @@ impl<'a> ParseSession<'a> {
 
     pub fn advance(&mut self) {
         self.last_range = self.range();
-        self.last_token =
-            std::mem::replace(&mut self.token, self.lexer.next().unwrap_or(Token::End));
+        let next_token = self.next_active_token();
+        self.last_token = std::mem::replace(&mut self.token, next_token);
         self.parse_progress += 1;
     }
 
+    /// returns the next token that is not skipped by a conditional compilation pragma,
+    /// the conditional compilation pragmas themselves are never returned
+    fn next_active_token(&mut self) -> Token {
+        loop {
+            let token = self.lexer.next().unwrap_or(Token::End);
+            if token == Token::End {
+                self.conditionals.close_all(&mut self.diagnostics);
+                return token;
+            }
+            if token == Token::Pragma
+                && self.conditionals.apply_pragma(
+                    self.lexer.slice(),
+                    SourceRange::new(self.lexer.span()),
+                    &mut self.diagnostics,
+                )
+            {
+                continue;
+            }
+            if self.conditionals.is_active() {
+                return token;
+            }
+        }
+    }
+
     pub fn slice(&self) -> &str {
         self.lexer.slice()
     }
@@ -221,67 +257,79 @@ pub enum Token {
     #[regex(r"(?m)\r", logos::skip)]
     Error,
 
-    #[token("@EXTERNAL")]
+    #[token("@EXTERNAL", ignore(case))]
     PropertyExternal,
 
-    #[token("PROGRAM")]
+    #[regex(r"\{[^}]*\}")]
+    Pragma,
+
+    #[token("PROGRAM", ignore(case))]
     KeywordProgram,
 
-    #[token("VAR_INPUT")]
+    #[token("VAR_INPUT", ignore(case))]
     KeywordVarInput,
 
-    #[token("VAR_OUTPUT")]
+    #[token("VAR_OUTPUT", ignore(case))]
     KeywordVarOutput,
 
-    #[token("VAR")]
+    #[token("VAR", ignore(case))]
     KeywordVar,
 
-    #[token("VAR_GLOBAL")]
+    #[token("VAR_GLOBAL", ignore(case))]
     KeywordVarGlobal,
 
-    #[token("VAR_IN_OUT")]
+    #[token("VAR_IN_OUT", ignore(case))]
     KeywordVarInOut,
 
-    #[token("END_VAR")]
+    #[token("END_VAR", ignore(case))]
     KeywordEndVar,
 
-    #[token("END_PROGRAM")]
+    #[token("CONSTANT", ignore(case))]
+    KeywordConstant,
+
+    #[token("END_PROGRAM", ignore(case))]
     KeywordEndProgram,
 
-    #[token("FUNCTION")]
+    #[token("FUNCTION", ignore(case))]
     KeywordFunction,
 
-    #[token("END_FUNCTION")]
+    #[token("END_FUNCTION", ignore(case))]
     KeywordEndFunction,
 
-    #[token("FUNCTION_BLOCK")]
+    #[token("FUNCTION_BLOCK", ignore(case))]
     KeywordFunctionBlock,
 
-    #[token("END_FUNCTION_BLOCK")]
+    #[token("END_FUNCTION_BLOCK", ignore(case))]
     KeywordEndFunctionBlock,
 
-    #[token("TYPE")]
+    #[token("TYPE", ignore(case))]
     KeywordType,
 
-    #[token("STRUCT")]
+    #[token("STRUCT", ignore(case))]
     KeywordStruct,
 
-    #[token("END_TYPE")]
+    #[token("END_TYPE", ignore(case))]
     KeywordEndType,
 
-    #[token("END_STRUCT")]
+    #[token("END_STRUCT", ignore(case))]
     KeywordEndStruct,
 
-    #[token("ACTIONS")]
+    #[token("UNION", ignore(case))]
+    KeywordUnion,
+
+    #[token("END_UNION", ignore(case))]
+    KeywordEndUnion,
+
+    #[token("ACTIONS", ignore(case))]
     KeywordActions,
 
-    #[token("ACTION")]
+    #[token("ACTION", ignore(case))]
     KeywordAction,
 
-    #[token("END_ACTION")]
+    #[token("END_ACTION", ignore(case))]
     KeywordEndAction,
 
-    #[token("END_ACTIONS")]
+    #[token("END_ACTIONS", ignore(case))]
     KeywordEndActions,
 
     #[token(":")]
@@ -321,67 +369,73 @@ pub enum Token {
     KeywordDot,
 
     //Control Structures
-    #[token("IF")]
+    #[token("IF", ignore(case))]
     KeywordIf,
 
-    #[token("THEN")]
+    #[token("THEN", ignore(case))]
     KeywordThen,
 
-    #[token("ELSIF")]
+    #[token("ELSIF", ignore(case))]
     KeywordElseIf,
 
-    #[token("ELSE")]
+    #[token("ELSE", ignore(case))]
     KeywordElse,
 
-    #[token("END_IF")]
+    #[token("END_IF", ignore(case))]
     KeywordEndIf,
 
-    #[token("FOR")]
+    #[token("FOR", ignore(case))]
     KeywordFor,
 
-    #[token("TO")]
+    #[token("TO", ignore(case))]
     KeywordTo,
 
-    #[token("BY")]
+    #[token("BY", ignore(case))]
     KeywordBy,
 
-    #[token("DO")]
+    #[token("DO", ignore(case))]
     KeywordDo,
 
-    #[token("END_FOR")]
+    #[token("END_FOR", ignore(case))]
     KeywordEndFor,
 
-    #[token("WHILE")]
+    #[token("WHILE", ignore(case))]
     KeywordWhile,
 
-    #[token("END_WHILE")]
+    #[token("END_WHILE", ignore(case))]
     KeywordEndWhile,
 
-    #[token("REPEAT")]
+    #[token("REPEAT", ignore(case))]
     KeywordRepeat,
 
-    #[token("UNTIL")]
+    #[token("UNTIL", ignore(case))]
     KeywordUntil,
 
-    #[token("END_REPEAT")]
+    #[token("END_REPEAT", ignore(case))]
     KeywordEndRepeat,
 
-    #[token("CASE")]
+    #[token("CASE", ignore(case))]
     KeywordCase,
 
-    #[token("ARRAY")]
+    #[token("ARRAY", ignore(case))]
     KeywordArray,
 
-    #[token("STRING")]
+    #[token("STRING", ignore(case))]
     KeywordString,
 
-    #[token("WSTRING")]
+    #[token("WSTRING", ignore(case))]
     KeywordWideString,
 
-    #[token("OF")]
+    #[token("POINTER", ignore(case))]
+    KeywordPointer,
+
+    #[token("REFERENCE", ignore(case))]
+    KeywordReference,
+
+    #[token("OF", ignore(case))]
     KeywordOf,
 
-    #[token("END_CASE")]
+    #[token("END_CASE", ignore(case))]
     KeywordEndCase,
 
     //Operators
@@ -415,21 +469,24 @@ pub enum Token {
     #[token(">=")]
     OperatorGreaterOrEqual,
 
-    #[token("MOD")]
+    #[token("MOD", ignore(case))]
     OperatorModulo,
 
-    #[token("AND")]
+    #[token("AND", ignore(case))]
     OperatorAnd,
 
-    #[token("OR")]
+    #[token("OR", ignore(case))]
     OperatorOr,
 
-    #[token("XOR")]
+    #[token("XOR", ignore(case))]
     OperatorXor,
 
-    #[token("NOT")]
+    #[token("NOT", ignore(case))]
     OperatorNot,
 
+    #[token("^")]
+    OperatorDeref,
+
     //Identifiers
     #[regex(r"[a-zA-Z_][a-zA-Z_0-9]*")]
     Identifier,
@@ -441,22 +498,25 @@ pub enum Token {
     #[regex("[eE][+-]?[0-9]+")]
     LiteralExponent,
 
-    #[token("TRUE")]
+    #[token("TRUE", ignore(case))]
     LiteralTrue,
 
-    #[token("FALSE")]
+    #[token("FALSE", ignore(case))]
     LiteralFalse,
 
-    #[regex("D(ATE)?#\\d+-\\d+-\\d+")]
+    #[regex("L?D(ATE)?#\\d+-\\d+-\\d+", ignore(case))]
     LiteralDate,
 
-    #[regex("(DATE_AND_TIME|DT)#\\d+-\\d+-\\d+-\\d+:\\d+:\\d+(\\.\\d+)?")]
+    #[regex(
+        "L?(DATE_AND_TIME|DT)#\\d+-\\d+-\\d+-\\d+:\\d+:\\d+(\\.\\d+)?",
+        ignore(case)
+    )]
     LiteralDateAndTime,
 
-    #[regex("(TIME_OF_DAY|TOD)#\\d+:\\d+:\\d+(\\.\\d+)?")]
+    #[regex("L?(TIME_OF_DAY|TOD)#\\d+:\\d+:\\d+(\\.\\d+)?", ignore(case))]
     LiteralTimeOfDay,
 
-    #[regex("T(IME)?#-?(\\d+(\\.\\d+)?(d|h|ms|m|s|us|ns))+")]
+    #[regex("L?T(IME)?#-?(\\d+(\\.\\d+)?(d|h|ms|m|s|us|ns))+", ignore(case))]
     LiteralTime,
 
     #[regex("'((\\$.)|[^$'])*'")]
@@ -472,3 +532,8 @@ pub enum Token {
 pub fn lex(source: &str) -> ParseSession {
     ParseSession::new(Token::lexer(source))
 }
+
+/// lexes the given source, the tokens of inactive conditional compilation branches are skipped
+pub fn lex_with_symbols(source: &str, symbols: Symbols) -> ParseSession {
+    ParseSession::with_symbols(Token::lexer(source), symbols)
+}
@@ -0,0 +1,330 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+//! Conditional compilation pragmas select the parts of a source file that are passed to the parser:
+//!
+//! ```iecst
+//! {IF defined(SIMULATOR)}
+//!     ...
+//! {ELSIF TARGET_ARCH = 'aarch64' AND NOT defined(LEGACY_IO)}
+//!     ...
+//! {ELSE}
+//!     ...
+//! {END_IF}
+//! ```
+//!
+//! Conditions are evaluated against the defined symbols (e.g. `rustyc -D SIMULATOR`) while lexing,
+//! the tokens of inactive branches are skipped.
+use std::collections::HashMap;
+
+use crate::{ast::SourceRange, Diagnostic};
+
+/// the symbols conditions are evaluated against, mapping the symbol's name to its optional value
+/// (`-D NAME` defines a symbol without a value, `-D NAME=VALUE` one with a value)
+pub type Symbols = HashMap<String, Option<String>>;
+
+/// a conditional compilation pragma
+#[derive(Debug, PartialEq)]
+enum Directive<'a> {
+    If(&'a str),
+    Elsif(&'a str),
+    Else,
+    EndIf,
+}
+
+/// returns the directive of the given pragma or None if it is no conditional compilation pragma
+fn parse_directive(pragma: &str) -> Option<Directive> {
+    let content = pragma.trim_start_matches('{').trim_end_matches('}').trim();
+    let keyword_end = content
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or_else(|| content.len());
+    let (keyword, condition) = content.split_at(keyword_end);
+    match keyword.to_uppercase().as_str() {
+        "IF" => Some(Directive::If(condition)),
+        "ELSIF" => Some(Directive::Elsif(condition)),
+        "ELSE" if condition.trim().is_empty() => Some(Directive::Else),
+        "END_IF" if condition.trim().is_empty() => Some(Directive::EndIf),
+        _ => None,
+    }
+}
+
+/// an `{IF}` ... `{END_IF}` region that is currently open
+struct Region {
+    /// the location of the `{IF ...}` pragma that opened this region
+    location: SourceRange,
+    /// whether the surrounding code is active, the branches of an inactive region are never active
+    parent_active: bool,
+    /// whether one of the branches was already selected
+    taken: bool,
+    /// whether the current branch is active
+    active: bool,
+    /// whether the region's `{ELSE}` branch was reached
+    in_else: bool,
+}
+
+/// tracks the open conditional regions of a source file
+#[derive(Default)]
+pub struct ConditionalCompilation {
+    symbols: Symbols,
+    regions: Vec<Region>,
+}
+
+impl ConditionalCompilation {
+    pub fn new(symbols: Symbols) -> ConditionalCompilation {
+        ConditionalCompilation {
+            symbols,
+            regions: vec![],
+        }
+    }
+
+    /// returns true if the tokens at the current position should be parsed
+    pub fn is_active(&self) -> bool {
+        self.regions.last().map_or(true, |it| it.active)
+    }
+
+    /// applies the given pragma if it is a conditional compilation pragma
+    ///
+    /// returns true if the pragma was consumed, errors are reported to the given diagnostics
+    pub fn apply_pragma(
+        &mut self,
+        pragma: &str,
+        location: SourceRange,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> bool {
+        let directive = match parse_directive(pragma) {
+            Some(directive) => directive,
+            None => return false,
+        };
+        let parent_active = self.is_active();
+        match (directive, self.regions.last_mut()) {
+            (Directive::If(condition), _) => {
+                let active =
+                    parent_active && self.evaluate_or_report(condition, &location, diagnostics);
+                self.regions.push(Region {
+                    location,
+                    parent_active,
+                    taken: active,
+                    active,
+                    in_else: false,
+                });
+            }
+            (Directive::Elsif(condition), Some(region)) if !region.in_else => {
+                let can_be_taken = region.parent_active && !region.taken;
+                //the condition is only evaluated (and reported) if the branch could be selected
+                let active =
+                    can_be_taken && self.evaluate_or_report(condition, &location, diagnostics);
+                let region = self.regions.last_mut().unwrap();
+                region.active = active;
+                region.taken |= active;
+            }
+            (Directive::Else, Some(region)) if !region.in_else => {
+                region.active = region.parent_active && !region.taken;
+                region.taken = true;
+                region.in_else = true;
+            }
+            (Directive::EndIf, Some(_)) => {
+                self.regions.pop();
+            }
+            _ => diagnostics.push(Diagnostic::syntax_error(
+                format!("Unexpected {}, it does not continue an open {{IF}}", pragma),
+                location,
+            )),
+        }
+        true
+    }
+
+    /// reports all regions that are still open at the end of the file and closes them
+    pub fn close_all(&mut self, diagnostics: &mut Vec<Diagnostic>) {
+        for region in self.regions.drain(..) {
+            diagnostics.push(Diagnostic::syntax_error(
+                "Missing {END_IF}".to_string(),
+                region.location,
+            ));
+        }
+    }
+
+    fn evaluate_or_report(
+        &self,
+        condition: &str,
+        location: &SourceRange,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> bool {
+        evaluate_condition(condition, &self.symbols).unwrap_or_else(|message| {
+            diagnostics.push(Diagnostic::syntax_error(
+                format!("Invalid condition '{}': {}", condition.trim(), message),
+                location.clone(),
+            ));
+            false
+        })
+    }
+}
+
+/// the words, literals and operators a condition consists of
+#[derive(Debug, PartialEq, Clone)]
+enum ConditionToken {
+    Word(String),
+    Literal(String),
+    ParensOpen,
+    ParensClose,
+    Equal,
+    NotEqual,
+}
+
+fn tokenize(condition: &str) -> Result<Vec<ConditionToken>, String> {
+    let mut tokens = vec![];
+    let mut chars = condition.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(ConditionToken::ParensOpen),
+            ')' => tokens.push(ConditionToken::ParensClose),
+            '=' => tokens.push(ConditionToken::Equal),
+            '<' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push(ConditionToken::NotEqual);
+            }
+            '\'' => {
+                let mut literal = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => literal.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(ConditionToken::Literal(literal));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                        word.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(ConditionToken::Word(word));
+            }
+            c => return Err(format!("unexpected '{}'", c)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// evaluates the given condition against the given symbols. A condition consists of
+/// `defined(NAME)`, comparisons of a symbol's value (`NAME = 'value'`, `NAME <> 4`),
+/// `NOT`, `AND`, `OR` and parentheses.
+pub fn evaluate_condition(condition: &str, symbols: &Symbols) -> Result<bool, String> {
+    let tokens = tokenize(condition)?;
+    let mut parser = ConditionParser {
+        tokens: &tokens,
+        position: 0,
+        symbols,
+    };
+    let result = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(result),
+        Some(token) => Err(format!("unexpected {:?}", token)),
+    }
+}
+
+struct ConditionParser<'a> {
+    tokens: &'a [ConditionToken],
+    position: usize,
+    symbols: &'a Symbols,
+}
+
+impl<'a> ConditionParser<'a> {
+    fn peek(&self) -> Option<&'a ConditionToken> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&'a ConditionToken> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    /// consumes the next token if it is the given keyword
+    fn allow_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(ConditionToken::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, expected: ConditionToken) -> Result<(), String> {
+        match self.next() {
+            Some(token) if *token == expected => Ok(()),
+            token => Err(format!("expected {:?} but found {:?}", expected, token)),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<bool, String> {
+        let mut result = self.parse_and()?;
+        while self.allow_keyword("OR") {
+            //evaluate both sides to report errors in the right one as well
+            result |= self.parse_and()?;
+        }
+        Ok(result)
+    }
+
+    fn parse_and(&mut self) -> Result<bool, String> {
+        let mut result = self.parse_not()?;
+        while self.allow_keyword("AND") {
+            result &= self.parse_not()?;
+        }
+        Ok(result)
+    }
+
+    fn parse_not(&mut self) -> Result<bool, String> {
+        if self.allow_keyword("NOT") {
+            self.parse_not().map(|it| !it)
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<bool, String> {
+        if self.allow_keyword("defined") {
+            self.expect(ConditionToken::ParensOpen)?;
+            let name = self.parse_word()?;
+            self.expect(ConditionToken::ParensClose)?;
+            return Ok(self.symbols.contains_key(name));
+        }
+        match self.peek() {
+            Some(ConditionToken::ParensOpen) => {
+                self.next();
+                let result = self.parse_or()?;
+                self.expect(ConditionToken::ParensClose)?;
+                Ok(result)
+            }
+            Some(ConditionToken::Word(_)) => {
+                let name = self.parse_word()?;
+                let equal = match self.next() {
+                    Some(ConditionToken::Equal) => true,
+                    Some(ConditionToken::NotEqual) => false,
+                    token => return Err(format!("expected a comparison but found {:?}", token)),
+                };
+                let expected = match self.next() {
+                    Some(ConditionToken::Word(value)) | Some(ConditionToken::Literal(value)) => {
+                        value
+                    }
+                    token => return Err(format!("expected a value but found {:?}", token)),
+                };
+                let value = self.symbols.get(name).and_then(|it| it.as_ref());
+                Ok((value == Some(expected)) == equal)
+            }
+            token => Err(format!("unexpected {:?}", token)),
+        }
+    }
+
+    fn parse_word(&mut self) -> Result<&'a str, String> {
+        match self.next() {
+            Some(ConditionToken::Word(word)) => Ok(word.as_str()),
+            token => Err(format!("expected a symbol but found {:?}", token)),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 use pretty_assertions::{assert_eq, assert_ne};
 
 use crate::lexer::{ParseSession, Token::*};
+use crate::Diagnostic;
 
 fn lex(source: &str) -> ParseSession {
     crate::lexer::lex(source)
@@ -13,6 +14,15 @@ fn generic_properties() {
     assert_eq!(lexer.token, PropertyExternal);
 }
 
+#[test]
+fn pragmas_are_lexed_as_one_token() {
+    let mut lexer = lex("{attribute 'calling_convention' := 'C'} @EXTERNAL");
+    assert_eq!(lexer.token, Pragma);
+    assert_eq!(lexer.slice(), "{attribute 'calling_convention' := 'C'}");
+    lexer.advance();
+    assert_eq!(lexer.token, PropertyExternal);
+}
+
 #[test]
 fn windows_and_linux_line_separators_ignored() {
     let mut lexer = lex("PROGRAM\r\nEND_PROGRAM");
@@ -21,6 +31,24 @@ fn windows_and_linux_line_separators_ignored() {
     assert_eq!(lexer.token, KeywordEndProgram, "Token : {}", lexer.slice());
 }
 
+#[test]
+fn keywords_and_literals_are_case_insensitive() {
+    let mut lexer = lex("program End_Program @external true False t#5S dt#1-1-1-1:1:1 lTime#1D");
+    for expected in &[
+        KeywordProgram,
+        KeywordEndProgram,
+        PropertyExternal,
+        LiteralTrue,
+        LiteralFalse,
+        LiteralTime,
+        LiteralDateAndTime,
+        LiteralTime,
+    ] {
+        assert_eq!(lexer.token, *expected, "Token : {}", lexer.slice());
+        lexer.advance();
+    }
+}
+
 #[test]
 fn comments_are_ignored_by_the_lexer() {
     let mut lexer = lex(r"
@@ -323,6 +351,36 @@ fn time_literals_test() {
     }
 }
 
+#[test]
+fn long_date_and_time_literals_test() {
+    let mut lexer = lex(r#"
+    LDATE#1984-10-01 LD#1-1-1
+    LDATE_AND_TIME#1984-10-01-20:15:12 LDT#1-1-1-1:1:1.123456789
+    LTIME_OF_DAY#20:15:12 LTOD#1:1:1.123456789
+    LTIME#12d LT#12m4s3ns
+    "#);
+    for expected in &[
+        LiteralDate,
+        LiteralDate,
+        LiteralDateAndTime,
+        LiteralDateAndTime,
+        LiteralTimeOfDay,
+        LiteralTimeOfDay,
+        LiteralTime,
+        LiteralTime,
+    ] {
+        assert_eq!(
+            lexer.token,
+            *expected,
+            "{} at {:?} is no {:?}",
+            lexer.slice(),
+            lexer.location(),
+            expected
+        );
+        lexer.advance();
+    }
+}
+
 #[test]
 fn a_full_program_generates_correct_token_sequence() {
     let mut lexer = lex(r"
@@ -472,6 +530,19 @@ fn struct_enum_datatype() {
     assert_eq!(lexer.token, KeywordEndType);
 }
 
+#[test]
+fn union_datatype() {
+    let mut lexer = lex(r"TYPE UNION END_UNION END_TYPE");
+
+    assert_eq!(lexer.token, KeywordType);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordUnion);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndUnion);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordEndType);
+}
+
 #[test]
 fn array_parsing() {
     let mut lexer = lex(r"ARRAY OF x[5]");
@@ -490,6 +561,55 @@ fn array_parsing() {
     lexer.advance();
 }
 
+#[test]
+fn pointer_parsing() {
+    let mut lexer = lex(r"POINTER TO x p^.y");
+
+    assert_eq!(lexer.token, KeywordPointer);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordTo);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    lexer.advance();
+    assert_eq!(lexer.token, OperatorDeref);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordDot);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+}
+
+#[test]
+fn reference_parsing() {
+    let mut lexer = lex(r"REFERENCE TO x r REF= y ref=1 reference");
+
+    assert_eq!(lexer.token, KeywordReference);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordTo);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    lexer.advance();
+    //REF= is no token of its own, the parser decides whether it binds a reference
+    assert_eq!(lexer.token, Identifier);
+    assert_eq!("REF", lexer.slice());
+    lexer.advance();
+    assert_eq!(lexer.token, OperatorEqual);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    lexer.advance();
+    assert_eq!(lexer.token, Identifier);
+    assert_eq!("ref", lexer.slice());
+    lexer.advance();
+    assert_eq!(lexer.token, OperatorEqual);
+    lexer.advance();
+    assert_eq!(lexer.token, LiteralInteger);
+    lexer.advance();
+    assert_eq!(lexer.token, KeywordReference);
+}
+
 #[test]
 fn string_parsing() {
     let mut lexer = lex(r"STRING 'AB C' 'AB$$' 'AB$''");
@@ -530,3 +650,127 @@ fn wide_string_parsing() {
     assert_eq!(r#""AB$"""#, lexer.slice());
     lexer.advance();
 }
+
+/// returns the slices of all tokens lexed with the given symbols
+fn lex_slices(source: &str, symbols: &[(&str, Option<&str>)]) -> (Vec<String>, Vec<Diagnostic>) {
+    let symbols = symbols
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.map(str::to_string)))
+        .collect();
+    let mut lexer = crate::lexer::lex_with_symbols(source, symbols);
+    let mut slices = vec![];
+    while lexer.token != End {
+        slices.push(lexer.slice_and_advance());
+    }
+    (slices, lexer.diagnostics)
+}
+
+#[test]
+fn conditional_pragmas_select_the_active_branch() {
+    let source = "
+    a
+    {IF defined(SIMULATOR)}
+        b
+    {ELSIF CHANNELS = 4}
+        c
+    {ELSE}
+        d
+    {END_IF}
+    e
+    ";
+
+    assert_eq!(
+        lex_slices(source, &[("SIMULATOR", None)]).0,
+        vec!["a", "b", "e"]
+    );
+    assert_eq!(
+        lex_slices(source, &[("CHANNELS", Some("4"))]).0,
+        vec!["a", "c", "e"]
+    );
+    assert_eq!(
+        lex_slices(source, &[("CHANNELS", Some("2"))]).0,
+        vec!["a", "d", "e"]
+    );
+    assert_eq!(
+        lex_slices(source, &[("SIMULATOR", None), ("CHANNELS", Some("4"))]).0,
+        vec!["a", "b", "e"]
+    );
+}
+
+#[test]
+fn conditional_pragmas_can_be_nested() {
+    let source = "
+    {IF defined(A)}
+        {IF defined(B)} ab {ELSE} a {END_IF}
+    {ELSE}
+        {IF defined(B)} b {ELSE} none {END_IF}
+    {END_IF}
+    ";
+
+    assert_eq!(
+        lex_slices(source, &[("A", None), ("B", None)]).0,
+        vec!["ab"]
+    );
+    assert_eq!(lex_slices(source, &[("A", None)]).0, vec!["a"]);
+    assert_eq!(lex_slices(source, &[("B", None)]).0, vec!["b"]);
+    assert_eq!(lex_slices(source, &[]).0, vec!["none"]);
+}
+
+#[test]
+fn conditions_support_logical_operators_and_comparisons() {
+    let symbols = [
+        ("A", None),
+        ("TARGET_ARCH", Some("x86_64")),
+        ("MODE", Some("fast")),
+    ];
+    let evaluate = |condition: &str| {
+        let source = format!("{{IF {}}} yes {{ELSE}} no {{END_IF}}", condition);
+        let (slices, diagnostics) = lex_slices(&source, &symbols);
+        assert_eq!(diagnostics, vec![]);
+        slices == vec!["yes"]
+    };
+
+    assert!(evaluate("defined(A)"));
+    assert!(!evaluate("defined(B)"));
+    assert!(evaluate("NOT defined(B)"));
+    assert!(evaluate("defined(A) AND TARGET_ARCH = 'x86_64'"));
+    assert!(!evaluate("defined(A) AND defined(B)"));
+    assert!(evaluate("defined(B) OR MODE = fast"));
+    assert!(evaluate("MODE <> 'slow'"));
+    assert!(!evaluate("B = 'x'"));
+    assert!(evaluate("NOT (defined(B) OR defined(C)) and defined(A)"));
+}
+
+#[test]
+fn attribute_pragmas_are_not_consumed_by_conditional_pragmas() {
+    let (slices, _) = lex_slices(
+        "{IF defined(A)} {attribute 'calling_convention' := 'C'} {END_IF} x",
+        &[("A", None)],
+    );
+    assert_eq!(slices, vec!["{attribute 'calling_convention' := 'C'}", "x"]);
+}
+
+#[test]
+fn unbalanced_conditional_pragmas_are_reported() {
+    let (slices, diagnostics) = lex_slices("a {END_IF} b {IF defined(A)} c", &[]);
+    assert_eq!(slices, vec!["a", "b"]);
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::syntax_error(
+                "Unexpected {END_IF}, it does not continue an open {IF}".to_string(),
+                (2..10).into()
+            ),
+            Diagnostic::syntax_error("Missing {END_IF}".to_string(), (13..28).into()),
+        ]
+    );
+
+    let (_, diagnostics) = lex_slices("{IF defined(A} a {END_IF}", &[]);
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::syntax_error(
+            "Invalid condition 'defined(A': expected ParensClose but found None".to_string(),
+            (0..14).into()
+        )]
+    );
+}
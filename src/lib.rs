@@ -27,21 +27,24 @@ use codespan_reporting::term::{self, Chars, Styles};
 use compile_error::CompileError;
 use index::Index;
 use inkwell::context::Context;
-use inkwell::targets::{
-    CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
-};
+use inkwell::targets::{FileType, RelocMode, TargetMachine, TargetTriple};
 use parser::ParsedAst;
 
 use crate::ast::CompilationUnit;
 mod ast;
+mod builtins;
 pub mod cli;
 mod codegen;
 pub mod compile_error;
+mod generics;
 pub mod index;
 mod lexer;
 mod parser;
+mod stdlib;
 mod typesystem;
 
+pub use codegen::CodeGenOptions;
+
 #[macro_use]
 extern crate pretty_assertions;
 
@@ -145,30 +148,12 @@ fn compile_to_obj(
     output: &str,
     reloc: RelocMode,
     triple: Option<String>,
+    options: CodeGenOptions,
 ) -> Result<(), CompileError> {
-    let initialization_config = &InitializationConfig::default();
-    Target::initialize_all(initialization_config);
-
-    let triple = triple
-        .map(|it| TargetTriple::create(it.as_str()))
-        .or_else(|| Some(TargetMachine::get_default_triple()))
-        .unwrap();
-    let target = Target::from_triple(&triple).unwrap();
-    let machine = target
-        .create_target_machine(
-            &triple,
-            //TODO : Add cpu features as optionals
-            "generic", //TargetMachine::get_host_cpu_name().to_string().as_str(),
-            "",        //TargetMachine::get_host_cpu_features().to_string().as_str(),
-            //TODO Optimisation as parameter
-            inkwell::OptimizationLevel::Default,
-            reloc,
-            CodeModel::Default,
-        )
-        .unwrap();
+    let machine = codegen::create_target_machine(&get_target_triple(triple), reloc)?;
 
     let c = Context::create();
-    let code_generator = compile_module(&c, sources)?;
+    let code_generator = compile_module_for_target(&c, sources, options, &machine)?;
     machine
         .write_to_file(&code_generator.module, FileType::Object, Path::new(output))
         .unwrap();
@@ -189,7 +174,25 @@ pub fn compile_to_static_obj(
     output: &str,
     target: Option<String>,
 ) -> Result<(), CompileError> {
-    compile_to_obj(sources, output, RelocMode::Default, target)
+    compile_to_static_obj_with_options(sources, output, target, CodeGenOptions::default())
+}
+
+/// Compiles a given source string to a static object using the given options and saves the output.
+///
+/// # Arguments
+///
+/// * `sources` - the source to be compiled
+/// * `output` - the location on disk to save the output
+/// * `target` - an optional llvm target triple
+///     If not provided, the machine's triple will be used.
+/// * `options` - the options used to generate the code
+pub fn compile_to_static_obj_with_options(
+    sources: &Sources,
+    output: &str,
+    target: Option<String>,
+    options: CodeGenOptions,
+) -> Result<(), CompileError> {
+    compile_to_obj(sources, output, RelocMode::Default, target, options)
 }
 
 /// Compiles a given source string to a shared position independent object and saves the output.
@@ -205,7 +208,25 @@ pub fn compile_to_shared_pic_object(
     output: &str,
     target: Option<String>,
 ) -> Result<(), CompileError> {
-    compile_to_obj(sources, output, RelocMode::PIC, target)
+    compile_to_shared_pic_object_with_options(sources, output, target, CodeGenOptions::default())
+}
+
+/// Compiles a given source string to a shared position independent object using the given options and saves the output.
+///
+/// # Arguments
+///
+/// * `sources` - the source to be compiled
+/// * `output` - the location on disk to save the output
+/// * `target` - an optional llvm target triple
+///     If not provided, the machine's triple will be used.
+/// * `options` - the options used to generate the code
+pub fn compile_to_shared_pic_object_with_options(
+    sources: &Sources,
+    output: &str,
+    target: Option<String>,
+    options: CodeGenOptions,
+) -> Result<(), CompileError> {
+    compile_to_obj(sources, output, RelocMode::PIC, target, options)
 }
 
 /// Compiles a given source string to a dynamic non PIC object and saves the output.
@@ -221,7 +242,25 @@ pub fn compile_to_shared_object(
     output: &str,
     target: Option<String>,
 ) -> Result<(), CompileError> {
-    compile_to_obj(sources, output, RelocMode::DynamicNoPic, target)
+    compile_to_shared_object_with_options(sources, output, target, CodeGenOptions::default())
+}
+
+/// Compiles a given source string to a dynamic non PIC object using the given options and saves the output.
+///
+/// # Arguments
+///
+/// * `sources` - the source to be compiled
+/// * `output` - the location on disk to save the output
+/// * `target` - an optional llvm target triple
+///     If not provided, the machine's triple will be used.
+/// * `options` - the options used to generate the code
+pub fn compile_to_shared_object_with_options(
+    sources: &Sources,
+    output: &str,
+    target: Option<String>,
+    options: CodeGenOptions,
+) -> Result<(), CompileError> {
+    compile_to_obj(sources, output, RelocMode::DynamicNoPic, target, options)
 }
 
 ///
@@ -232,9 +271,29 @@ pub fn compile_to_shared_object(
 /// * `sources` - the source to be compiled
 /// * `output` - the location on disk to save the output
 pub fn compile_to_bitcode(sources: &Sources, output: &str) -> Result<(), CompileError> {
+    compile_to_bitcode_with_options(sources, output, None, CodeGenOptions::default())
+}
+
+///
+/// Compiles the given source into a bitcode file using the given options
+///
+/// # Arguments
+///
+/// * `sources` - the source to be compiled
+/// * `output` - the location on disk to save the output
+/// * `target` - an optional llvm target triple
+///     If not provided, the machine's triple will be used.
+/// * `options` - the options used to generate the code
+pub fn compile_to_bitcode_with_options(
+    sources: &Sources,
+    output: &str,
+    target: Option<String>,
+    options: CodeGenOptions,
+) -> Result<(), CompileError> {
     let path = Path::new(output);
+    let machine = codegen::create_target_machine(&get_target_triple(target), RelocMode::Default)?;
     let c = Context::create();
-    let code_generator = compile_module(&c, sources)?;
+    let code_generator = compile_module_for_target(&c, sources, options, &machine)?;
     code_generator.module.write_bitcode_to_path(path);
     Ok(())
 }
@@ -246,8 +305,26 @@ pub fn compile_to_bitcode(sources: &Sources, output: &str) -> Result<(), Compile
 ///
 /// * `sources` - the source to be compiled
 pub fn compile_to_ir(sources: &Sources) -> Result<String, CompileError> {
+    compile_to_ir_with_options(sources, None, CodeGenOptions::default())
+}
+
+///
+/// Compiles the given source into LLVM IR using the given options and returns it
+///
+/// # Arguments
+///
+/// * `sources` - the source to be compiled
+/// * `target` - an optional llvm target triple
+///     If not provided, the machine's triple will be used.
+/// * `options` - the options used to generate the code
+pub fn compile_to_ir_with_options(
+    sources: &Sources,
+    target: Option<String>,
+    options: CodeGenOptions,
+) -> Result<String, CompileError> {
+    let machine = codegen::create_target_machine(&get_target_triple(target), RelocMode::Default)?;
     let c = Context::create();
-    let code_gen = compile_module(&c, sources)?;
+    let code_gen = compile_module_for_target(&c, sources, options, &machine)?;
     Ok(code_gen.module.print_to_string().to_string())
 }
 
@@ -262,8 +339,47 @@ pub fn compile_module<'c>(
     context: &'c Context,
     sources: &Sources,
 ) -> Result<codegen::CodeGen<'c>, CompileError> {
+    compile_module_with_options(context, sources, CodeGenOptions::default())
+}
+
+///
+/// Compiles the given source into a `codegen::CodeGen` using the provided context and options
+///
+/// # Arguments
+///
+/// * `context` - the LLVM Context to be used for the compilation
+/// * `sources` - the source to be compiled
+/// * `options` - the options used to generate the code
+pub fn compile_module_with_options<'c>(
+    context: &'c Context,
+    sources: &Sources,
+    options: CodeGenOptions,
+) -> Result<codegen::CodeGen<'c>, CompileError> {
+    let machine =
+        codegen::create_target_machine(&TargetMachine::get_default_triple(), RelocMode::Default)?;
+    compile_module_for_target(context, sources, options, &machine)
+}
+
+/// returns the given target triple or the machine's triple if no triple is provided
+fn get_target_triple(triple: Option<String>) -> TargetTriple {
+    triple
+        .map(|it| TargetTriple::create(it.as_str()))
+        .unwrap_or_else(TargetMachine::get_default_triple)
+}
+
+///
+/// Compiles the given source into a `codegen::CodeGen` for the given target machine.
+/// The machine defines the target symbols used for conditional compilation and the data layout
+fn compile_module_for_target<'c>(
+    context: &'c Context,
+    sources: &Sources,
+    options: CodeGenOptions,
+    machine: &TargetMachine,
+) -> Result<codegen::CodeGen<'c>, CompileError> {
+    let symbols = create_symbols(&options, machine);
     let mut full_index = Index::new();
     let mut unit = CompilationUnit::default();
+    let mut generic_functions = CompilationUnit::default();
     // let mut diagnostics : Vec<Diagnostic> = vec![];
     let mut files: SimpleFiles<String, String> = SimpleFiles::new();
     for container in sources {
@@ -271,8 +387,10 @@ pub fn compile_module<'c>(
             .load_source()
             .map_err(|err| CompileError::io_error(err, container.get_location().to_string()))?;
 
-        let (mut parse_result, diagnostics) = parse(e.source.as_str())?;
+        let (mut parse_result, diagnostics) = parse(e.source.as_str(), symbols.clone())?;
+        name_global_variable_lists(&mut parse_result, &e.path);
         ast::pre_process(&mut parse_result);
+        generic_functions.import(generics::extract_generic_functions(&mut parse_result));
         full_index.import(index::visitor::visit(&parse_result));
         unit.import(parse_result);
 
@@ -304,15 +422,73 @@ pub fn compile_module<'c>(
         }
     }
 
+    //create an implementation of every generic function for the types it is called with
+    generics::instantiate_generic_functions(&generic_functions, &mut unit, &mut full_index)?;
+
+    //link the used parts of the standard library
+    let mut library = CompilationUnit::default();
+    for library_source in stdlib::LIBRARY_SOURCES.iter() {
+        let (mut parse_result, diagnostics) = parse(library_source, symbols.clone())?;
+        //the library is part of the compiler, it must parse without any diagnostics
+        if let Some(diagnostic) = diagnostics.first() {
+            return Err(CompileError::codegen_error(
+                format!(
+                    "Cannot parse the standard library: {}",
+                    diagnostic.get_message()
+                ),
+                diagnostic.get_location(),
+            ));
+        }
+        ast::pre_process(&mut parse_result);
+        library.import(parse_result);
+    }
+    let library = stdlib::link_library(library, &unit, &full_index);
+    full_index.import(index::visitor::visit(&library));
+    unit.import(library);
+
     //and finally codegen
-    let code_generator = codegen::CodeGen::new(context, "main");
+    let code_generator = codegen::CodeGen::new_for_target(context, "main", options, machine);
     code_generator.generate(unit, &full_index)?;
     Ok(code_generator)
 }
 
-fn parse(source: &str) -> Result<ParsedAst, CompileError> {
+/// returns the symbols used for conditional compilation: the target symbols of the given machine
+/// (`TARGET_TRIPLE`, `TARGET_ARCH`, `TARGET_POINTER_SIZE`) and the symbols defined in the options
+fn create_symbols(options: &CodeGenOptions, machine: &TargetMachine) -> lexer::Symbols {
+    let triple = machine.get_triple().as_str().to_string_lossy().to_string();
+    let arch = triple.split('-').next().unwrap_or_default().to_string();
+    //the pointer size in bytes
+    let pointer_size = machine.get_target_data().get_pointer_byte_size(None);
+    let mut symbols = lexer::Symbols::new();
+    symbols.insert("TARGET_TRIPLE".to_string(), Some(triple));
+    symbols.insert("TARGET_ARCH".to_string(), Some(arch));
+    symbols.insert(
+        "TARGET_POINTER_SIZE".to_string(),
+        Some(pointer_size.to_string()),
+    );
+    symbols.extend(options.defines.iter().cloned());
+    symbols
+}
+
+/// names the unnamed `qualified_only` global variable lists of the given unit after the file
+/// they are declared in, so `{attribute 'qualified_only'}` globals in `Gvl_Io.st` are accessed as `Gvl_Io.x`
+fn name_global_variable_lists(unit: &mut CompilationUnit, path: &str) {
+    let file_name = Path::new(path)
+        .file_stem()
+        .and_then(|it| it.to_str())
+        .unwrap_or_default();
+    for block in unit
+        .global_vars
+        .iter_mut()
+        .filter(|it| it.qualified_only && it.name.is_none())
+    {
+        block.name = Some(file_name.to_string());
+    }
+}
+
+fn parse(source: &str, symbols: lexer::Symbols) -> Result<ParsedAst, CompileError> {
     //Start lexing
-    let lexer = lexer::lex(source);
+    let lexer = lexer::lex_with_symbols(source, symbols);
     //Parse
     //TODO : Parser should also return compile errors with sane locations
     parser::parse(lexer).map_err(|err| err.into())
@@ -21,8 +21,9 @@ use glob::glob;
 use rusty::{
     cli::{CompileParameters, FormatOption, ParameterError},
     compile_error::CompileError,
-    compile_to_bitcode, compile_to_ir, compile_to_shared_object, compile_to_static_obj, SourceCode,
-    SourceContainer,
+    compile_to_bitcode_with_options, compile_to_ir_with_options,
+    compile_to_shared_object_with_options, compile_to_static_obj_with_options, CodeGenOptions,
+    SourceCode, SourceContainer,
 };
 use std::fs;
 
@@ -80,24 +81,54 @@ fn main_compile(parameters: CompileParameters) {
 
     let sources = sources.as_slice();
     let output_filename = parameters.output_name().unwrap();
+    let options = parameters.codegen_options();
 
     match parameters.output_format_or_default() {
         FormatOption::Static => {
-            compile_to_static_obj(sources, output_filename.as_str(), parameters.target).unwrap();
+            compile_to_static_obj_with_options(
+                sources,
+                output_filename.as_str(),
+                parameters.target,
+                options,
+            )
+            .unwrap();
         }
         FormatOption::Shared | FormatOption::PIC => {
-            compile_to_shared_object(sources, output_filename.as_str(), parameters.target).unwrap();
+            compile_to_shared_object_with_options(
+                sources,
+                output_filename.as_str(),
+                parameters.target,
+                options,
+            )
+            .unwrap();
         }
         FormatOption::Bitcode => {
-            compile_to_bitcode(sources, output_filename.as_str()).unwrap();
+            compile_to_bitcode_with_options(
+                sources,
+                output_filename.as_str(),
+                parameters.target,
+                options,
+            )
+            .unwrap();
         }
         FormatOption::IR => {
-            generate_ir(sources, output_filename.as_str()).unwrap();
+            generate_ir(
+                sources,
+                output_filename.as_str(),
+                parameters.target,
+                options,
+            )
+            .unwrap();
         }
     }
 }
-fn generate_ir(sources: &[&dyn SourceContainer], output: &str) -> Result<(), CompileError> {
-    let ir = compile_to_ir(sources)?;
+fn generate_ir(
+    sources: &[&dyn SourceContainer],
+    output: &str,
+    target: Option<String>,
+    options: CodeGenOptions,
+) -> Result<(), CompileError> {
+    let ir = compile_to_ir_with_options(sources, target, options)?;
     fs::write(output, ir).unwrap();
     Ok(())
 }
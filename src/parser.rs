@@ -21,6 +21,8 @@ pub fn parse(mut lexer: ParseSession) -> PResult<ParsedAst> {
     let mut unit = CompilationUnit::default();
 
     let mut linkage = LinkageType::Internal;
+    let mut calling_convention = None;
+    let mut pack_mode = None;
     loop {
         match lexer.token {
             PropertyExternal => {
@@ -29,10 +31,22 @@ pub fn parse(mut lexer: ParseSession) -> PResult<ParsedAst> {
                 //Don't reset linkage
                 continue;
             }
+            Pragma => {
+                if let Some(convention) = parse_calling_convention_pragma(&mut lexer) {
+                    calling_convention = Some((convention, lexer.location()));
+                }
+                if let Some(mode) = parse_pack_mode_pragma(&mut lexer) {
+                    pack_mode = Some((mode, lexer.location()));
+                }
+                lexer.advance();
+                //Don't reset the calling convention or the pack mode
+                continue;
+            }
             KeywordVarGlobal => unit
                 .global_vars
                 .push(parse_variable_block(&mut lexer, VariableBlockType::Global)),
             KeywordProgram => {
+                report_calling_convention_of_non_function(&mut lexer, &calling_convention);
                 if let Some((pou, implementation)) =
                     parse_pou(&mut lexer, PouType::Program, linkage, KeywordEndProgram)
                 {
@@ -41,14 +55,18 @@ pub fn parse(mut lexer: ParseSession) -> PResult<ParsedAst> {
                 }
             }
             KeywordFunction => {
-                if let Some((pou, implementation)) =
+                if let Some((pou, mut implementation)) =
                     parse_pou(&mut lexer, PouType::Function, linkage, KeywordEndFunction)
                 {
+                    if let Some((convention, _)) = calling_convention {
+                        implementation.calling_convention = convention;
+                    }
                     unit.units.push(pou);
                     unit.implementations.push(implementation);
                 }
             }
             KeywordFunctionBlock => {
+                report_calling_convention_of_non_function(&mut lexer, &calling_convention);
                 if let Some((pou, implementation)) = parse_pou(
                     &mut lexer,
                     PouType::FunctionBlock,
@@ -60,16 +78,33 @@ pub fn parse(mut lexer: ParseSession) -> PResult<ParsedAst> {
                 }
             }
             KeywordAction => {
+                report_calling_convention_of_non_function(&mut lexer, &calling_convention);
                 if let Some(implementation) = parse_action(&mut lexer, linkage, None) {
                     unit.implementations.push(implementation);
                 }
             }
             KeywordActions => {
+                report_calling_convention_of_non_function(&mut lexer, &calling_convention);
                 let mut actions = parse_actions(&mut lexer, linkage)?;
                 unit.implementations.append(&mut actions);
             }
             KeywordType => {
-                if let Some(unit_type) = parse_type(&mut lexer) {
+                if let Some(mut unit_type) = parse_type(&mut lexer) {
+                    if let Some((mode, location)) = pack_mode {
+                        if let DataType::StructType {
+                            pack_mode: struct_pack_mode,
+                            ..
+                        } = &mut unit_type.data_type
+                        {
+                            *struct_pack_mode = Some(mode);
+                        } else {
+                            lexer.accept_diagnostic(Diagnostic::syntax_error(
+                                "The pack_mode attribute can only be applied to structs"
+                                    .to_string(),
+                                location,
+                            ));
+                        }
+                    }
                     unit.types.push(unit_type);
                 }
             }
@@ -83,10 +118,92 @@ pub fn parse(mut lexer: ParseSession) -> PResult<ParsedAst> {
             }
         };
         linkage = LinkageType::Internal;
+        calling_convention = None;
+        pack_mode = None;
     }
     //the match in the loop will always return
 }
 
+/// parses an attribute-pragma like `{attribute 'name'}` or `{attribute 'name' := 'value'}`
+///
+/// returns the attribute's name and its optional value, or None if the pragma is no attribute.
+pub fn parse_attribute_pragma(pragma: &str) -> Option<(String, Option<String>)> {
+    let unquote = |text: &str| {
+        let text = text.trim();
+        if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+            Some(text[1..text.len() - 1].to_string())
+        } else {
+            None
+        }
+    };
+
+    let content = pragma.trim_start_matches('{').trim_end_matches('}').trim();
+    let attribute = content.strip_prefix("attribute")?;
+    let mut segments = attribute.splitn(2, ":=");
+    let name = segments.next().and_then(unquote)?;
+    match segments.next() {
+        Some(value) => unquote(value).map(|value| (name, Some(value))),
+        None => Some((name, None)),
+    }
+}
+
+/// reports a calling convention that was declared in front of a program, function block or action
+///
+/// only functions can be called with the C calling convention
+fn report_calling_convention_of_non_function(
+    lexer: &mut ParseSession,
+    calling_convention: &Option<(CallingConvention, SourceRange)>,
+) {
+    if let Some((_, location)) = calling_convention {
+        lexer.accept_diagnostic(Diagnostic::syntax_error(
+            "The calling_convention attribute can only be applied to functions".to_string(),
+            location.clone(),
+        ));
+    }
+}
+
+/// parses the current `{attribute 'calling_convention' := '...'}` pragma
+///
+/// other pragmas are ignored, unknown calling conventions are reported
+fn parse_calling_convention_pragma(lexer: &mut ParseSession) -> Option<CallingConvention> {
+    match parse_attribute_pragma(lexer.slice()) {
+        Some((name, value)) if name == "calling_convention" => match value.as_deref() {
+            Some("C") => Some(CallingConvention::C),
+            _ => {
+                lexer.accept_diagnostic(Diagnostic::syntax_error(
+                    format!("Unknown calling convention {:?}", value.unwrap_or_default()),
+                    lexer.location(),
+                ));
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+/// parses the current `{attribute 'pack_mode' := '...'}` pragma and returns the maximum
+/// alignment of a struct's members in bytes (`'0'` and `'1'` both pack the struct without padding)
+///
+/// other pragmas are ignored, unsupported pack modes are reported
+fn parse_pack_mode_pragma(lexer: &mut ParseSession) -> Option<u32> {
+    match parse_attribute_pragma(lexer.slice()) {
+        Some((name, value)) if name == "pack_mode" => match value.as_deref() {
+            Some("0") | Some("1") => Some(1),
+            Some("2") => Some(2),
+            Some("4") => Some(4),
+            Some("8") => Some(8),
+            _ => {
+                lexer.accept_diagnostic(Diagnostic::syntax_error(
+                    format!("Unsupported pack mode {:?}", value.unwrap_or_default()),
+                    lexer.location(),
+                ));
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
 fn parse_actions(
     mut lexer: &mut ParseSession,
     linkage: LinkageType,
@@ -230,6 +347,7 @@ fn parse_implementation(
         name: call_name.into(),
         type_name: type_name.into(),
         linkage,
+        calling_convention: CallingConvention::Default,
         pou_type,
         statements,
         location: SourceRange::new(start..lexer.range().end),
@@ -308,10 +426,10 @@ fn parse_full_data_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
 ) -> Option<DataTypeWithInitializer> {
-    let end_keyword = if lexer.token == KeywordStruct {
-        KeywordEndStruct
-    } else {
-        KeywordSemicolon
+    let end_keyword = match lexer.token {
+        KeywordStruct => KeywordEndStruct,
+        KeywordUnion => KeywordEndUnion,
+        _ => KeywordSemicolon,
     };
     parse_any_in_region(lexer, vec![end_keyword], |lexer| {
         if lexer.allow(&KeywordDotDotDot) {
@@ -342,27 +460,39 @@ fn parse_full_data_type_definition(
     })
 }
 
-// TYPE xxx : 'STRUCT' | '(' | IDENTIFIER
+// TYPE xxx : 'STRUCT' | 'UNION' | 'ARRAY' | 'POINTER' | 'REFERENCE' | '(' | IDENTIFIER
 fn parse_data_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
 ) -> Result<DataTypeWithInitializer, Diagnostic> {
     let result = if lexer.allow(&KeywordStruct) {
         //STRUCT
-        let mut variables = Vec::new();
-        while lexer.token == Identifier {
-            if let Some(variable) = parse_variable(lexer) {
-                variables.push(variable);
-            }
-        }
+        let variables = parse_member_variables(lexer);
+        Ok((
+            DataTypeDeclaration::DataTypeDefinition {
+                data_type: DataType::StructType {
+                    name,
+                    variables,
+                    pack_mode: None,
+                },
+            },
+            None,
+        ))
+    } else if lexer.allow(&KeywordUnion) {
+        //UNION
+        let variables = parse_member_variables(lexer);
         Ok((
             DataTypeDeclaration::DataTypeDefinition {
-                data_type: DataType::StructType { name, variables },
+                data_type: DataType::UnionType { name, variables },
             },
             None,
         ))
     } else if lexer.allow(&KeywordArray) {
         parse_array_type_definition(lexer, name)
+    } else if lexer.allow(&KeywordPointer) {
+        parse_pointer_type_definition(lexer, name, false)
+    } else if lexer.allow(&KeywordReference) {
+        parse_pointer_type_definition(lexer, name, true)
     } else if lexer.allow(&KeywordParensOpen) {
         parse_enum_type_definition(lexer, name)
     } else if lexer.token == KeywordString || lexer.token == KeywordWideString {
@@ -381,6 +511,17 @@ fn parse_data_type_definition(
     result
 }
 
+/// parses the member variables of a STRUCT or UNION
+fn parse_member_variables(lexer: &mut ParseSession) -> Vec<Variable> {
+    let mut variables = Vec::new();
+    while lexer.token == Identifier {
+        if let Some(variable) = parse_variable(lexer) {
+            variables.push(variable);
+        }
+    }
+    variables
+}
+
 fn parse_type_reference_type_definition(
     lexer: &mut ParseSession,
     name: Option<String>,
@@ -490,7 +631,11 @@ fn parse_array_type_definition(
         lexer.expect(KeywordSquareParensOpen)?;
         lexer.advance();
         //parse range
-        let range = parse_primary_expression(lexer);
+        let range = if lexer.token == OperatorMultiplication {
+            parse_variable_length_array_bounds(lexer)
+        } else {
+            parse_primary_expression(lexer)
+        };
         //expect close range
         lexer.expect(KeywordSquareParensClose)?;
         lexer.advance();
@@ -511,6 +656,54 @@ fn parse_array_type_definition(
     })
 }
 
+/// parses a `POINTER TO` or a `REFERENCE TO` (`auto_deref`) type
+fn parse_pointer_type_definition(
+    lexer: &mut ParseSession,
+    name: Option<String>,
+    auto_deref: bool,
+) -> PResult<(DataTypeDeclaration, Option<Statement>)> {
+    //POINTER TO | REFERENCE TO
+    lexer.expect(KeywordTo)?;
+    lexer.advance();
+    parse_data_type_definition(lexer, None).map(|(reference, initializer)| {
+        (
+            DataTypeDeclaration::DataTypeDefinition {
+                data_type: DataType::PointerType {
+                    name,
+                    referenced_type: Box::new(reference),
+                    auto_deref,
+                },
+            },
+            initializer,
+        )
+    })
+}
+
+/// parses the bounds of a variable-length array (e.g. `[*]` or `[*, *]`)
+/// every `*` is represented as an EmptyStatement
+fn parse_variable_length_array_bounds(lexer: &mut ParseSession) -> PResult<Statement> {
+    let mut dimensions = vec![];
+    loop {
+        lexer.expect(OperatorMultiplication)?;
+        dimensions.push(Statement::EmptyStatement {
+            location: lexer.location(),
+        });
+        lexer.advance();
+        if lexer.token == KeywordComma {
+            lexer.advance();
+        } else {
+            break;
+        }
+    }
+    if dimensions.len() == 1 {
+        Ok(dimensions.remove(0))
+    } else {
+        Ok(Statement::ExpressionList {
+            expressions: dimensions,
+        })
+    }
+}
+
 /// parse a body and recovers until the given `end_keywords`
 fn parse_body_in_region(
     lexer: &mut ParseSession,
@@ -606,8 +799,26 @@ fn parse_variable_block(
 ) -> VariableBlock {
     //Consume the type keyword
     lexer.advance();
-    let variables = parse_any_in_region(lexer, vec![KeywordEndVar], |lexer| {
+    let mut block = VariableBlock {
+        variables: vec![],
+        variable_block_type,
+        name: None,
+        qualified_only: false,
+        constant: false,
+    };
+    let first_variable = match variable_block_type {
+        VariableBlockType::Global => parse_global_variable_list_header(lexer, &mut block),
+        VariableBlockType::Input => {
+            parse_input_block_header(lexer, &mut block);
+            None
+        }
+        _ => None,
+    };
+    block.variables = parse_any_in_region(lexer, vec![KeywordEndVar], |lexer| {
         let mut variables = vec![];
+        if let Some((name, location)) = first_variable {
+            variables.extend(parse_variable_declaration(lexer, name, location));
+        }
         while lexer.token == Identifier {
             if let Some(variable) = parse_variable(lexer) {
                 variables.push(variable);
@@ -616,16 +827,67 @@ fn parse_variable_block(
         Ok(variables)
     })
     .unwrap_or_default();
-    VariableBlock {
-        variables,
-        variable_block_type,
+    block
+}
+
+/// parses the optional name and `{attribute 'qualified_only'}` pragma of a global variable list
+/// (e.g. `VAR_GLOBAL Gvl_Io {attribute 'qualified_only'}`)
+///
+/// the name can only be told apart from the first variable by the missing colon, so the first
+/// variable's name and location are returned if they were consumed
+fn parse_global_variable_list_header(
+    lexer: &mut ParseSession,
+    block: &mut VariableBlock,
+) -> Option<(String, SourceRange)> {
+    loop {
+        match lexer.token {
+            Pragma => {
+                if let Some((name, None)) = parse_attribute_pragma(lexer.slice()) {
+                    block.qualified_only |= name == "qualified_only";
+                }
+                lexer.advance();
+            }
+            Identifier if block.name.is_none() => {
+                let location = lexer.location();
+                let name = lexer.slice_and_advance();
+                if lexer.token == KeywordColon {
+                    return Some((name, location));
+                }
+                block.name = Some(name);
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// parses the optional `CONSTANT` keyword or `{attribute 'by_ref'}` pragma of a VAR_INPUT block
+fn parse_input_block_header(lexer: &mut ParseSession, block: &mut VariableBlock) {
+    loop {
+        match lexer.token {
+            KeywordConstant => block.constant = true,
+            Pragma => {
+                if let Some((name, None)) = parse_attribute_pragma(lexer.slice()) {
+                    block.constant |= name == "by_ref";
+                }
+            }
+            _ => return,
+        }
+        lexer.advance();
     }
 }
 
 fn parse_variable(lexer: &mut ParseSession) -> Option<Variable> {
     let variable_location = lexer.location();
     let name = lexer.slice_and_advance();
+    parse_variable_declaration(lexer, name, variable_location)
+}
 
+/// parses the declaration of the variable with the given name, starting at the colon
+fn parse_variable_declaration(
+    lexer: &mut ParseSession,
+    name: String,
+    variable_location: SourceRange,
+) -> Option<Variable> {
     //parse or recover until the colon
     if !lexer.allow(&KeywordColon) {
         lexer.accept_diagnostic(Diagnostic::missing_token(
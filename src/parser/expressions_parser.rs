@@ -33,6 +33,26 @@ pub fn parse_expression_list(lexer: &mut ParseSession) -> Result<Statement, Pars
     left
 }
 
+/// parses the arguments of a call. A single struct literal (`foo((x := 1, y := 2))`) is
+/// wrapped into an argument-list, so its assignments are not mistaken for formal arguments
+fn parse_call_arguments(lexer: &mut ParseSession) -> Result<Statement, ParseError> {
+    let first = parse_range_statement(lexer)?;
+    if lexer.token == KeywordComma {
+        let mut expressions = vec![first];
+        while lexer.token == KeywordComma {
+            lexer.advance();
+            expressions.push(parse_range_statement(lexer)?);
+        }
+        Ok(Statement::ExpressionList { expressions })
+    } else if let Statement::ExpressionList { .. } = first {
+        Ok(Statement::ExpressionList {
+            expressions: vec![first],
+        })
+    } else {
+        Ok(first)
+    }
+}
+
 pub(crate) fn parse_range_statement(lexer: &mut ParseSession) -> Result<Statement, ParseError> {
     let start = parse_or_expression(lexer)?;
 
@@ -246,10 +266,26 @@ fn parse_leaf_expression(lexer: &mut ParseSession) -> Result<Statement, ParseErr
             left: Box::new(current?),
             right: Box::new(parse_range_statement(lexer)?),
         });
+    } else if current.is_ok() && is_ref_assignment(lexer) {
+        lexer.advance();
+        lexer.expect(OperatorEqual)?;
+        lexer.advance();
+        return Ok(Statement::RefAssignment {
+            left: Box::new(current?),
+            right: Box::new(parse_range_statement(lexer)?),
+        });
     };
     current
 }
 
+/// returns true if the current token is the `REF` of a `REF=` assignment
+///
+/// `REF` is no keyword so variables named `ref` can still be used (e.g. `IF ref=1 THEN`),
+/// an identifier following a complete operand can only start a `REF=` though
+fn is_ref_assignment(lexer: &ParseSession) -> bool {
+    lexer.token == Identifier && lexer.slice().eq_ignore_ascii_case("REF")
+}
+
 fn parse_array_literal(lexer: &mut ParseSession) -> Result<Statement, ParseError> {
     let start = lexer.range().start;
     lexer.expect(KeywordSquareParensOpen)?;
@@ -299,7 +335,7 @@ pub fn parse_qualified_reference(lexer: &mut ParseSession) -> Result<Statement,
             parse_statement_in_region(lexer, vec![KeywordParensClose], |lexer| {
                 Ok(Statement::CallStatement {
                     operator: Box::new(reference),
-                    parameters: Box::new(Some(parse_expression_list(lexer)?)),
+                    parameters: Box::new(Some(parse_call_arguments(lexer)?)),
                     location: SourceRange::new(start..lexer.range().end),
                 })
             })
@@ -316,17 +352,28 @@ pub fn parse_reference_access(lexer: &mut ParseSession) -> Result<Statement, Par
         name: lexer.slice_and_advance(),
         location,
     };
-    //If (while) we hit a dereference, parse and append the dereference to the result
-    while lexer.allow(&KeywordSquareParensOpen) {
-        let access = parse_primary_expression(lexer)?;
-        lexer.expect(KeywordSquareParensClose)?;
-        lexer.advance();
-        reference = Statement::ArrayAccess {
-            reference: Box::new(reference),
-            access: Box::new(access),
-        };
+    //If (while) we hit an array access or a dereference, parse and append it to the result
+    loop {
+        if lexer.allow(&KeywordSquareParensOpen) {
+            let access = parse_primary_expression(lexer)?;
+            lexer.expect(KeywordSquareParensClose)?;
+            lexer.advance();
+            reference = Statement::ArrayAccess {
+                reference: Box::new(reference),
+                access: Box::new(access),
+            };
+        } else if lexer.token == OperatorDeref {
+            let location =
+                SourceRange::new(reference.get_location().get_start()..lexer.range().end);
+            lexer.advance();
+            reference = Statement::PointerAccess {
+                reference: Box::new(reference),
+                location,
+            };
+        } else {
+            return Ok(reference);
+        }
     }
-    Ok(reference)
 }
 
 fn parse_literal_number(lexer: &mut ParseSession) -> Result<Statement, ParseError> {
@@ -361,7 +408,28 @@ fn parse_number<F: FromStr>(text: &str, location: &SourceRange) -> Result<F, Dia
     })
 }
 
-fn parse_date_from_string(text: &str, location: SourceRange) -> Result<Statement, ParseError> {
+/// returns true if the given literal's prefix denotes a long date and time type (e.g. LDT#)
+fn is_long_date_and_time_literal(literal: &str) -> bool {
+    literal.starts_with(|c: char| c.eq_ignore_ascii_case(&'L'))
+}
+
+/// parses the seconds of a time (e.g. `12.125`) into the seconds and the nanoseconds of the second
+fn parse_seconds(text: &str, location: &SourceRange) -> Result<(u32, u32), ParseError> {
+    let mut segments = text.split('.');
+    let sec = parse_number::<u32>(segments.next().unwrap(), location)?;
+    let nano = match segments.next() {
+        //only nanoseconds are relevant, pad the fraction to 9 digits
+        Some(fraction) => parse_number::<u32>(&format!("{:0<9.9}", fraction), location)?,
+        None => 0,
+    };
+    Ok((sec, nano))
+}
+
+fn parse_date_from_string(
+    text: &str,
+    is_long: bool,
+    location: SourceRange,
+) -> Result<Statement, ParseError> {
     let mut segments = text.split('-');
 
     //we can safely expect 3 numbers
@@ -382,6 +450,7 @@ fn parse_date_from_string(text: &str, location: SourceRange) -> Result<Statement
         year,
         month,
         day,
+        is_long,
         location,
     })
 }
@@ -390,6 +459,7 @@ fn parse_literal_date_and_time(lexer: &mut ParseSession) -> Result<Statement, Pa
     let location = lexer.location();
     //get rid of D# or DATE#
     let slice = lexer.slice_and_advance();
+    let is_long = is_long_date_and_time_literal(&slice);
     let hash_location = slice.find('#').unwrap_or_default();
     let last_minus_location = slice.rfind('-').unwrap();
 
@@ -406,10 +476,7 @@ fn parse_literal_date_and_time(lexer: &mut ParseSession) -> Result<Statement, Pa
     let mut segments = time.split(':');
     let hour = parse_number::<u32>(segments.next().unwrap(), &location)?;
     let min = parse_number::<u32>(segments.next().unwrap(), &location)?;
-    let sec_fraction = parse_number::<f64>(segments.next().unwrap(), &location)?;
-
-    let sec = sec_fraction as u32;
-    let milli = ((sec_fraction - sec as f64) * 1000_f64) as u32;
+    let (sec, nano) = parse_seconds(segments.next().unwrap(), &location)?;
 
     Ok(Statement::LiteralDateAndTime {
         location,
@@ -419,7 +486,8 @@ fn parse_literal_date_and_time(lexer: &mut ParseSession) -> Result<Statement, Pa
         hour,
         min,
         sec,
-        milli,
+        nano,
+        is_long,
     })
 }
 
@@ -427,30 +495,32 @@ fn parse_literal_date(lexer: &mut ParseSession) -> Result<Statement, ParseError>
     let location = lexer.location();
     //get rid of D# or DATE#
     let slice = lexer.slice_and_advance();
+    let is_long = is_long_date_and_time_literal(&slice);
     let hash_location = slice.find('#').unwrap_or_default();
     let (_, slice) = slice.split_at(hash_location + 1); //get rid of the prefix
 
-    parse_date_from_string(slice, location)
+    parse_date_from_string(slice, is_long, location)
 }
 
 fn parse_literal_time_of_day(lexer: &mut ParseSession) -> Result<Statement, ParseError> {
     let location = lexer.location();
     //get rid of TOD# or TIME_OF_DAY#
     let slice = lexer.slice_and_advance();
+    let is_long = is_long_date_and_time_literal(&slice);
     let hash_location = slice.find('#').unwrap_or_default();
     let (_, slice) = slice.split_at(hash_location + 1); //get rid of the prefix
 
     let mut segments = slice.split(':');
     let hour = parse_number::<u32>(segments.next().unwrap(), &location)?;
     let min = parse_number::<u32>(segments.next().unwrap(), &location)?;
+    let (sec, nano) = parse_seconds(segments.next().unwrap(), &location)?;
 
-    let sec = parse_number::<f64>(segments.next().unwrap(), &location)?;
-    let milli = (sec.fract() * 1000_f64) as u32;
     Ok(Statement::LiteralTimeOfDay {
         hour,
         min,
-        sec: sec.floor() as u32,
-        milli,
+        sec,
+        nano,
+        is_long,
         location,
     })
 }
@@ -466,6 +536,7 @@ fn parse_literal_time(lexer: &mut ParseSession) -> Result<Statement, ParseError>
     let location = lexer.location();
     //get rid of T# or TIME#
     let slice = lexer.slice_and_advance();
+    let is_long = is_long_date_and_time_literal(&slice);
     let (_, slice) = slice.split_at(slice.find('#').unwrap_or_default() + 1); //get rid of the prefix
 
     let mut chars = slice.char_indices();
@@ -509,7 +580,7 @@ fn parse_literal_time(lexer: &mut ParseSession) -> Result<Statement, ParseError>
         };
 
         //now assign the number to the according segment of the value's array
-        let position = match unit {
+        let position = match unit.to_lowercase().as_str() {
             "d" => Some(POS_D),
             "h" => Some(POS_H),
             "m" => Some(POS_M),
@@ -553,6 +624,7 @@ fn parse_literal_time(lexer: &mut ParseSession) -> Result<Statement, ParseError>
         micro: values[POS_US].unwrap_or_default(),
         nano: values[POS_NS].map(|it| it as u32).unwrap_or(0u32),
         negative: is_negative,
+        is_long,
         location,
     })
 }
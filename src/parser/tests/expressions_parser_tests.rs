@@ -45,6 +45,120 @@ fn qualified_reference_statement_parsed() {
     }
 }
 
+#[test]
+fn pointer_access_statements_parsed() {
+    let lexer = super::lex("PROGRAM exp p^; p^.x; p^[2]; END_PROGRAM");
+    let result = parse(lexer).unwrap().0;
+
+    let prg = &result.implementations[0];
+    assert_eq!(
+        prg.statements[0],
+        Statement::PointerAccess {
+            reference: Box::new(Statement::Reference {
+                name: "p".to_string(),
+                location: (12..13).into()
+            }),
+            location: (12..14).into()
+        }
+    );
+
+    let ast_string = format!("{:#?}", prg.statements[1]);
+    let expected_ast = r#"QualifiedReference {
+    elements: [
+        PointerAccess {
+            reference: Reference {
+                name: "p",
+            },
+        },
+        Reference {
+            name: "x",
+        },
+    ],
+}"#;
+    assert_eq!(ast_string, expected_ast);
+
+    let ast_string = format!("{:#?}", prg.statements[2]);
+    let expected_ast = r#"ArrayAccess {
+    reference: PointerAccess {
+        reference: Reference {
+            name: "p",
+        },
+    },
+    access: LiteralInteger {
+        value: "2",
+    },
+}"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
+#[test]
+fn ref_assignment_statements_parsed() {
+    let lexer = super::lex("PROGRAM exp r REF= x; s.r ref= 0; END_PROGRAM");
+    let result = parse(lexer).unwrap().0;
+
+    let prg = &result.implementations[0];
+    assert_eq!(
+        prg.statements[0],
+        Statement::RefAssignment {
+            left: Box::new(Statement::Reference {
+                name: "r".to_string(),
+                location: (12..13).into()
+            }),
+            right: Box::new(Statement::Reference {
+                name: "x".to_string(),
+                location: (19..20).into()
+            }),
+        }
+    );
+
+    let ast_string = format!("{:#?}", prg.statements[1]);
+    let expected_ast = r#"RefAssignment {
+    left: QualifiedReference {
+        elements: [
+            Reference {
+                name: "s",
+            },
+            Reference {
+                name: "r",
+            },
+        ],
+    },
+    right: LiteralInteger {
+        value: "0",
+    },
+}"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
+#[test]
+fn variables_named_ref_can_still_be_compared() {
+    let lexer = super::lex("PROGRAM exp ref=1; r REF= ref; END_PROGRAM");
+    let result = parse(lexer).unwrap().0;
+
+    let prg = &result.implementations[0];
+    let ast_string = format!("{:#?}", prg.statements);
+    let expected_ast = r#"[
+    BinaryExpression {
+        operator: Equal,
+        left: Reference {
+            name: "ref",
+        },
+        right: LiteralInteger {
+            value: "1",
+        },
+    },
+    RefAssignment {
+        left: Reference {
+            name: "r",
+        },
+        right: Reference {
+            name: "ref",
+        },
+    },
+]"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
 #[test]
 fn literal_can_be_parsed() {
     let lexer = super::lex("PROGRAM exp 7; END_PROGRAM");
@@ -637,11 +751,13 @@ fn literal_date_test() {
         year: 1984,
         month: 10,
         day: 1,
+        is_long: false,
     },
     LiteralDate {
         year: 2021,
         month: 4,
         day: 20,
+        is_long: false,
     },
 ]"#;
     assert_eq!(ast_string, expected_ast);
@@ -676,6 +792,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: false,
+        is_long: false,
     },
     LiteralTime {
         day: 12.4,
@@ -686,6 +803,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: false,
+        is_long: false,
     },
     LiteralTime {
         day: 0.0,
@@ -696,6 +814,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: true,
+        is_long: false,
     },
     LiteralTime {
         day: 0.0,
@@ -706,6 +825,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: false,
+        is_long: false,
     },
     LiteralTime {
         day: 0.0,
@@ -716,6 +836,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: false,
+        is_long: false,
     },
     LiteralTime {
         day: 12.0,
@@ -726,6 +847,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: false,
+        is_long: false,
     },
     LiteralTime {
         day: 0.0,
@@ -736,6 +858,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: true,
+        is_long: false,
     },
     LiteralTime {
         day: 0.0,
@@ -746,6 +869,7 @@ fn literal_time_test() {
         micro: 0.0,
         nano: 0,
         negative: false,
+        is_long: false,
     },
     LiteralTime {
         day: 4.0,
@@ -756,6 +880,7 @@ fn literal_time_test() {
         micro: 4.0,
         nano: 8,
         negative: false,
+        is_long: false,
     },
 ]"#;
     assert_eq!(ast_string, expected_ast);
@@ -782,37 +907,43 @@ fn literal_time_of_day_test() {
         hour: 12,
         min: 0,
         sec: 0,
-        milli: 0,
+        nano: 0,
+        is_long: false,
     },
     LiteralTimeOfDay {
         hour: 0,
         min: 12,
         sec: 0,
-        milli: 0,
+        nano: 0,
+        is_long: false,
     },
     LiteralTimeOfDay {
         hour: 0,
         min: 0,
         sec: 12,
-        milli: 0,
+        nano: 0,
+        is_long: false,
     },
     LiteralTimeOfDay {
         hour: 4,
         min: 16,
         sec: 22,
-        milli: 0,
+        nano: 0,
+        is_long: false,
     },
     LiteralTimeOfDay {
         hour: 4,
         min: 16,
         sec: 22,
-        milli: 100,
+        nano: 100000000,
+        is_long: false,
     },
     LiteralTimeOfDay {
         hour: 4,
         min: 16,
         sec: 22,
-        milli: 1,
+        nano: 1000000,
+        is_long: false,
     },
 ]"#;
     assert_eq!(ast_string, expected_ast);
@@ -839,7 +970,18 @@ fn literal_date_and_time_test() {
         hour: 16,
         min: 40,
         sec: 22,
-        milli: 0,
+        nano: 0,
+        is_long: false,
+    },
+    LiteralDateAndTime {
+        year: 2021,
+        month: 4,
+        day: 20,
+        hour: 22,
+        min: 33,
+        sec: 14,
+        nano: 0,
+        is_long: false,
     },
     LiteralDateAndTime {
         year: 2021,
@@ -848,7 +990,33 @@ fn literal_date_and_time_test() {
         hour: 22,
         min: 33,
         sec: 14,
-        milli: 0,
+        nano: 999000000,
+        is_long: false,
+    },
+]"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
+#[test]
+fn literal_long_date_and_time_test() {
+    let lexer = super::lex(
+        "
+        PROGRAM exp 
+            LDATE#1984-10-01; 
+            LDT#2021-04-20-22:33:14.123456789; 
+            LTOD#22:33:14.000000001; 
+            LTIME#1s5ns; 
+        END_PROGRAM
+        ",
+    );
+    let result = parse(lexer).unwrap().0;
+    let ast_string = format!("{:#?}", &result.implementations[0].statements);
+    let expected_ast = r#"[
+    LiteralDate {
+        year: 1984,
+        month: 10,
+        day: 1,
+        is_long: true,
     },
     LiteralDateAndTime {
         year: 2021,
@@ -857,7 +1025,26 @@ fn literal_date_and_time_test() {
         hour: 22,
         min: 33,
         sec: 14,
-        milli: 999,
+        nano: 123456789,
+        is_long: true,
+    },
+    LiteralTimeOfDay {
+        hour: 22,
+        min: 33,
+        sec: 14,
+        nano: 1,
+        is_long: true,
+    },
+    LiteralTime {
+        day: 0.0,
+        hour: 0.0,
+        min: 0.0,
+        sec: 1.0,
+        milli: 0.0,
+        micro: 0.0,
+        nano: 5,
+        negative: false,
+        is_long: true,
     },
 ]"#;
     assert_eq!(ast_string, expected_ast);
@@ -1863,6 +2050,56 @@ fn arrays_of_structs_can_be_parsed() {
     assert_eq!(ast_string, expected_ast);
 }
 
+#[test]
+fn function_call_with_a_single_struct_literal_argument() {
+    let lexer = super::lex(
+        "
+    PROGRAM prg
+    fn((x := 1, y := 2));
+    END_PROGRAM
+    ",
+    );
+    let parse_result = parse(lexer).unwrap().0;
+
+    let statement = &parse_result.implementations[0].statements[0];
+
+    let ast_string = format!("{:#?}", statement);
+
+    let expected_ast = r#"CallStatement {
+    operator: Reference {
+        name: "fn",
+    },
+    parameters: Some(
+        ExpressionList {
+            expressions: [
+                ExpressionList {
+                    expressions: [
+                        Assignment {
+                            left: Reference {
+                                name: "x",
+                            },
+                            right: LiteralInteger {
+                                value: "1",
+                            },
+                        },
+                        Assignment {
+                            left: Reference {
+                                name: "y",
+                            },
+                            right: LiteralInteger {
+                                value: "2",
+                            },
+                        },
+                    ],
+                },
+            ],
+        },
+    ),
+}"#;
+
+    assert_eq!(ast_string, expected_ast);
+}
+
 #[test]
 fn function_call_formal_params() {
     let lexer = super::lex(
@@ -122,6 +122,9 @@ fn varargs_parameters_can_be_parsed() {
         }),
         variable_blocks: vec![VariableBlock {
             variable_block_type: VariableBlockType::Input,
+            name: None,
+            qualified_only: false,
+            constant: false,
             variables: vec![
                 Variable {
                     name: "args1".into(),
@@ -1,7 +1,8 @@
 // Copyright (c) 2020 Ghaith Hachem and Mathias Rieder
 use crate::{
     ast::*,
-    parser::{parse, tests::lex},
+    parser::{parse, parse_attribute_pragma, tests::lex},
+    Diagnostic,
 };
 use pretty_assertions::*;
 
@@ -18,3 +19,155 @@ fn programs_can_be_external() {
     let implementation = &parse_result.implementations[0];
     assert_eq!(LinkageType::External, implementation.linkage);
 }
+
+#[test]
+fn external_functions_can_use_the_c_calling_convention() {
+    let lexer = lex("
+        {attribute 'calling_convention' := 'C'}
+        @EXTERNAL FUNCTION foo : INT END_FUNCTION
+        @EXTERNAL FUNCTION bar : INT END_FUNCTION
+        ");
+    let (parse_result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        CallingConvention::C,
+        parse_result.implementations[0].calling_convention
+    );
+    assert_eq!(
+        CallingConvention::Default,
+        parse_result.implementations[1].calling_convention
+    );
+}
+
+#[test]
+fn functions_can_use_the_c_calling_convention() {
+    let lexer = lex("{attribute 'calling_convention' := 'C'} FUNCTION foo : INT END_FUNCTION");
+    let (parse_result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        CallingConvention::C,
+        parse_result.implementations[0].calling_convention
+    );
+}
+
+#[test]
+fn unknown_calling_conventions_are_reported() {
+    let lexer =
+        lex("{attribute 'calling_convention' := 'stdcall'} FUNCTION foo : INT END_FUNCTION");
+    let (parse_result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::syntax_error(
+            "Unknown calling convention \"stdcall\"".into(),
+            SourceRange::new(0..45)
+        )]
+    );
+    assert_eq!(
+        CallingConvention::Default,
+        parse_result.implementations[0].calling_convention
+    );
+}
+
+#[test]
+fn calling_conventions_of_programs_and_function_blocks_are_reported() {
+    let lexer = lex("
+        {attribute 'calling_convention' := 'C'} PROGRAM prg END_PROGRAM
+        {attribute 'calling_convention' := 'C'} FUNCTION_BLOCK fb END_FUNCTION_BLOCK
+        ");
+    let (_, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::syntax_error(
+                "The calling_convention attribute can only be applied to functions".into(),
+                SourceRange::new(9..48)
+            ),
+            Diagnostic::syntax_error(
+                "The calling_convention attribute can only be applied to functions".into(),
+                SourceRange::new(81..120)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unknown_pragmas_are_ignored() {
+    let lexer = lex("{attribute 'hide'} {info 'x'} @EXTERNAL FUNCTION foo : INT END_FUNCTION");
+    let (parse_result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(
+        CallingConvention::Default,
+        parse_result.implementations[0].calling_convention
+    );
+}
+
+#[test]
+fn attribute_pragmas_are_split_into_name_and_value() {
+    assert_eq!(
+        Some(("pack_mode".to_string(), Some("1".to_string()))),
+        parse_attribute_pragma("{attribute 'pack_mode' := '1'}")
+    );
+    assert_eq!(
+        Some(("qualified_only".to_string(), None)),
+        parse_attribute_pragma("{attribute 'qualified_only'}")
+    );
+    assert_eq!(None, parse_attribute_pragma("{IF defined(X)}"));
+}
+
+#[test]
+fn structs_can_be_packed_with_a_pack_mode_attribute() {
+    let lexer = lex("
+        {attribute 'pack_mode' := '1'}
+        TYPE Packed : STRUCT a : BYTE; b : DINT; END_STRUCT END_TYPE
+        TYPE Natural : STRUCT a : BYTE; b : DINT; END_STRUCT END_TYPE
+        ");
+    let (parse_result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(diagnostics, vec![]);
+    assert!(matches!(
+        parse_result.types[0].data_type,
+        DataType::StructType {
+            pack_mode: Some(1),
+            ..
+        }
+    ));
+    assert!(matches!(
+        parse_result.types[1].data_type,
+        DataType::StructType {
+            pack_mode: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn unsupported_pack_modes_are_reported() {
+    let lexer = lex("{attribute 'pack_mode' := '3'} TYPE S : STRUCT a : BYTE; END_STRUCT END_TYPE");
+    let (parse_result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::syntax_error(
+            "Unsupported pack mode \"3\"".into(),
+            SourceRange::new(0..30)
+        )]
+    );
+    assert!(matches!(
+        parse_result.types[0].data_type,
+        DataType::StructType {
+            pack_mode: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn pack_modes_of_types_other_than_structs_are_reported() {
+    let lexer = lex("{attribute 'pack_mode' := '1'} TYPE MyInt : INT := 7; END_TYPE");
+    let (_, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::syntax_error(
+            "The pack_mode attribute can only be applied to structs".into(),
+            SourceRange::new(0..30)
+        )]
+    );
+}
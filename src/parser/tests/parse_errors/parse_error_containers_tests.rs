@@ -240,6 +240,9 @@ fn unclosed_var_container() {
             "{:#?}",
             VariableBlock {
                 variable_block_type: VariableBlockType::Local,
+                name: None,
+                qualified_only: false,
+                constant: false,
                 variables: vec![Variable {
                     name: "a".into(),
                     data_type: crate::ast::DataTypeDeclaration::DataTypeReference {
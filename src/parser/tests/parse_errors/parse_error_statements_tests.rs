@@ -268,6 +268,9 @@ fn invalid_variable_name_error_recovery() {
                     location: SourceRange::undefined(),
                 },],
                 variable_block_type: VariableBlockType::Local,
+                name: None,
+                qualified_only: false,
+                constant: false,
             }
         )
     );
@@ -50,6 +50,7 @@ fn simple_struct_type_can_be_parsed() {
                         location: SourceRange::undefined(),
                     },
                 ),
+                pack_mode: None,
             },
             initializer: None,
         }
@@ -138,6 +139,56 @@ fn array_type_can_be_parsed_test() {
     assert_eq!(ast_string, expected_ast);
 }
 
+#[test]
+fn variable_length_array_type_can_be_parsed_test() {
+    let (result, ..) = parse(lex(r#"
+            TYPE MyArray : ARRAY[*] OF INT; END_TYPE
+            TYPE MyMatrix : ARRAY[*, *] OF REAL; END_TYPE
+            "#))
+    .unwrap();
+
+    let ast_string = format!("{:#?}", &result.types);
+
+    let expected_ast = format!(
+        "{:#?}",
+        vec![
+            UserTypeDeclaration {
+                data_type: DataType::ArrayType {
+                    name: Some("MyArray".to_string()),
+                    bounds: Statement::EmptyStatement {
+                        location: SourceRange::undefined(),
+                    },
+                    referenced_type: Box::new(DataTypeDeclaration::DataTypeReference {
+                        referenced_type: "INT".to_string(),
+                    }),
+                },
+                initializer: None,
+            },
+            UserTypeDeclaration {
+                data_type: DataType::ArrayType {
+                    name: Some("MyMatrix".to_string()),
+                    bounds: Statement::ExpressionList {
+                        expressions: vec![
+                            Statement::EmptyStatement {
+                                location: SourceRange::undefined(),
+                            },
+                            Statement::EmptyStatement {
+                                location: SourceRange::undefined(),
+                            },
+                        ],
+                    },
+                    referenced_type: Box::new(DataTypeDeclaration::DataTypeReference {
+                        referenced_type: "REAL".to_string(),
+                    }),
+                },
+                initializer: None,
+            },
+        ]
+    );
+
+    assert_eq!(ast_string, expected_ast);
+}
+
 #[test]
 fn string_type_can_be_parsed_test() {
     let (result, ..) = parse(lex(r#"
@@ -244,6 +295,58 @@ fn subrangetype_can_be_parsed() {
     assert_eq!(format!("{:#?}", expected), format!("{:#?}", x).as_str());
 }
 
+#[test]
+fn union_type_can_be_parsed() {
+    let (result, ..) = parse(lex(r#"
+        TYPE SampleUnion :
+            UNION
+                One: DWORD;
+                Two: ARRAY[0..3] OF BYTE;
+            END_UNION
+        END_TYPE
+        "#))
+    .unwrap();
+
+    let ast_string = format!("{:#?}", &result.types[0]);
+
+    let expected_ast = r#"UserTypeDeclaration {
+    data_type: UnionType {
+        name: Some(
+            "SampleUnion",
+        ),
+        variables: [
+            Variable {
+                name: "One",
+                data_type: DataTypeReference {
+                    referenced_type: "DWORD",
+                },
+            },
+            Variable {
+                name: "Two",
+                data_type: DataTypeDefinition {
+                    data_type: ArrayType {
+                        name: None,
+                        bounds: RangeStatement {
+                            start: LiteralInteger {
+                                value: "0",
+                            },
+                            end: LiteralInteger {
+                                value: "3",
+                            },
+                        },
+                        referenced_type: DataTypeReference {
+                            referenced_type: "BYTE",
+                        },
+                    },
+                },
+            },
+        ],
+    },
+    initializer: None,
+}"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
 #[test]
 fn struct_with_inline_array_can_be_parsed() {
     let (result, ..) = parse(lex(r#"
@@ -288,3 +391,68 @@ fn struct_with_inline_array_can_be_parsed() {
 }"#;
     assert_eq!(ast_string, expected_ast);
 }
+
+#[test]
+fn pointer_type_can_be_parsed() {
+    let (result, ..) = parse(lex(r#"
+        TYPE NodePtr : POINTER TO Node; END_TYPE
+        TYPE IntPtrPtr : POINTER TO POINTER TO INT; END_TYPE
+        "#))
+    .unwrap();
+
+    let ast_string = format!("{:#?}", &result.types[0]);
+    let expected_ast = r#"UserTypeDeclaration {
+    data_type: PointerType {
+        name: Some(
+            "NodePtr",
+        ),
+        referenced_type: DataTypeReference {
+            referenced_type: "Node",
+        },
+    },
+    initializer: None,
+}"#;
+    assert_eq!(ast_string, expected_ast);
+
+    let ast_string = format!("{:#?}", &result.types[1]);
+    let expected_ast = r#"UserTypeDeclaration {
+    data_type: PointerType {
+        name: Some(
+            "IntPtrPtr",
+        ),
+        referenced_type: DataTypeDefinition {
+            data_type: PointerType {
+                name: None,
+                referenced_type: DataTypeReference {
+                    referenced_type: "INT",
+                },
+            },
+        },
+    },
+    initializer: None,
+}"#;
+    assert_eq!(ast_string, expected_ast);
+}
+
+#[test]
+fn reference_type_can_be_parsed() {
+    let (result, ..) = parse(lex(r#"
+        TYPE NodeRef : REFERENCE TO Node; END_TYPE
+        "#))
+    .unwrap();
+
+    let ast_string = format!("{:#?}", &result.types[0]);
+    let expected_ast = r#"UserTypeDeclaration {
+    data_type: PointerType {
+        name: Some(
+            "NodeRef",
+        ),
+        referenced_type: DataTypeReference {
+            referenced_type: "Node",
+        },
+        auto_deref: true,
+    },
+    initializer: None,
+}"#;
+    assert_eq!(ast_string, expected_ast);
+}
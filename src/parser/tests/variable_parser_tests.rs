@@ -80,3 +80,56 @@ fn two_global_vars_can_be_parsed() {
 ]"#;
     assert_eq!(ast_string, expected_ast)
 }
+
+#[test]
+fn named_global_variable_lists_can_be_parsed() {
+    let lexer = lex("
+        VAR_GLOBAL Gvl_Io x : INT; y : BOOL; END_VAR
+        VAR_GLOBAL Gvl_Hmi {attribute 'qualified_only'} x : INT; END_VAR
+        VAR_GLOBAL {attribute 'qualified_only'} z : INT; END_VAR
+        VAR_GLOBAL a : INT; END_VAR
+        ");
+    let (result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(diagnostics, vec![]);
+
+    let headers: Vec<_> = result
+        .global_vars
+        .iter()
+        .map(|it| (it.name.as_deref(), it.qualified_only, it.variables.len()))
+        .collect();
+    assert_eq!(
+        headers,
+        vec![
+            (Some("Gvl_Io"), false, 2),
+            (Some("Gvl_Hmi"), true, 1),
+            (None, true, 1),
+            (None, false, 1),
+        ]
+    );
+    assert_eq!("x", result.global_vars[0].variables[0].name);
+    assert_eq!("a", result.global_vars[3].variables[0].name);
+}
+
+#[test]
+fn constant_inputs_can_be_parsed() {
+    let lexer = lex("
+        FUNCTION_BLOCK fb
+        VAR_INPUT CONSTANT a : INT; END_VAR
+        VAR_INPUT {attribute 'by_ref'} b : INT; END_VAR
+        VAR_INPUT c : INT; END_VAR
+        VAR_OUTPUT d : INT; END_VAR
+        END_FUNCTION_BLOCK
+        ");
+    let (result, diagnostics) = parse(lexer).unwrap();
+    assert_eq!(diagnostics, vec![]);
+
+    let blocks: Vec<_> = result.units[0]
+        .variable_blocks
+        .iter()
+        .map(|it| (it.variables[0].name.as_str(), it.constant))
+        .collect();
+    assert_eq!(
+        blocks,
+        vec![("a", true), ("b", true), ("c", false), ("d", false)]
+    );
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+
+/// The standard library bundled with the compiler
+/// # responsibilities
+/// - provides the IEC 61131-3 standard function blocks (timers, edge-detection, counters, bistables)
+///   and date and time functions as ST-sources that are compiled together with the user's sources
+/// - links only those POUs of the library that are used by the compiled sources, POUs declared by the
+///   compiled sources take precedence over the library's POUs with the same name
+use std::collections::HashSet;
+
+use crate::{
+    ast::{CompilationUnit, ConditionalBlock, Statement},
+    index::Index,
+    typesystem::DataTypeInformation,
+};
+
+/// the sources of the standard function blocks
+pub const STANDARD_FUNCTION_BLOCKS: &str = include_str!("stdlib/standard_function_blocks.st");
+
+/// the sources of the date and time functions
+pub const DATE_AND_TIME_FUNCTIONS: &str = include_str!("stdlib/date_and_time_functions.st");
+
+/// all sources of the standard library
+pub const LIBRARY_SOURCES: [&str; 2] = [STANDARD_FUNCTION_BLOCKS, DATE_AND_TIME_FUNCTIONS];
+
+/// removes all POUs from the library that are not (transitively) referenced by the given unit
+/// or that are already declared by it.
+///
+/// # Arguments
+/// * `library` - the parsed library
+/// * `unit` - the compilation unit of the compiled sources
+/// * `index` - the index of the compiled sources
+pub fn link_library(
+    mut library: CompilationUnit,
+    unit: &CompilationUnit,
+    index: &Index,
+) -> CompilationUnit {
+    let library_index = crate::index::visitor::visit(&library);
+
+    //names are compared in lowercase, identifiers are case-insensitive
+    let declared_names: HashSet<String> = unit
+        .units
+        .iter()
+        .map(|it| it.name.as_str())
+        .chain(unit.types.iter().filter_map(|it| it.data_type.get_name()))
+        .map(str::to_lowercase)
+        .collect();
+
+    //collect everything referenced by the compiled sources
+    let mut referenced_names = HashSet::new();
+    collect_referenced_types(index, &mut referenced_names);
+    for implementation in &unit.implementations {
+        collect_references(&implementation.statements, &mut referenced_names);
+    }
+
+    //add the library's POUs until no new references show up
+    let mut linked_names: HashSet<String> = HashSet::new();
+    loop {
+        let newly_linked: Vec<String> = library
+            .units
+            .iter()
+            .map(|it| it.name.to_lowercase())
+            .filter(|it| {
+                referenced_names.contains(it)
+                    && !linked_names.contains(it)
+                    && !declared_names.contains(it)
+            })
+            .collect();
+        if newly_linked.is_empty() {
+            break;
+        }
+
+        for name in newly_linked {
+            for member in library_index.find_local_members(&name) {
+                referenced_names.insert(member.get_type_name().to_lowercase());
+            }
+            library
+                .implementations
+                .iter()
+                .filter(|it| it.type_name.eq_ignore_ascii_case(&name))
+                .for_each(|it| collect_references(&it.statements, &mut referenced_names));
+            linked_names.insert(name);
+        }
+    }
+
+    library
+        .units
+        .retain(|it| linked_names.contains(&it.name.to_lowercase()));
+    library
+        .implementations
+        .retain(|it| linked_names.contains(&it.type_name.to_lowercase()));
+    library
+}
+
+/// collects the lowercase names of all types used by the variables, struct- and union-members and arrays in the given index
+fn collect_referenced_types(index: &Index, names: &mut HashSet<String>) {
+    for variable in index.get_globals().values() {
+        names.insert(variable.get_type_name().to_lowercase());
+    }
+    for (type_name, data_type) in index.get_types() {
+        match data_type.get_type_information() {
+            DataTypeInformation::Struct { .. } | DataTypeInformation::Union { .. } => {
+                for member in index.find_local_members(type_name) {
+                    names.insert(member.get_type_name().to_lowercase());
+                }
+            }
+            DataTypeInformation::Array {
+                inner_type_name, ..
+            }
+            | DataTypeInformation::Pointer {
+                inner_type_name, ..
+            }
+            | DataTypeInformation::VariableLengthArray {
+                inner_type_name, ..
+            } => {
+                names.insert(inner_type_name.to_lowercase());
+            }
+            DataTypeInformation::Alias {
+                referenced_type, ..
+            }
+            | DataTypeInformation::SubRange {
+                referenced_type, ..
+            } => {
+                names.insert(referenced_type.to_lowercase());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// collects the lowercase names of all references (variables and called POUs) in the given statements
+fn collect_references(statements: &[Statement], names: &mut HashSet<String>) {
+    for statement in statements {
+        collect_references_of(statement, names);
+    }
+}
+
+fn collect_references_of(statement: &Statement, names: &mut HashSet<String>) {
+    match statement {
+        Statement::Reference { name, .. } => {
+            names.insert(name.to_lowercase());
+        }
+        Statement::QualifiedReference { elements } => collect_references(elements, names),
+        Statement::ExpressionList { expressions } => collect_references(expressions, names),
+        Statement::ArrayAccess { reference, access } => {
+            collect_references_of(reference, names);
+            collect_references_of(access, names);
+        }
+        Statement::BinaryExpression { left, right, .. }
+        | Statement::Assignment { left, right }
+        | Statement::OutputAssignment { left, right }
+        | Statement::RefAssignment { left, right } => {
+            collect_references_of(left, names);
+            collect_references_of(right, names);
+        }
+        Statement::RangeStatement { start, end } => {
+            collect_references_of(start, names);
+            collect_references_of(end, names);
+        }
+        Statement::UnaryExpression { value, .. }
+        | Statement::PointerAccess {
+            reference: value, ..
+        } => collect_references_of(value, names),
+        Statement::MultipliedStatement { element, .. } => collect_references_of(element, names),
+        Statement::LiteralArray {
+            elements: Some(elements),
+            ..
+        } => collect_references_of(elements, names),
+        Statement::CaseCondition { condition } => collect_references_of(condition, names),
+        Statement::CallStatement {
+            operator,
+            parameters,
+            ..
+        } => {
+            collect_references_of(operator, names);
+            if let Some(parameters) = parameters.as_ref() {
+                collect_references_of(parameters, names);
+            }
+        }
+        Statement::IfStatement {
+            blocks, else_block, ..
+        } => {
+            collect_conditional_blocks(blocks, names);
+            collect_references(else_block, names);
+        }
+        Statement::CaseStatement {
+            selector,
+            case_blocks,
+            else_block,
+            ..
+        } => {
+            collect_references_of(selector, names);
+            collect_conditional_blocks(case_blocks, names);
+            collect_references(else_block, names);
+        }
+        Statement::ForLoopStatement {
+            counter,
+            start,
+            end,
+            by_step,
+            body,
+            ..
+        } => {
+            collect_references_of(counter, names);
+            collect_references_of(start, names);
+            collect_references_of(end, names);
+            if let Some(by_step) = by_step {
+                collect_references_of(by_step, names);
+            }
+            collect_references(body, names);
+        }
+        Statement::WhileLoopStatement {
+            condition, body, ..
+        }
+        | Statement::RepeatLoopStatement {
+            condition, body, ..
+        } => {
+            collect_references_of(condition, names);
+            collect_references(body, names);
+        }
+        _ => {}
+    }
+}
+
+fn collect_conditional_blocks(blocks: &[ConditionalBlock], names: &mut HashSet<String>) {
+    for block in blocks {
+        collect_references_of(&block.condition, names);
+        collect_references(&block.body, names);
+    }
+}
+
+#[cfg(test)]
+mod tests;
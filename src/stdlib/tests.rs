@@ -0,0 +1,135 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use crate::{ast::CompilationUnit, index::visitor::visit, lexer::lex, parser::parse};
+
+use super::{link_library, DATE_AND_TIME_FUNCTIONS, LIBRARY_SOURCES, STANDARD_FUNCTION_BLOCKS};
+
+fn parse_and_preprocess(source: &str) -> CompilationUnit {
+    let (mut unit, diagnostics) = parse(lex(source)).unwrap();
+    assert_eq!(Vec::<crate::Diagnostic>::new(), diagnostics);
+    crate::ast::pre_process(&mut unit);
+    unit
+}
+
+fn get_linked_pous(source: &str) -> Vec<String> {
+    let unit = parse_and_preprocess(source);
+    let index = visit(&unit);
+    let mut library = CompilationUnit::default();
+    for library_source in LIBRARY_SOURCES.iter() {
+        library.import(parse_and_preprocess(library_source));
+    }
+    let library = link_library(library, &unit, &index);
+    library.units.iter().map(|it| it.name.clone()).collect()
+}
+
+#[test]
+fn standard_function_blocks_can_be_parsed() {
+    let unit = parse_and_preprocess(STANDARD_FUNCTION_BLOCKS);
+    let names: Vec<&str> = unit.units.iter().map(|it| it.name.as_str()).collect();
+    assert_eq!(
+        vec![
+            "__get_time_ns",
+            "TON",
+            "TOF",
+            "TP",
+            "R_TRIG",
+            "F_TRIG",
+            "CTU",
+            "CTD",
+            "CTUD",
+            "SR",
+            "RS"
+        ],
+        names
+    );
+}
+
+#[test]
+fn date_and_time_functions_can_be_parsed() {
+    let unit = parse_and_preprocess(DATE_AND_TIME_FUNCTIONS);
+    let names: Vec<&str> = unit.units.iter().map(|it| it.name.as_str()).collect();
+    assert_eq!(
+        vec![
+            "ADD_TOD_TIME",
+            "SUB_DT_DT",
+            "CONCAT_DATE_TOD",
+            "SPLIT_DT",
+            "TIME_TO_LTIME",
+            "LTIME_TO_TIME",
+            "DATE_TO_LDATE",
+            "LDATE_TO_DATE",
+            "TOD_TO_LTOD",
+            "LTOD_TO_TOD",
+            "DT_TO_LDT",
+            "LDT_TO_DT"
+        ],
+        names
+    );
+}
+
+#[test]
+fn unused_library_pous_are_not_linked() {
+    let linked = get_linked_pous(
+        "
+        PROGRAM main
+        VAR
+            x : INT;
+        END_VAR
+            x := x + 1;
+        END_PROGRAM
+        ",
+    );
+    assert_eq!(Vec::<String>::new(), linked);
+}
+
+#[test]
+fn instantiated_function_blocks_and_their_dependencies_are_linked() {
+    let linked = get_linked_pous(
+        "
+        PROGRAM main
+        VAR
+            trigger : R_TRIG;
+            timers : ARRAY[0..2] OF TON;
+        END_VAR
+        END_PROGRAM
+        ",
+    );
+    assert_eq!(vec!["__get_time_ns", "TON", "R_TRIG"], linked);
+}
+
+#[test]
+fn function_blocks_declared_by_the_sources_take_precedence() {
+    let linked = get_linked_pous(
+        "
+        FUNCTION_BLOCK TON
+        END_FUNCTION_BLOCK
+
+        FUNCTION __get_time_ns : TIME
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            on_delay : TON;
+            pulse : TP;
+        END_VAR
+            on_delay();
+        END_PROGRAM
+        ",
+    );
+    assert_eq!(vec!["TP"], linked);
+}
+
+#[test]
+fn called_library_functions_are_linked() {
+    let linked = get_linked_pous(
+        "
+        PROGRAM main
+        VAR
+            now : DATE_AND_TIME;
+            year : DINT;
+        END_VAR
+            SPLIT_DT(IN1 := now, YEAR => year);
+        END_PROGRAM
+        ",
+    );
+    assert_eq!(vec!["SPLIT_DT"], linked);
+}
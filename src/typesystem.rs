@@ -11,6 +11,30 @@ pub const RANGE_CHECK_LS_FN: &str = "CheckLRangeSigned";
 pub const RANGE_CHECK_U_FN: &str = "CheckRangeUnsigned";
 pub const RANGE_CHECK_LU_FN: &str = "CheckLRangeUnsigned";
 
+//CheckBounds is called for every array access if declared by the user
+pub const BOUNDS_CHECK_FN: &str = "CheckBounds";
+
+//CheckDivDInt and CheckDivReal are called with the divisor of every division and modulo if declared by the user,
+//CheckDivLInt and CheckDivLReal with the divisors wider than 32 bits
+pub const DIV_CHECK_INT_FN: &str = "CheckDivDInt";
+pub const DIV_CHECK_LINT_FN: &str = "CheckDivLInt";
+pub const DIV_CHECK_REAL_FN: &str = "CheckDivReal";
+pub const DIV_CHECK_LREAL_FN: &str = "CheckDivLReal";
+
+//__NEW and __DELETE allocate and release memory through these C functions, if they are not declared by the user
+//the compiler generates weak defaults calling malloc and free
+pub const ALLOC_HOOK_FN: &str = "__rusty_alloc";
+pub const FREE_HOOK_FN: &str = "__rusty_free";
+
+//the generic type classes that can be used as the types of a FUNCTION's variables
+pub const GENERIC_TYPE_CLASSES: [&str; 5] =
+    ["ANY_NUM", "ANY_INT", "ANY_REAL", "ANY_BIT", "ANY_STRING"];
+const ANY_INT_TYPES: [&str; 8] = [
+    "SINT", "INT", "DINT", "LINT", "USINT", "UINT", "UDINT", "ULINT",
+];
+const ANY_REAL_TYPES: [&str; 2] = ["REAL", "LREAL"];
+const ANY_BIT_TYPES: [&str; 5] = ["BOOL", "BYTE", "WORD", "DWORD", "LWORD"];
+
 #[derive(Debug, PartialEq)]
 pub struct DataType {
     pub name: String,
@@ -51,12 +75,97 @@ impl StringEncoding {
     }
 }
 
+/// the IEC date and time types. All of them are stored as 64 bit integers:
+/// - TIME and LTIME in nanoseconds
+/// - DATE, TIME_OF_DAY and DATE_AND_TIME in milliseconds since 1970-01-01-00:00:00
+/// - LDATE, LTIME_OF_DAY and LDATE_AND_TIME in nanoseconds since 1970-01-01-00:00:00
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateAndTimeType {
+    Time,
+    LTime,
+    Date,
+    LDate,
+    TimeOfDay,
+    LTimeOfDay,
+    DateAndTime,
+    LDateAndTime,
+}
+
+impl DateAndTimeType {
+    /// returns the name of the type
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            DateAndTimeType::Time => "TIME",
+            DateAndTimeType::LTime => "LTIME",
+            DateAndTimeType::Date => "DATE",
+            DateAndTimeType::LDate => "LDATE",
+            DateAndTimeType::TimeOfDay => "TIME_OF_DAY",
+            DateAndTimeType::LTimeOfDay => "LTIME_OF_DAY",
+            DateAndTimeType::DateAndTime => "DATE_AND_TIME",
+            DateAndTimeType::LDateAndTime => "LDATE_AND_TIME",
+        }
+    }
+
+    /// returns true for the long types (LTIME, LDATE, LTIME_OF_DAY and LDATE_AND_TIME)
+    pub fn is_long(&self) -> bool {
+        matches!(
+            self,
+            DateAndTimeType::LTime
+                | DateAndTimeType::LDate
+                | DateAndTimeType::LTimeOfDay
+                | DateAndTimeType::LDateAndTime
+        )
+    }
+
+    /// returns true for the durations TIME and LTIME
+    pub fn is_duration(&self) -> bool {
+        matches!(self, DateAndTimeType::Time | DateAndTimeType::LTime)
+    }
+
+    /// returns the duration used for differences of this type (TIME or LTIME)
+    pub fn get_duration(&self) -> DateAndTimeType {
+        if self.is_long() {
+            DateAndTimeType::LTime
+        } else {
+            DateAndTimeType::Time
+        }
+    }
+
+    /// returns the short form of this type (e.g. DATE for LDATE)
+    pub fn get_short_form(&self) -> DateAndTimeType {
+        match self {
+            DateAndTimeType::LTime => DateAndTimeType::Time,
+            DateAndTimeType::LDate => DateAndTimeType::Date,
+            DateAndTimeType::LTimeOfDay => DateAndTimeType::TimeOfDay,
+            DateAndTimeType::LDateAndTime => DateAndTimeType::DateAndTime,
+            _ => *self,
+        }
+    }
+
+    /// returns the nanoseconds represented by one unit of this type's values
+    pub fn get_resolution(&self) -> u64 {
+        match self {
+            DateAndTimeType::Date | DateAndTimeType::TimeOfDay | DateAndTimeType::DateAndTime => {
+                1_000_000
+            }
+            _ => 1,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DataTypeInformation {
     Struct {
         name: String,
         member_names: Vec<String>,
         varargs: Option<VarArgs>,
+        /// the maximum alignment of the members in bytes, None for the natural alignment
+        pack_mode: Option<u32>,
+    },
+    /// a UNION, all of its members are stored at the same address
+    Union {
+        name: String,
+        member_names: Vec<String>,
     },
     Array {
         name: String,
@@ -68,6 +177,13 @@ pub enum DataTypeInformation {
         inner_type_name: String,
         auto_deref: bool,
     },
+    /// an ARRAY[*] that can only be passed as a VAR_IN_OUT. it is represented as a fat pointer
+    /// holding a pointer to the first element and the lower and upper bound of every dimension
+    VariableLengthArray {
+        name: String,
+        inner_type_name: String,
+        dimensions: u32,
+    },
     Integer {
         name: String,
         signed: bool,
@@ -97,8 +213,10 @@ impl DataTypeInformation {
     pub fn get_name(&self) -> &str {
         match self {
             DataTypeInformation::Struct { name, .. } => name,
+            DataTypeInformation::Union { name, .. } => name,
             DataTypeInformation::Array { name, .. } => name,
             DataTypeInformation::Pointer { name, .. } => name,
+            DataTypeInformation::VariableLengthArray { name, .. } => name,
             DataTypeInformation::Integer { name, .. } => name,
             DataTypeInformation::Float { name, .. } => name,
             DataTypeInformation::String { .. } => "String",
@@ -123,6 +241,30 @@ impl DataTypeInformation {
         )
     }
 
+    /// returns the date and time type this integer represents or None if it
+    /// is no date and time type
+    pub fn get_date_and_time_type(&self) -> Option<DateAndTimeType> {
+        if let DataTypeInformation::Integer { name, .. } = self {
+            match name.as_str() {
+                "TIME" => Some(DateAndTimeType::Time),
+                "LTIME" => Some(DateAndTimeType::LTime),
+                "DATE" => Some(DateAndTimeType::Date),
+                "LDATE" => Some(DateAndTimeType::LDate),
+                "TIME_OF_DAY" => Some(DateAndTimeType::TimeOfDay),
+                "LTIME_OF_DAY" => Some(DateAndTimeType::LTimeOfDay),
+                "DATE_AND_TIME" => Some(DateAndTimeType::DateAndTime),
+                "LDATE_AND_TIME" => Some(DateAndTimeType::LDateAndTime),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    pub fn is_date_or_time(&self) -> bool {
+        self.get_date_and_time_type().is_some()
+    }
+
     pub fn is_variadic(&self) -> bool {
         matches!(
             self,
@@ -151,8 +293,12 @@ impl DataTypeInformation {
             DataTypeInformation::Float { size, .. } => *size,
             DataTypeInformation::String { size, .. } => *size,
             DataTypeInformation::Struct { .. } => 0, //TODO : Should we fill in the struct members here for size calculation or save the struct size.
+            DataTypeInformation::Union { .. } => 0,
             DataTypeInformation::Array { .. } => unimplemented!(), //Propably length * inner type size
             DataTypeInformation::Pointer { .. } => unimplemented!(),
+            //the size of the fat pointer depends on the target's pointer size, like the size of a struct
+            //it is only known to the codegen (the target data's abi-size of the fat pointer's llvm type)
+            DataTypeInformation::VariableLengthArray { .. } => 0,
             DataTypeInformation::SubRange { .. } => unimplemented!(),
             DataTypeInformation::Alias { .. } => unimplemented!(),
             DataTypeInformation::Void => 0,
@@ -176,6 +322,15 @@ pub fn get_builtin_types() -> Vec<DataType> {
                 size: 1,
             },
         },
+        DataType {
+            name: "BIT".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "BIT".into(),
+                signed: false,
+                size: 1,
+            },
+        },
         DataType {
             name: "BYTE".into(),
             initial_value: None,
@@ -311,6 +466,78 @@ pub fn get_builtin_types() -> Vec<DataType> {
                 size: 64,
             },
         },
+        DataType {
+            name: "DT".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "DATE_AND_TIME".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "TOD".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "TIME_OF_DAY".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "LTIME".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "LTIME".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "LDATE".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "LDATE".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "LTIME_OF_DAY".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "LTIME_OF_DAY".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "LTOD".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "LTIME_OF_DAY".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "LDATE_AND_TIME".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "LDATE_AND_TIME".into(),
+                signed: true,
+                size: 64,
+            },
+        },
+        DataType {
+            name: "LDT".into(),
+            initial_value: None,
+            information: DataTypeInformation::Integer {
+                name: "LDATE_AND_TIME".into(),
+                signed: true,
+                size: 64,
+            },
+        },
         DataType {
             name: "ULINT".into(),
             initial_value: None,
@@ -355,6 +582,27 @@ pub fn get_builtin_types() -> Vec<DataType> {
     ]
 }
 
+/// returns the generic type class with the given (case-insensitive) name or None if there is no such type class
+pub fn find_type_class(name: &str) -> Option<&'static str> {
+    GENERIC_TYPE_CLASSES
+        .iter()
+        .find(|it| it.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// returns true if the given (effective) type belongs to the given generic type class
+pub fn is_in_type_class(type_class: &str, data_type: &DataTypeInformation) -> bool {
+    let name = data_type.get_name();
+    match type_class {
+        "ANY_NUM" => ANY_INT_TYPES.contains(&name) || ANY_REAL_TYPES.contains(&name),
+        "ANY_INT" => ANY_INT_TYPES.contains(&name),
+        "ANY_REAL" => ANY_REAL_TYPES.contains(&name),
+        "ANY_BIT" => ANY_BIT_TYPES.contains(&name),
+        "ANY_STRING" => matches!(data_type, DataTypeInformation::String { .. }),
+        _ => false,
+    }
+}
+
 pub fn new_string_information(len: u32) -> DataTypeInformation {
     DataTypeInformation::String {
         size: len + 1,
@@ -402,6 +650,21 @@ fn get_lreal_type() -> DataTypeInformation {
     }
 }
 
+/// returns true if the given integer literal can be represented by the given int-type
+pub fn fits_into_int_type(value: &str, data_type: &DataTypeInformation) -> bool {
+    match (value.parse::<i128>(), data_type) {
+        (Ok(value), DataTypeInformation::Integer { signed, size, .. }) if *size < 128 => {
+            let (min, max) = if *signed {
+                (-(1_i128 << (size - 1)), (1_i128 << (size - 1)) - 1)
+            } else {
+                (0, (1_i128 << size) - 1)
+            };
+            min <= value && value <= max
+        }
+        _ => false,
+    }
+}
+
 pub fn get_bigger_type(
     ltype: &DataTypeInformation,
     rtype: &DataTypeInformation,
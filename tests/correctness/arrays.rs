@@ -177,3 +177,212 @@ fn cube_array_assignments2() {
         }
     }
 }
+
+#[test]
+fn variable_length_arrays_can_be_passed_to_functions() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        small: [i32; 3],
+        big: [i32; 7],
+        matrix: [[i32; 3]; 2],
+        small_count: i32,
+        big_count: i32,
+        total: i32,
+    }
+
+    let function = r"
+        FUNCTION sort : DINT
+        VAR_IN_OUT
+            values : ARRAY[*] OF DINT;
+        END_VAR
+        VAR
+            i : DINT;
+            j : DINT;
+            tmp : DINT;
+        END_VAR
+            FOR i := LOWER_BOUND(values, 1) TO UPPER_BOUND(values, 1) - 1 DO
+                FOR j := LOWER_BOUND(values, 1) TO UPPER_BOUND(values, 1) - 1 - (i - LOWER_BOUND(values, 1)) DO
+                    IF values[j] > values[j + 1] THEN
+                        tmp := values[j];
+                        values[j] := values[j + 1];
+                        values[j + 1] := tmp;
+                    END_IF
+                END_FOR
+            END_FOR
+            sort := UPPER_BOUND(values, 1) - LOWER_BOUND(values, 1) + 1;
+        END_FUNCTION
+
+        FUNCTION forward_sort : DINT
+        VAR_IN_OUT
+            values : ARRAY[*] OF DINT;
+        END_VAR
+            forward_sort := sort(values);
+        END_FUNCTION
+
+        FUNCTION sum : DINT
+        VAR_IN_OUT
+            matrix : ARRAY[*, *] OF DINT;
+        END_VAR
+        VAR
+            i : DINT;
+            j : DINT;
+            result : DINT;
+        END_VAR
+            result := 0;
+            FOR i := LOWER_BOUND(matrix, 1) TO UPPER_BOUND(matrix, 1) DO
+                FOR j := LOWER_BOUND(matrix, 2) TO UPPER_BOUND(matrix, 2) DO
+                    result := result + matrix[i, j] * (i + 1) * j;
+                END_FOR
+            END_FOR
+            sum := result;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            small : ARRAY[1..3] OF DINT;
+            big : ARRAY[-2..4] OF DINT;
+            matrix : ARRAY[0..1, 1..3] OF DINT;
+            small_count : DINT;
+            big_count : DINT;
+            total : DINT;
+        END_VAR
+            small[1] := 3;
+            small[2] := 1;
+            small[3] := 2;
+            big[-2] := 7;
+            big[-1] := -5;
+            big[0] := 0;
+            big[1] := 42;
+            big[2] := 3;
+            big[3] := 3;
+            big[4] := -1;
+            matrix[0, 1] := 1;
+            matrix[0, 2] := 2;
+            matrix[0, 3] := 3;
+            matrix[1, 1] := 4;
+            matrix[1, 2] := 5;
+            matrix[1, 3] := 6;
+            small_count := forward_sort(small);
+            big_count := sort(big);
+            total := sum(matrix);
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType::default();
+    compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!([1, 2, 3], maintype.small);
+    assert_eq!([-5, -1, 0, 3, 3, 7, 42], maintype.big);
+    assert_eq!(3, maintype.small_count);
+    assert_eq!(7, maintype.big_count);
+    //every element is weighted with its row (i + 1) and column (j)
+    assert_eq!(
+        1 + 2 * 2 + 3 * 3 + 2 * 4 + 2 * 5 * 2 + 2 * 6 * 3,
+        maintype.total
+    );
+}
+
+#[test]
+fn check_bounds_is_called_for_every_array_access() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        arr: [i16; 3],
+        i: i32,
+        x: i16,
+        y: i16,
+    }
+
+    let function = r"
+        FUNCTION CheckBounds : DINT
+        VAR_INPUT
+            index, lower, upper : DINT;
+        END_VAR
+            IF index < lower THEN
+                CheckBounds := lower;
+            ELSIF index > upper THEN
+                CheckBounds := upper;
+            ELSE
+                CheckBounds := index;
+            END_IF
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            arr : ARRAY[1..3] OF INT;
+            i : DINT;
+            x : INT;
+            y : INT;
+        END_VAR
+            i := 7;
+            arr[i] := 30;
+            arr[-4] := 10;
+            arr[2] := 20;
+            x := arr[i - 10];
+            y := arr[100];
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType::default();
+    compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!([10, 20, 30], maintype.arr);
+    assert_eq!(10, maintype.x);
+    assert_eq!(30, maintype.y);
+}
+
+#[test]
+fn function_blocks_are_called_through_array_elements() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Counter {
+        step: i32,
+        count: i32,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        counters: [Counter; 3],
+        i: i32,
+        total: i32,
+    }
+
+    let function = r"
+        FUNCTION_BLOCK counter
+        VAR_INPUT
+            step : DINT;
+        END_VAR
+        VAR_OUTPUT
+            count : DINT;
+        END_VAR
+            count := count + step;
+        END_FUNCTION_BLOCK
+
+        PROGRAM main
+        VAR
+            counters : ARRAY[1..3] OF counter;
+            i : DINT;
+            total : DINT;
+        END_VAR
+            FOR i := 1 TO 3 DO
+                counters[i](step := i);
+            END_FOR
+            counters[2](step := 10);
+            total := counters[1].count + counters[2].count + counters[3].count;
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType::default();
+    compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!(1, maintype.counters[0].count);
+    assert_eq!(12, maintype.counters[1].count);
+    assert_eq!(3, maintype.counters[2].count);
+    assert_eq!(16, maintype.total);
+}
@@ -0,0 +1,149 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default)]
+struct MainType {
+    a: u16,
+    b: u16,
+    mask_and: u16,
+    mask_or: u16,
+    mask_xor: u16,
+    mask_not: u16,
+}
+
+#[test]
+fn bitwise_operators_on_words_are_not_boolean() {
+    let function = r"
+        PROGRAM main
+        VAR
+            a : WORD;
+            b : WORD;
+            mask_and : WORD;
+            mask_or : WORD;
+            mask_xor : WORD;
+            mask_not : WORD;
+        END_VAR
+            a := 3855; (* 16#0F0F *)
+            b := 255;  (* 16#00FF *)
+            mask_and := a AND b;
+            mask_or := a OR b;
+            mask_xor := a XOR b;
+            mask_not := NOT a;
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(0x000F, maintype.mask_and);
+    assert_eq!(0x0FFF, maintype.mask_or);
+    assert_eq!(0x0FF0, maintype.mask_xor);
+    assert_eq!(0xF0F0, maintype.mask_not);
+}
+
+#[test]
+fn shift_and_rotate_respect_the_width_of_the_value() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Type {
+        byte_value: u8,
+        shl_byte: u8,
+        rol_byte: u8,
+        word_value: u16,
+        shr_word: u16,
+        ror_word: u16,
+        shl_word_out: u16,
+        shr_formal: u16,
+        dword_value: u32,
+        ror_dword: u32,
+        lword_value: u64,
+        rol_lword: u64,
+    }
+
+    let function = r"
+        PROGRAM main
+        VAR
+            byte_value : BYTE;
+            shl_byte : BYTE;
+            rol_byte : BYTE;
+            word_value : WORD;
+            shr_word : WORD;
+            ror_word : WORD;
+            shl_word_out : WORD;
+            shr_formal : WORD;
+            dword_value : DWORD;
+            ror_dword : DWORD;
+            lword_value : LWORD;
+            rol_lword : LWORD;
+        END_VAR
+            byte_value := 129; (* 16#81 *)
+            shl_byte := SHL(byte_value, 1);
+            rol_byte := ROL(byte_value, 1);
+
+            word_value := 3855; (* 16#0F0F *)
+            shr_word := SHR(word_value, 4);
+            ror_word := ROR(word_value, 4);
+            shl_word_out := SHL(word_value, 16);
+            shr_formal := SHR(N := 8, IN := word_value);
+
+            dword_value := 1;
+            ror_dword := ROR(dword_value, 1);
+
+            lword_value := 1;
+            rol_lword := ROL(lword_value, 65);
+        END_PROGRAM
+        ";
+
+    let mut maintype = Type::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(0x02, maintype.shl_byte);
+    assert_eq!(0x03, maintype.rol_byte);
+    assert_eq!(0x00F0, maintype.shr_word);
+    assert_eq!(0xF0F0, maintype.ror_word);
+    assert_eq!(0, maintype.shl_word_out);
+    assert_eq!(0x000F, maintype.shr_formal);
+    assert_eq!(0x8000_0000, maintype.ror_dword);
+    assert_eq!(2, maintype.rol_lword);
+}
+
+#[test]
+fn shift_counts_wider_than_the_value_are_not_truncated() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Type {
+        byte_value: u8,
+        count: i16,
+        shl_literal: u8,
+        shr_variable: u8,
+        rol_variable: u8,
+    }
+
+    let function = r"
+        PROGRAM main
+        VAR
+            byte_value : BYTE;
+            count : INT;
+            shl_literal : BYTE;
+            shr_variable : BYTE;
+            rol_variable : BYTE;
+        END_VAR
+            byte_value := 1;
+            count := 257;
+            shl_literal := SHL(byte_value, 256);
+            shr_variable := SHR(byte_value, count);
+            rol_variable := ROL(byte_value, count);
+        END_PROGRAM
+        ";
+
+    let mut maintype = Type::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(0, maintype.shl_literal);
+    assert_eq!(0, maintype.shr_variable);
+    assert_eq!(2, maintype.rol_variable);
+}
@@ -0,0 +1,94 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[test]
+fn keywords_and_identifiers_are_case_insensitive() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        point_sum: i32,
+        counted: i32,
+        is_green: bool,
+        doubled: i32,
+        maximum: i32,
+        duration: i64,
+        instance: i32,
+        p_x: i32,
+        p_y: i32,
+        c: i32,
+    }
+
+    let prog = "
+    type Point : struct
+        X : DINT;
+        Y : DINT;
+    end_struct
+    end_type
+
+    Type Color : (Red, Green, Blue); End_Type
+
+    Var_Global
+        Origin : Point := (X := 3, Y := 4);
+    End_Var
+
+    Function Double : DINT
+    Var_Input
+        Value : DINT;
+    End_Var
+        DOUBLE := value * 2;
+    End_Function
+
+    function_block Counter
+    var
+        Count : DINT;
+    end_var
+        count := COUNT + 1;
+    end_function_block
+
+    program main
+    var
+        point_sum : DINT;
+        counted : DINT;
+        is_green : BOOL;
+        doubled : DINT;
+        maximum : DINT;
+        duration : TIME;
+        instance : COUNTER;
+        P : POINT;
+        c : color;
+    END_VAR
+        p.x := ORIGIN.x;
+        P.Y := origin.Y;
+        Point_Sum := p.X + p.y;
+        Instance();
+        INSTANCE();
+        counted := instance.COUNT;
+        c := GREEN;
+        is_green := c = Green;
+        doubled := double(VALUE := 21);
+        maximum := max(3, 7);
+        duration := t#1S;
+    end_program
+    ";
+
+    let mut main = MainType {
+        point_sum: 0,
+        counted: 0,
+        is_green: false,
+        doubled: 0,
+        maximum: 0,
+        duration: 0,
+        instance: 0,
+        p_x: 0,
+        p_y: 0,
+        c: 0,
+    };
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(7, main.point_sum);
+    assert_eq!(2, main.counted);
+    assert_eq!(true, main.is_green);
+    assert_eq!(42, main.doubled);
+    assert_eq!(7, main.maximum);
+    assert_eq!(1_000_000_000, main.duration);
+}
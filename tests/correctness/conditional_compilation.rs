@@ -0,0 +1,89 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+struct MainType {
+    channels: i32,
+    pointer_size: i32,
+}
+
+const PROGRAM: &str = "
+    PROGRAM main
+    VAR
+        channels : DINT;
+        pointer_size : DINT;
+    END_VAR
+    {IF defined(SIMULATOR)}
+        channels := 0;
+    {ELSIF CHANNELS = 4}
+        channels := 4;
+    {ELSE}
+        channels := 2;
+    {END_IF}
+
+    {IF TARGET_POINTER_SIZE = 8}
+        pointer_size := 8;
+    {ELSIF TARGET_POINTER_SIZE = 4}
+        pointer_size := 4;
+    {END_IF}
+    END_PROGRAM
+    ";
+
+fn run_with_defines(defines: &[(&str, Option<&str>)]) -> MainType {
+    let context: Context = Context::create();
+    let source = SourceCode {
+        path: "conditional.st".to_string(),
+        source: PROGRAM.to_string(),
+    };
+    let options = CodeGenOptions {
+        defines: defines
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.map(str::to_string)))
+            .collect(),
+        ..Default::default()
+    };
+    let code_gen =
+        compile_module_with_options(&context, &[source.as_source_container()], options).unwrap();
+    let exec_engine = code_gen
+        .module
+        .create_jit_execution_engine(inkwell::OptimizationLevel::None)
+        .unwrap();
+
+    let mut main = MainType {
+        channels: -1,
+        pointer_size: -1,
+    };
+    run::<MainType>(&exec_engine, "main", &mut main);
+    main
+}
+
+#[test]
+fn defined_symbols_select_the_compiled_code() {
+    assert_eq!(0, run_with_defines(&[("SIMULATOR", None)]).channels);
+    assert_eq!(4, run_with_defines(&[("CHANNELS", Some("4"))]).channels);
+    assert_eq!(2, run_with_defines(&[]).channels);
+}
+
+#[test]
+fn target_symbols_are_defined_for_the_host() {
+    let pointer_size = std::mem::size_of::<usize>() as i32;
+    assert_eq!(pointer_size, run_with_defines(&[]).pointer_size);
+}
+
+#[test]
+fn target_symbols_are_defined_for_the_given_target() {
+    let source = SourceCode {
+        path: "conditional.st".to_string(),
+        source: PROGRAM.to_string(),
+    };
+    let ir = compile_to_ir_with_options(
+        &[source.as_source_container()],
+        Some("i686-unknown-linux-gnu".to_string()),
+        CodeGenOptions::default(),
+    )
+    .unwrap();
+
+    assert!(ir.contains(r#"target triple = "i686-unknown-linux-gnu""#));
+    assert!(ir.contains("store i32 4, i32* %pointer_size"));
+}
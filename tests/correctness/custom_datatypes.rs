@@ -334,3 +334,491 @@ fn using_arrays() {
         assert_eq!(i as i32, *j);
     }
 }
+
+#[test]
+fn whole_structs_and_arrays_can_be_assigned_and_compared() {
+    #[repr(C)]
+    #[derive(Debug, Default, PartialEq, Clone, Copy)]
+    struct Recipe {
+        id: i16,
+        amounts: [i32; 3],
+        temperature: f32,
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        original: Recipe,
+        copy: Recipe,
+        equal_after_copy: bool,
+        different_after_change: bool,
+        amounts: [i32; 3],
+        amounts_equal: bool,
+    }
+
+    let testcode = r#"
+    TYPE Recipe:
+        STRUCT
+            id : INT;
+            amounts : ARRAY[1..3] OF DINT;
+            temperature : REAL;
+        END_STRUCT
+    END_TYPE
+
+    PROGRAM main
+    VAR
+        original : Recipe;
+        copy : Recipe;
+        equal_after_copy : BOOL;
+        different_after_change : BOOL;
+        amounts : ARRAY[0..2] OF DINT;
+        amounts_equal : BOOL;
+    END_VAR
+        original.id := 7;
+        original.amounts[1] := 10;
+        original.amounts[2] := 20;
+        original.amounts[3] := 30;
+        original.temperature := 21.5;
+
+        copy := original;
+        equal_after_copy := copy = original;
+        copy.amounts[2] := 21;
+        different_after_change := copy <> original;
+
+        amounts := original.amounts;
+        amounts_equal := amounts = original.amounts;
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType::default();
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    let expected = Recipe {
+        id: 7,
+        amounts: [10, 20, 30],
+        temperature: 21.5,
+    };
+    assert_eq!(expected, main_data.original);
+    assert_eq!(
+        Recipe {
+            amounts: [10, 21, 30],
+            ..expected
+        },
+        main_data.copy
+    );
+    assert!(main_data.equal_after_copy);
+    assert!(main_data.different_after_change);
+    assert_eq!([10, 20, 30], main_data.amounts);
+    assert!(main_data.amounts_equal);
+}
+
+#[test]
+fn strings_in_structs_are_compared_up_to_their_terminator() {
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct Label {
+        id: i16,
+        text: [u8; 5],
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        a: Label,
+        b: Label,
+        c: Label,
+        a_equals_b: bool,
+        a_differs_from_c: bool,
+    }
+
+    let testcode = r#"
+    TYPE Label:
+        STRUCT
+            id : INT;
+            text : STRING[4];
+        END_STRUCT
+    END_TYPE
+
+    PROGRAM main
+    VAR
+        a : Label;
+        b : Label;
+        c : Label;
+        a_equals_b : BOOL;
+        a_differs_from_c : BOOL;
+    END_VAR
+        a_equals_b := a = b;
+        a_differs_from_c := a <> c;
+    END_PROGRAM
+    "#;
+
+    //the bytes after the terminator are left over from longer strings and must not be compared
+    let mut main_data = MainType {
+        a: Label {
+            id: 1,
+            text: *b"ab\0xy",
+        },
+        b: Label {
+            id: 1,
+            text: *b"ab\0zw",
+        },
+        c: Label {
+            id: 1,
+            text: *b"abc\0\0",
+        },
+        ..MainType::default()
+    };
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    assert!(main_data.a_equals_b);
+    assert!(main_data.a_differs_from_c);
+}
+
+#[test]
+fn struct_and_array_literals_are_evaluated_at_runtime() {
+    #[repr(C)]
+    #[derive(Debug, Default, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        a: f32,
+        n: i32,
+        pos: Point,
+        buf: [i32; 4],
+        length: f32,
+        sum: i32,
+    }
+
+    let testcode = r#"
+    TYPE Point:
+        STRUCT
+            x : REAL;
+            y : REAL := 5.0;
+        END_STRUCT
+    END_TYPE
+
+    FUNCTION manhattan : REAL
+    VAR_INPUT
+        p : Point;
+    END_VAR
+        manhattan := p.x + p.y;
+    END_FUNCTION
+
+    FUNCTION total : DINT
+    VAR_INPUT
+        values : ARRAY[0..3] OF DINT;
+    END_VAR
+        total := values[0] + values[1] + values[2] + values[3];
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        a : REAL;
+        n : DINT;
+        pos : Point;
+        buf : ARRAY[0..3] OF DINT;
+        length : REAL;
+        sum : DINT;
+    END_VAR
+        pos := (x := a * 2.0);
+        buf := [0, 1, n, 3];
+        length := manhattan((x := a, y := a + 1.0));
+        sum := total(values := [n, n, 1]);
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType {
+        a: 1.5,
+        n: 7,
+        ..MainType::default()
+    };
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    assert_eq!(Point { x: 3.0, y: 5.0 }, main_data.pos);
+    assert_eq!([0, 1, 7, 3], main_data.buf);
+    assert_eq!(4.0, main_data.length);
+    assert_eq!(15, main_data.sum);
+}
+
+#[test]
+fn literals_can_read_the_struct_or_array_they_are_assigned_to() {
+    #[repr(C)]
+    #[derive(Debug, Default, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        swapped: Point,
+        moved: Point,
+        rotated: [i32; 3],
+    }
+
+    let testcode = r#"
+    TYPE Point:
+        STRUCT
+            x : REAL;
+            y : REAL := 5.0;
+        END_STRUCT
+    END_TYPE
+
+    PROGRAM main
+    VAR
+        swapped : Point;
+        moved : Point;
+        rotated : ARRAY[0..2] OF DINT;
+    END_VAR
+        swapped := (x := swapped.y, y := swapped.x);
+        moved := (x := moved.x + 1.0);
+        rotated := [rotated[1], rotated[2], rotated[0]];
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType {
+        swapped: Point { x: 1.0, y: 2.0 },
+        moved: Point { x: 3.0, y: 4.0 },
+        rotated: [1, 2, 3],
+    };
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    assert_eq!(Point { x: 2.0, y: 1.0 }, main_data.swapped);
+    //the omitted member gets its initial value
+    assert_eq!(Point { x: 4.0, y: 5.0 }, main_data.moved);
+    assert_eq!([2, 3, 1], main_data.rotated);
+}
+
+#[test]
+fn union_members_overlay_the_same_storage() {
+    #[repr(C)]
+    union Bytes {
+        a: u32,
+        b: [u8; 4],
+    }
+
+    #[repr(C)]
+    union Padded {
+        l: i64,
+        s: [u8; 12],
+    }
+
+    #[repr(C)]
+    struct MainType {
+        word: u32,
+        first: u8,
+        last: u8,
+        u: Bytes,
+        p: Padded,
+    }
+
+    let testcode = r#"
+    TYPE Bytes : UNION
+        a : DWORD;
+        b : ARRAY[0..3] OF BYTE;
+    END_UNION
+    END_TYPE
+
+    TYPE Padded : UNION
+        l : LINT;
+        s : ARRAY[0..11] OF BYTE;
+    END_UNION
+    END_TYPE
+
+    PROGRAM main
+    VAR
+        word : DWORD;
+        first : BYTE;
+        last : BYTE;
+        u : Bytes;
+        p : Padded;
+    END_VAR
+        u.a := word;
+        first := u.b[0];
+        last := u.b[3];
+        p.l := -1;
+        p.s[11] := 42;
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType {
+        word: 0x0102_0304,
+        first: 0,
+        last: 0,
+        u: Bytes { a: 0 },
+        p: Padded { s: [0; 12] },
+    };
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    assert_eq!(16, std::mem::size_of::<Padded>());
+    assert_eq!(0x04, main_data.first);
+    assert_eq!(0x01, main_data.last);
+    assert_eq!(0x0102_0304, unsafe { main_data.u.a });
+    assert_eq!(
+        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 42],
+        unsafe { main_data.p.s }
+    );
+}
+
+#[test]
+fn unions_are_laid_out_for_the_target() {
+    let source = SourceCode {
+        path: "unions.st".to_string(),
+        source: "
+        TYPE Padded : UNION
+          l : LINT;
+          s : ARRAY[0..11] OF BYTE;
+        END_UNION
+        END_TYPE
+
+        PROGRAM main
+        VAR
+          p : Padded;
+        END_VAR
+        END_PROGRAM
+        "
+        .to_string(),
+    };
+    //a LINT is aligned to 8 bytes on x86_64 but only to 4 bytes on x86
+    let ir = compile_to_ir_with_options(
+        &[source.as_source_container()],
+        Some("x86_64-unknown-linux-gnu".to_string()),
+        CodeGenOptions::default(),
+    )
+    .unwrap();
+    assert!(ir.contains("%Padded = type { i64, [8 x i8] }"));
+
+    let ir = compile_to_ir_with_options(
+        &[source.as_source_container()],
+        Some("i686-unknown-linux-gnu".to_string()),
+        CodeGenOptions::default(),
+    )
+    .unwrap();
+    assert!(ir.contains("%Padded = type { i64, [4 x i8] }"));
+}
+
+#[test]
+fn packed_structs_with_bits_match_c_layouts() {
+    #[repr(C, packed)]
+    #[derive(Clone, Copy)]
+    struct Header {
+        kind: u8,
+        length: i32,
+        bits: u8,
+        ok: bool,
+    }
+
+    #[repr(C)]
+    struct MainType {
+        header: Header,
+        length: i32,
+        valid: bool,
+        urgent: bool,
+    }
+
+    let testcode = r#"
+    {attribute 'pack_mode' := '1'}
+    TYPE Header : STRUCT
+        kind : BYTE;
+        length : DINT;
+        valid : BIT;
+        urgent : BIT;
+        last : BIT;
+        ok : BOOL;
+    END_STRUCT
+    END_TYPE
+
+    PROGRAM main
+    VAR
+        header : Header;
+        length : DINT;
+        valid : BOOL;
+        urgent : BOOL;
+    END_VAR
+        header.kind := 7;
+        header.length := length;
+        header.urgent := TRUE;
+        header.last := TRUE;
+        header.last := FALSE;
+        header.ok := TRUE;
+        valid := header.valid;
+        urgent := header.urgent;
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType {
+        header: Header {
+            kind: 0,
+            length: 0,
+            bits: 0b001,
+            ok: false,
+        },
+        length: 1_000_000,
+        valid: false,
+        urgent: false,
+    };
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    let Header {
+        kind,
+        length,
+        bits,
+        ok,
+    } = main_data.header;
+    assert_eq!(7, kind);
+    assert_eq!(1_000_000, length);
+    assert_eq!(0b011, bits);
+    assert!(ok);
+    assert!(main_data.valid);
+    assert!(main_data.urgent);
+}
+
+#[test]
+fn big_arrays_are_compared_and_copied_to_themselves() {
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    struct MainType {
+        equal_after_copy: bool,
+        different_after_change: bool,
+        equal_after_self_assignment: bool,
+    }
+
+    let testcode = r#"
+    PROGRAM main
+    VAR
+        equal_after_copy : BOOL;
+        different_after_change : BOOL;
+        equal_after_self_assignment : BOOL;
+        original : ARRAY[0..99, 0..99] OF DINT;
+        copy : ARRAY[0..99, 0..99] OF DINT;
+        i : INT;
+        j : INT;
+    END_VAR
+        FOR i := 0 TO 99 DO
+            FOR j := 0 TO 99 DO
+                original[i, j] := i * 100 + j;
+            END_FOR
+        END_FOR
+
+        copy := original;
+        equal_after_copy := copy = original;
+        copy[99, 99] := 0;
+        different_after_change := copy <> original;
+        copy := copy;
+        equal_after_self_assignment := copy[99, 98] = original[99, 98] AND copy[99, 99] = 0;
+    END_PROGRAM
+    "#;
+
+    let mut main_data = MainType::default();
+    compile_and_run(testcode.to_string(), &mut main_data);
+
+    assert!(main_data.equal_after_copy);
+    assert!(main_data.different_after_change);
+    assert!(main_data.equal_after_self_assignment);
+}
@@ -0,0 +1,221 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default)]
+struct MainType {
+    dt1: i64,
+    dt2: i64,
+    diff: i64,
+    later: i64,
+    combined: i64,
+    scaled: i64,
+    year: i32,
+    month: i32,
+    day: i32,
+    hour: i32,
+    minute: i32,
+    second: i32,
+    milli: i32,
+    factor: f32,
+}
+
+const MILLIS_PER_HOUR: i64 = 3_600_000;
+const NANOS_PER_MILLI: i64 = 1_000_000;
+
+#[test]
+fn date_and_time_arithmetic_and_functions() {
+    let prog = "
+    PROGRAM main
+    VAR
+        dt1 : DT;
+        dt2 : DT;
+        diff : TIME;
+        later : TOD;
+        combined : DT;
+        scaled : TIME;
+        year : DINT;
+        month : DINT;
+        day : DINT;
+        hour : DINT;
+        minute : DINT;
+        second : DINT;
+        milli : DINT;
+        factor : REAL;
+    END_VAR
+        dt1 := DT#2021-05-01-19:29:17.250;
+        dt2 := dt1 - T#25h;
+        diff := SUB_DT_DT(dt1, dt2);
+        later := ADD_TOD_TIME(TOD#10:00:00, T#1h30m);
+        combined := CONCAT_DATE_TOD(D#2021-05-01, TOD#19:29:17.250);
+        factor := 2.5;
+        scaled := T#1s * factor + T#1s / 4;
+        SPLIT_DT(IN1 := dt1,
+            YEAR => year,
+            MONTH => month,
+            DAY => day,
+            HOUR => hour,
+            MINUTE => minute,
+            SECOND => second,
+            MILLI => milli);
+    END_PROGRAM
+    ";
+
+    let mut main = MainType::default();
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(1_619_897_357_250, main.dt1);
+    assert_eq!(main.dt1 - 25 * MILLIS_PER_HOUR, main.dt2);
+    assert_eq!(25 * MILLIS_PER_HOUR * NANOS_PER_MILLI, main.diff);
+    assert_eq!(11 * MILLIS_PER_HOUR + 30 * 60_000, main.later);
+    assert_eq!(main.dt1, main.combined);
+    assert_eq!(2_750 * NANOS_PER_MILLI, main.scaled);
+    assert_eq!(
+        (2021, 5, 1, 19, 29, 17, 250),
+        (
+            main.year,
+            main.month,
+            main.day,
+            main.hour,
+            main.minute,
+            main.second,
+            main.milli
+        )
+    );
+}
+
+#[test]
+fn split_dt_before_1970() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct SplitType {
+        year: i32,
+        month: i32,
+        day: i32,
+        hour: i32,
+        minute: i32,
+        second: i32,
+    }
+
+    let prog = "
+    PROGRAM main
+    VAR
+        year : DINT;
+        month : DINT;
+        day : DINT;
+        hour : DINT;
+        minute : DINT;
+        second : DINT;
+    END_VAR
+        SPLIT_DT(IN1 := DT#1969-12-31-23:59:59,
+            YEAR => year,
+            MONTH => month,
+            DAY => day,
+            HOUR => hour,
+            MINUTE => minute,
+            SECOND => second);
+    END_PROGRAM
+    ";
+
+    let mut main = SplitType::default();
+    compile_and_run(prog.to_string(), &mut main);
+    assert_eq!(
+        (1969, 12, 31, 23, 59, 59),
+        (
+            main.year,
+            main.month,
+            main.day,
+            main.hour,
+            main.minute,
+            main.second
+        )
+    );
+}
+
+#[test]
+fn long_date_and_time_types_keep_nanoseconds() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct LongType {
+        ldt1: i64,
+        ldt2: i64,
+        diff: i64,
+        short: i64,
+        long: i64,
+        ltod: i64,
+        ltime: i64,
+    }
+
+    let prog = "
+    PROGRAM main
+    VAR
+        ldt1 : LDT;
+        ldt2 : LDT;
+        diff : LTIME;
+        short : DT;
+        long : LDT;
+        ltod : LTOD;
+        ltime : LTIME;
+    END_VAR
+        ldt1 := LDT#2021-05-01-19:29:17.123456789;
+        ldt2 := ldt1 + LTIME#1s1ns;
+        diff := ldt2 - ldt1;
+        short := LDT_TO_DT(ldt1);
+        long := DT_TO_LDT(short);
+        ltod := LTOD#23:59:59.999999999;
+        ltime := TIME_TO_LTIME(T#1s);
+    END_PROGRAM
+    ";
+
+    let mut main = LongType::default();
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(1_619_897_357_123_456_789, main.ldt1);
+    assert_eq!(main.ldt1 + 1_000_000_001, main.ldt2);
+    assert_eq!(1_000_000_001, main.diff);
+    assert_eq!(1_619_897_357_123, main.short);
+    assert_eq!(1_619_897_357_123 * NANOS_PER_MILLI, main.long);
+    assert_eq!(86_399_999_999_999, main.ltod);
+    assert_eq!(1_000_000_000, main.ltime);
+}
+
+#[test]
+fn durations_can_be_multiplied_from_either_side() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct ScaleType {
+        left: i64,
+        right: i64,
+        real_factor: i64,
+        long: i64,
+    }
+
+    let prog = "
+    PROGRAM main
+    VAR
+        left : TIME;
+        right : TIME;
+        real_factor : TIME;
+        long : LTIME;
+        factor : INT;
+    END_VAR
+        factor := 3;
+        left := T#2s * factor;
+        right := factor * T#2s;
+        real_factor := 0.5 * T#2s;
+        long := 2 * LTIME#1s1ns;
+    END_PROGRAM
+    ";
+
+    let mut main = ScaleType::default();
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(6_000 * NANOS_PER_MILLI, main.left);
+    assert_eq!(main.left, main.right);
+    assert_eq!(1_000 * NANOS_PER_MILLI, main.real_factor);
+    assert_eq!(2_000_000_002, main.long);
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default)]
+struct MainType {
+    zero: i32,
+    quotient: i32,
+    remainder: i32,
+    real_quotient: f32,
+}
+
+#[test]
+fn zero_divisors_are_replaced_by_the_check_functions() {
+    let function = r"
+        FUNCTION CheckDivDInt : DINT
+        VAR_INPUT divisor : DINT; END_VAR
+            IF divisor = 0 THEN
+                CheckDivDInt := 1;
+            ELSE
+                CheckDivDInt := divisor;
+            END_IF
+        END_FUNCTION
+
+        FUNCTION CheckDivReal : REAL
+        VAR_INPUT divisor : REAL; END_VAR
+            IF divisor = 0.0 THEN
+                CheckDivReal := 2.0;
+            ELSE
+                CheckDivReal := divisor;
+            END_IF
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            zero : DINT;
+            quotient : DINT;
+            remainder : DINT;
+            real_quotient : REAL;
+        END_VAR
+            quotient := 42 / zero;
+            remainder := 42 MOD zero;
+            real_quotient := 5.0 / zero;
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType::default();
+    compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!(42, maintype.quotient);
+    assert_eq!(0, maintype.remainder);
+    assert_almost_eq!(2.5, maintype.real_quotient, f32::EPSILON);
+}
+
+#[test]
+fn wide_divisors_are_not_truncated_for_the_check_functions() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Type {
+        quotient: i64,
+        zero_quotient: i64,
+        narrow_quotient: i32,
+    }
+
+    let function = r"
+        FUNCTION CheckDivDInt : DINT
+        VAR_INPUT divisor : DINT; END_VAR
+            IF divisor = 0 THEN
+                CheckDivDInt := 1;
+            ELSE
+                CheckDivDInt := divisor;
+            END_IF
+        END_FUNCTION
+
+        FUNCTION CheckDivLInt : LINT
+        VAR_INPUT divisor : LINT; END_VAR
+            IF divisor = 0 THEN
+                CheckDivLInt := 2;
+            ELSE
+                CheckDivLInt := divisor;
+            END_IF
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            quotient : LINT;
+            zero_quotient : LINT;
+            narrow_quotient : DINT;
+            dividend : LINT;
+            divisor : LINT;
+            zero : LINT;
+            narrow_zero : DINT;
+        END_VAR
+            dividend := 42949672960;
+            divisor := 4294967296;
+            quotient := dividend / divisor;
+            zero_quotient := dividend / zero;
+            narrow_quotient := 42 / narrow_zero;
+        END_PROGRAM
+        ";
+
+    let mut maintype = Type::default();
+    compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!(10, maintype.quotient);
+    assert_eq!(21_474_836_480, maintype.zero_quotient);
+    assert_eq!(42, maintype.narrow_quotient);
+}
@@ -49,3 +49,93 @@ fn test_external_function_called() {
     //Call that function
     //Test the function's result is executed
 }
+
+extern "C" fn scale(value: i32, factor: f32, total: &mut i32, rounded: &mut i32) -> i32 {
+    let scaled = value as f32 * factor;
+    *total += scaled as i32;
+    *rounded = scaled.round() as i32;
+    scaled as i32
+}
+
+extern "C" fn text_length(text: *const u8) -> i32 {
+    unsafe { std::ffi::CStr::from_ptr(text as *const std::os::raw::c_char) }
+        .to_bytes()
+        .len() as i32
+}
+
+#[test]
+fn external_functions_with_the_c_calling_convention_are_called_with_native_arguments() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        total: i32,
+        rounded: i32,
+        scaled: i32,
+        length: i32,
+    }
+
+    let prog = "
+    {attribute 'calling_convention' := 'C'}
+    @EXTERNAL FUNCTION scale : DINT
+    VAR_INPUT
+        value : DINT;
+        factor : REAL;
+    END_VAR
+    VAR_IN_OUT
+        total : DINT;
+    END_VAR
+    VAR_OUTPUT
+        rounded : DINT;
+    END_VAR
+    END_FUNCTION
+
+    {attribute 'calling_convention' := 'C'}
+    @EXTERNAL FUNCTION text_length : DINT
+    VAR_INPUT
+        text : STRING;
+    END_VAR
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        total : DINT;
+        rounded : DINT;
+        scaled : DINT;
+        length : DINT;
+    END_VAR
+        total := 10;
+        scaled := scale(value := 5, factor := 2.5, total := total, rounded => rounded);
+        length := text_length('hello');
+    END_PROGRAM
+    ";
+
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+    let context: Context = Context::create();
+    let source = &SourceCode {
+        path: "external_test.st".to_string(),
+        source: prog.to_string(),
+    };
+    let code_gen = compile_module(&context, &[source.as_source_container()]).unwrap();
+    let exec_engine = code_gen
+        .module
+        .create_jit_execution_engine(inkwell::OptimizationLevel::None)
+        .unwrap();
+
+    let scale_fn = code_gen.module.get_function("scale").unwrap();
+    exec_engine.add_global_mapping(&scale_fn, scale as usize);
+    let text_length_fn = code_gen.module.get_function("text_length").unwrap();
+    exec_engine.add_global_mapping(&text_length_fn, text_length as usize);
+
+    let mut main = MainType {
+        total: 0,
+        rounded: 0,
+        scaled: 0,
+        length: 0,
+    };
+    run::<MainType>(&exec_engine, "main", &mut main);
+
+    assert_eq!(22, main.total);
+    assert_eq!(13, main.rounded);
+    assert_eq!(12, main.scaled);
+    assert_eq!(5, main.length);
+}
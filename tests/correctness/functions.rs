@@ -383,6 +383,48 @@ fn function_call_inout_variable() {
     assert_eq!(64, interface.baz);
 }
 
+#[test]
+fn non_formal_arguments_can_mix_inputs_and_inouts() {
+    #[repr(C)]
+    struct MainType {
+        total: i32,
+        result: i32,
+    }
+    let function = r#"
+        FUNCTION accumulate : DINT
+            VAR_INPUT
+                increment : DINT;
+            END_VAR
+            VAR_IN_OUT
+                total : DINT;
+            END_VAR
+            VAR_INPUT
+                factor : DINT;
+            END_VAR
+            total := total + increment * factor;
+            accumulate := total;
+        END_FUNCTION
+
+        PROGRAM main
+            VAR
+                total : DINT;
+                result : DINT;
+            END_VAR
+            accumulate(2, total, 3);
+            result := accumulate(1, total, 4);
+        END_PROGRAM
+    "#;
+
+    let mut interface = MainType {
+        total: 0,
+        result: 0,
+    };
+    let (_, _) = compile_and_run(function.to_string(), &mut interface);
+
+    assert_eq!(10, interface.total);
+    assert_eq!(10, interface.result);
+}
+
 #[test]
 fn inouts_behave_like_pointers() {
     #[repr(C)]
@@ -435,3 +477,332 @@ fn inouts_behave_like_pointers() {
     assert_eq!(8, interface.p2);
     assert_eq!(9, interface.p3);
 }
+
+#[test]
+fn members_and_elements_of_inouts_can_be_accessed() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        x: i32,
+        y: i32,
+        values: [i32; 3],
+    }
+    let function = r#"
+        TYPE Point : STRUCT
+            x : DINT;
+            y : DINT;
+        END_STRUCT
+        END_TYPE
+
+        FUNCTION shift : DINT
+            VAR_IN_OUT
+                p : Point;
+                values : ARRAY[0..2] OF DINT;
+            END_VAR
+            p.x := p.x + values[1];
+            p.y := p.x * 2;
+            values[2] := p.y;
+        END_FUNCTION
+
+        PROGRAM main
+            VAR
+                position : Point;
+                values : ARRAY[0..2] OF DINT;
+            END_VAR
+            position.x := 1;
+            values[1] := 4;
+            shift(position, values);
+        END_PROGRAM
+    "#;
+
+    let mut interface = MainType {
+        x: 0,
+        y: 0,
+        values: [0; 3],
+    };
+    let (_, _) = compile_and_run(function.to_string(), &mut interface);
+
+    assert_eq!(5, interface.x);
+    assert_eq!(10, interface.y);
+    assert_eq!([0, 4, 10], interface.values);
+}
+
+#[test]
+fn constant_inputs_are_passed_by_reference() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct ObserverType {
+        recipe: usize,
+        speed: i32,
+    }
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        speed: i32,
+        duration: i32,
+        observer: ObserverType,
+        by_variable: i32,
+        by_literal: i32,
+        by_expression: i32,
+        first_observed: i32,
+        observed: i32,
+        factor: i16,
+    }
+    let function = r#"
+        TYPE Recipe : STRUCT
+            speed : DINT;
+            duration : DINT;
+        END_STRUCT
+        END_TYPE
+
+        FUNCTION total : DINT
+            VAR_INPUT CONSTANT
+                recipe : Recipe;
+                factor : DINT := 2;
+            END_VAR
+            total := (recipe.speed + recipe.duration) * factor;
+        END_FUNCTION
+
+        FUNCTION_BLOCK Observer
+            VAR_INPUT {attribute 'by_ref'}
+                recipe : Recipe;
+            END_VAR
+            VAR_OUTPUT
+                speed : DINT;
+            END_VAR
+            speed := recipe.speed;
+        END_FUNCTION_BLOCK
+
+        PROGRAM main
+            VAR
+                recipe : Recipe;
+                observer : Observer;
+                by_variable : DINT;
+                by_literal : DINT;
+                by_expression : DINT;
+                first_observed : DINT;
+                observed : DINT;
+                factor : INT := 3;
+            END_VAR
+            recipe.speed := 10;
+            recipe.duration := 5;
+            by_variable := total(recipe);
+            by_literal := total(recipe := (speed := 1, duration := 2), factor := factor);
+            by_expression := total(recipe, factor + 1);
+
+            observer(recipe := recipe);
+            first_observed := observer.speed;
+            recipe.speed := 20;
+            observer(recipe := recipe);
+            observed := observer.speed;
+        END_PROGRAM
+    "#;
+
+    let mut interface = MainType {
+        speed: 0,
+        duration: 0,
+        observer: ObserverType {
+            recipe: 0,
+            speed: 0,
+        },
+        by_variable: 0,
+        by_literal: 0,
+        by_expression: 0,
+        first_observed: 0,
+        observed: 0,
+        factor: 3,
+    };
+    let (_, _) = compile_and_run(function.to_string(), &mut interface);
+
+    assert_eq!(30, interface.by_variable);
+    assert_eq!(9, interface.by_literal);
+    assert_eq!(60, interface.by_expression);
+    assert_eq!(10, interface.first_observed);
+    assert_eq!(20, interface.observed);
+}
+
+#[test]
+fn functions_can_return_structs_arrays_and_strings() {
+    #[repr(C)]
+    #[derive(Debug, Default, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[repr(C)]
+    struct MainType {
+        point: Point,
+        squares: [i32; 4],
+        message: [u8; 81],
+    }
+
+    let function = r"
+        TYPE Point : STRUCT
+            x : DINT;
+            y : DINT;
+        END_STRUCT
+        END_TYPE
+
+        FUNCTION make_point : Point
+        VAR_INPUT
+            x, y : DINT;
+        END_VAR
+        VAR
+            result : Point;
+        END_VAR
+            result.x := x;
+            result.y := y;
+            make_point := result;
+        END_FUNCTION
+
+        FUNCTION squares : ARRAY[0..3] OF DINT
+        VAR
+            i : DINT;
+            result : ARRAY[0..3] OF DINT;
+        END_VAR
+            FOR i := 0 TO 3 DO
+                result[i] := i * i;
+            END_FOR
+            squares := result;
+        END_FUNCTION
+
+        FUNCTION message : STRING
+        VAR_INPUT
+            text : STRING;
+        END_VAR
+            message := text;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            point : Point;
+            squares_of : ARRAY[0..3] OF DINT;
+            text : STRING;
+        END_VAR
+            point := make_point(3, 4);
+            squares_of := squares();
+            text := message('done');
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType {
+        point: Point::default(),
+        squares: [0; 4],
+        message: [0; 81],
+    };
+    compile_and_run(function.to_string(), &mut maintype);
+
+    assert_eq!(Point { x: 3, y: 4 }, maintype.point);
+    assert_eq!([0, 1, 4, 9], maintype.squares);
+    assert_eq!(b"done\0", &maintype.message[0..5]);
+}
+
+#[test]
+fn functions_with_the_c_calling_convention_can_be_called_natively() {
+    #[repr(C)]
+    struct MainType {
+        total: i32,
+        doubled: i32,
+        result: i32,
+    }
+
+    let source = r"
+        {attribute 'calling_convention' := 'C'}
+        FUNCTION add_to : DINT
+        VAR_INPUT
+            a : DINT;
+        END_VAR
+        VAR_IN_OUT
+            total : DINT;
+        END_VAR
+        VAR_OUTPUT
+            doubled : DINT;
+        END_VAR
+            total := total + a;
+            doubled := a * 2;
+            add_to := total;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            total : DINT;
+            doubled : DINT;
+            result : DINT;
+        END_VAR
+            total := 1;
+            result := add_to(a := 4, total := total, doubled => doubled);
+        END_PROGRAM
+        ";
+
+    let context = Context::create();
+    let exec_engine = compile(&context, source.to_string());
+
+    //call the function from ST
+    let mut main = MainType {
+        total: 0,
+        doubled: 0,
+        result: 0,
+    };
+    run::<MainType>(&exec_engine, "main", &mut main);
+    assert_eq!(5, main.total);
+    assert_eq!(8, main.doubled);
+    assert_eq!(5, main.result);
+
+    //call the function with its native signature
+    let mut total = 10;
+    let mut doubled = 0;
+    let result = unsafe {
+        let add_to: JitFunction<unsafe extern "C" fn(i32, *mut i32, *mut i32) -> i32> =
+            exec_engine.get_function("add_to").unwrap();
+        add_to.call(3, &mut total, &mut doubled)
+    };
+    assert_eq!(13, result);
+    assert_eq!(13, total);
+    assert_eq!(6, doubled);
+}
+
+#[test]
+fn omitted_inputs_use_their_initial_values() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        defaulted: i32,
+        overridden: i32,
+        positional: i32,
+    }
+
+    let source = r"
+        FUNCTION scale : DINT
+        VAR_INPUT
+            value : DINT;
+            factor : DINT := 10;
+            offset : DINT := 3;
+        END_VAR
+            scale := value * factor + offset;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            defaulted : DINT;
+            overridden : DINT;
+            positional : DINT;
+        END_VAR
+            defaulted := scale(value := 2);
+            overridden := scale(offset := 0, value := 2);
+            positional := scale(2, 4);
+        END_PROGRAM
+        ";
+
+    let context = Context::create();
+    let exec_engine = compile(&context, source.to_string());
+    let mut main = MainType {
+        defaulted: 0,
+        overridden: 0,
+        positional: 0,
+    };
+    run::<MainType>(&exec_engine, "main", &mut main);
+    assert_eq!(23, main.defaulted);
+    assert_eq!(20, main.overridden);
+    assert_eq!(11, main.positional);
+}
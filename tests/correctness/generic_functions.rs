@@ -0,0 +1,70 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default)]
+struct MainType {
+    i: i16,
+    d: i32,
+    r: f32,
+    l: f64,
+    b: u8,
+    m: u8,
+}
+
+#[test]
+fn generic_functions_are_instantiated_for_every_argument_type() {
+    let source = "
+        FUNCTION scale : ANY_NUM
+        VAR_INPUT
+            value : ANY_NUM;
+            factor : ANY_NUM;
+        END_VAR
+            scale := value * factor;
+        END_FUNCTION
+
+        FUNCTION twice : ANY_INT
+        VAR_INPUT
+            value : ANY_INT;
+        END_VAR
+            twice := scale(value, value / value + 1);
+        END_FUNCTION
+
+        FUNCTION mask : ANY_BIT
+        VAR_INPUT
+            value : ANY_BIT;
+            bits : ANY_BIT;
+        END_VAR
+            mask := value AND bits;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            i : INT;
+            d : DINT;
+            r : REAL;
+            l : LREAL;
+            b : BYTE;
+            m : BYTE;
+        END_VAR
+            i := 300;
+            i := scale(i, i / 100);
+            d := twice(21);
+            r := scale(r + 1.5, 3.0);
+            l := scale(l + 1.25, 2);
+            b := 255;
+            m := 15;
+            b := mask(b, m);
+        END_PROGRAM
+        ";
+
+    let mut main = MainType::default();
+    compile_and_run(source.to_string(), &mut main);
+
+    assert_eq!(900, main.i);
+    assert_eq!(42, main.d);
+    assert_almost_eq!(4.5, main.r, f32::EPSILON);
+    assert_almost_eq!(2.5, main.l, f64::EPSILON);
+    assert_eq!(0x0F, main.b);
+}
@@ -108,3 +108,30 @@ fn global_variables_with_initialization() {
         }
     );
 }
+
+#[test]
+fn global_variable_lists_can_declare_variables_with_the_same_name() {
+    let function = r"
+    VAR_GLOBAL Gvl_Io {attribute 'qualified_only'}
+        counter : DINT := 10;
+        values : ARRAY[0..2] OF DINT;
+    END_VAR
+    VAR_GLOBAL Gvl_Hmi
+        counter : DINT := 5;
+        limit : DINT := 100;
+    END_VAR
+    VAR_GLOBAL {attribute 'qualified_only'}
+        counter : DINT := 1;
+    END_VAR
+    FUNCTION main : DINT
+    VAR
+        counter : DINT := 1000;
+    END_VAR
+        Gvl_Io.values[1] := Gvl_Io.counter + Gvl_Hmi.counter;
+        Gvl_Hmi.counter := Gvl_Io.values[1] + limit;
+        main := Gvl_Hmi.counter + external_test.counter + counter;
+    END_FUNCTION
+    ";
+    let (res, _) = compile_and_run(function.to_string(), &mut MainType { x: 0, ret: 0 });
+    assert_eq!(res, 1116);
+}
@@ -0,0 +1,410 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+use inkwell::targets::{InitializationConfig, Target};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn linked_list_can_be_built_and_released() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        head: usize,
+        n: usize,
+        i: i32,
+        sum: i32,
+        count: i32,
+    }
+
+    let prog = "
+    TYPE Node : STRUCT
+        value : DINT;
+        next : NodePtr;
+    END_STRUCT
+    END_TYPE
+
+    TYPE NodePtr : POINTER TO Node; END_TYPE
+
+    PROGRAM main
+    VAR
+        head : NodePtr;
+        n : NodePtr;
+        i : DINT;
+        sum : DINT;
+        count : DINT;
+    END_VAR
+        head := 0;
+        FOR i := 1 TO 5 DO
+            n := __NEW(Node);
+            n^.value := i;
+            n^.next := head;
+            head := n;
+        END_FOR
+
+        WHILE head <> 0 DO
+            sum := sum + head^.value;
+            count := count + 1;
+            n := head;
+            head := head^.next;
+            __DELETE(n);
+        END_WHILE
+    END_PROGRAM
+    ";
+
+    let mut main = MainType {
+        head: 0,
+        n: 0,
+        i: 0,
+        sum: 0,
+        count: 0,
+    };
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(15, main.sum);
+    assert_eq!(5, main.count);
+    assert_eq!(0, main.head);
+    assert_eq!(0, main.n);
+}
+
+#[test]
+fn buffers_with_a_runtime_size_can_be_allocated() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        buffer: usize,
+        size: i32,
+        i: i32,
+        sum: i32,
+        released: bool,
+    }
+
+    let prog = "
+    TYPE DintPtr : POINTER TO DINT; END_TYPE
+
+    PROGRAM main
+    VAR
+        buffer : DintPtr;
+        size : DINT;
+        i : DINT;
+        sum : DINT;
+        released : BOOL;
+    END_VAR
+        buffer := __NEW(DINT, size);
+        FOR i := 0 TO size - 1 DO
+            buffer[i] := i * i;
+        END_FOR
+        FOR i := 0 TO size - 1 DO
+            sum := sum + buffer[i];
+        END_FOR
+        __DELETE(buffer);
+        released := buffer = 0;
+    END_PROGRAM
+    ";
+
+    let mut main = MainType {
+        buffer: 0,
+        size: 10,
+        i: 0,
+        sum: 0,
+        released: false,
+    };
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(285, main.sum);
+    assert_eq!(true, main.released);
+}
+
+#[test]
+fn allocated_function_blocks_are_initialized() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        counters: usize,
+        single: usize,
+        first: i32,
+        last: i32,
+        step: i32,
+        single_count: i32,
+    }
+
+    let prog = "
+    FUNCTION_BLOCK Counter
+    VAR
+        count : DINT := 10;
+        step : DINT := 3;
+    END_VAR
+        count := count + step;
+    END_FUNCTION_BLOCK
+
+    TYPE CounterPtr : POINTER TO Counter; END_TYPE
+
+    PROGRAM main
+    VAR
+        counters : CounterPtr;
+        single : CounterPtr;
+        first : DINT;
+        last : DINT;
+        step : DINT;
+        single_count : DINT;
+    END_VAR
+        counters := __NEW(Counter, 3);
+        single := __NEW(Counter);
+        first := counters[0].count;
+        last := counters[2].count;
+        step := counters[1].step;
+        single_count := single^.count;
+        __DELETE(counters);
+        __DELETE(single);
+    END_PROGRAM
+    ";
+
+    let mut main = MainType {
+        counters: 0,
+        single: 0,
+        first: 0,
+        last: 0,
+        step: 0,
+        single_count: 0,
+    };
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(10, main.first);
+    assert_eq!(10, main.last);
+    assert_eq!(3, main.step);
+    assert_eq!(10, main.single_count);
+}
+
+#[test]
+fn allocated_function_blocks_can_be_called_through_pointers() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        accumulator: usize,
+        total: i32,
+        direct_total: i32,
+    }
+
+    let prog = "
+    FUNCTION_BLOCK Accumulator
+    VAR_INPUT
+        increment : DINT;
+    END_VAR
+    VAR_OUTPUT
+        total : DINT;
+    END_VAR
+        total := total + increment;
+    END_FUNCTION_BLOCK
+
+    TYPE AccumulatorPtr : POINTER TO Accumulator; END_TYPE
+
+    PROGRAM main
+    VAR
+        accumulator : AccumulatorPtr;
+        total : DINT;
+        direct_total : DINT;
+    END_VAR
+        accumulator := __NEW(Accumulator);
+        accumulator^(5);
+        accumulator^(increment := 3, total => total);
+        direct_total := accumulator^.total;
+        __DELETE(accumulator);
+    END_PROGRAM
+    ";
+
+    let mut main = MainType {
+        accumulator: 0,
+        total: 0,
+        direct_total: 0,
+    };
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(8, main.total);
+    assert_eq!(8, main.direct_total);
+}
+
+#[test]
+fn references_are_dereferenced_implicitly() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        a: Point,
+        b: Point,
+        r: usize,
+        i: usize,
+        valid_before: bool,
+        valid_after: bool,
+        valid_reset: bool,
+        sum: i32,
+    }
+
+    let prog = "
+    TYPE Point : STRUCT
+        x : DINT;
+        y : DINT;
+    END_STRUCT
+    END_TYPE
+
+    PROGRAM main
+    VAR
+        a : Point;
+        b : Point;
+        r : REFERENCE TO Point;
+        i : REFERENCE TO DINT;
+        valid_before : BOOL;
+        valid_after : BOOL;
+        valid_reset : BOOL;
+        sum : DINT;
+    END_VAR
+        valid_before := __ISVALIDREF(r);
+        r REF= a;
+        valid_after := __ISVALIDREF(r);
+        r.x := 1;
+        r REF= b;
+        r.x := 2;
+        r.y := r.x * 10;
+        i REF= r.y;
+        i := i + 1;
+        sum := a.x + b.x + b.y;
+        r REF= 0;
+        valid_reset := __ISVALIDREF(r);
+    END_PROGRAM
+    ";
+
+    let mut main = MainType {
+        a: Point { x: 0, y: 0 },
+        b: Point { x: 0, y: 0 },
+        r: 0,
+        i: 0,
+        valid_before: true,
+        valid_after: false,
+        valid_reset: true,
+        sum: 0,
+    };
+    compile_and_run(prog.to_string(), &mut main);
+
+    assert_eq!(false, main.valid_before);
+    assert_eq!(true, main.valid_after);
+    assert_eq!(false, main.valid_reset);
+    assert_eq!(1, main.a.x);
+    assert_eq!(2, main.b.x);
+    assert_eq!(21, main.b.y);
+    assert_eq!(24, main.sum);
+    assert_eq!(0, main.r);
+}
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static RELEASED: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn counting_alloc(size: i64) -> *mut u8 {
+    ALLOCATED.fetch_add(size as usize, Ordering::SeqCst);
+    Box::into_raw(vec![0_u8; size as usize].into_boxed_slice()) as *mut u8
+}
+
+extern "C" fn counting_free(_memory: *mut u8) -> i32 {
+    //the memory is leaked on purpose, the test only counts the calls
+    RELEASED.fetch_add(1, Ordering::SeqCst);
+    0
+}
+
+#[test]
+fn allocator_hooks_can_be_replaced_by_external_functions() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct MainType {
+        buffer: usize,
+        value: i32,
+    }
+
+    let prog = "
+    TYPE BytePtr : POINTER TO BYTE; END_TYPE
+    TYPE IntPtr : POINTER TO INT; END_TYPE
+
+    {attribute 'calling_convention' := 'C'}
+    @EXTERNAL FUNCTION __rusty_alloc : BytePtr
+    VAR_INPUT
+        size : LINT;
+    END_VAR
+    END_FUNCTION
+
+    {attribute 'calling_convention' := 'C'}
+    @EXTERNAL FUNCTION __rusty_free : DINT
+    VAR_INPUT
+        memory : BytePtr;
+    END_VAR
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        buffer : IntPtr;
+        value : DINT;
+    END_VAR
+        buffer := __NEW(INT, 4);
+        buffer[3] := 7;
+        value := buffer[3];
+        __DELETE(buffer);
+    END_PROGRAM
+    ";
+
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+    let context: Context = Context::create();
+    let source = &SourceCode {
+        path: "external_test.st".to_string(),
+        source: prog.to_string(),
+    };
+    let code_gen = compile_module(&context, &[source.as_source_container()]).unwrap();
+    let exec_engine = code_gen
+        .module
+        .create_jit_execution_engine(inkwell::OptimizationLevel::None)
+        .unwrap();
+
+    let alloc_fn = code_gen.module.get_function("__rusty_alloc").unwrap();
+    exec_engine.add_global_mapping(&alloc_fn, counting_alloc as usize);
+    let free_fn = code_gen.module.get_function("__rusty_free").unwrap();
+    exec_engine.add_global_mapping(&free_fn, counting_free as usize);
+
+    let mut main = MainType {
+        buffer: 0,
+        value: 0,
+    };
+    run::<MainType>(&exec_engine, "main", &mut main);
+
+    assert_eq!(7, main.value);
+    assert_eq!(0, main.buffer);
+    assert_eq!(8, ALLOCATED.load(Ordering::SeqCst));
+    assert_eq!(1, RELEASED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn allocations_use_the_size_type_of_the_target() {
+    let source = SourceCode {
+        path: "allocations.st".to_string(),
+        source: "
+        TYPE DintPtr : POINTER TO DINT; END_TYPE
+
+        PROGRAM main
+        VAR
+            buffer : DintPtr;
+            allocated : BOOL;
+        END_VAR
+            buffer := __NEW(DINT, 4);
+            allocated := buffer <> 0;
+            __DELETE(buffer);
+        END_PROGRAM
+        "
+        .to_string(),
+    };
+    let ir = compile_to_ir_with_options(
+        &[source.as_source_container()],
+        Some("i686-unknown-linux-gnu".to_string()),
+        CodeGenOptions::default(),
+    )
+    .unwrap();
+
+    assert!(ir.contains("declare i8* @malloc(i32)"));
+}
@@ -0,0 +1,193 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default)]
+struct MainType {
+    sel_false: i16,
+    sel_true: i16,
+    mux_2: i32,
+    mux_out_of_range: i32,
+    max_int: i16,
+    min_real: f32,
+    max_udint: u32,
+    limit_low: i32,
+    limit_in: i32,
+    limit_high: i32,
+    move_lreal: f64,
+}
+
+#[test]
+fn selection_functions_on_elementary_types() {
+    let function = r"
+        PROGRAM main
+        VAR
+            sel_false : INT;
+            sel_true : INT;
+            mux_2 : DINT;
+            mux_out_of_range : DINT;
+            max_int : INT;
+            min_real : REAL;
+            max_udint : UDINT;
+            limit_low : DINT;
+            limit_in : DINT;
+            limit_high : DINT;
+            move_lreal : LREAL;
+            big : UDINT;
+            small : UDINT;
+        END_VAR
+            sel_false := SEL(FALSE, 10, 20);
+            sel_true := SEL(G := TRUE, IN0 := 10, IN1 := 20);
+            mux_2 := MUX(2, 10, 20, 30, 40);
+            mux_out_of_range := MUX(7, 10, 20, 30);
+            max_int := MAX(3, -4, 12, 5);
+            min_real := MIN(2.5, 0.5, 1.5);
+            big := 2147483647;
+            big := big + 2;
+            small := 7;
+            max_udint := MAX(small, big);
+            limit_low := LIMIT(0, -5, 10);
+            limit_in := LIMIT(MN := 0, IN := 5, MX := 10);
+            limit_high := LIMIT(0, 15, 10);
+            move_lreal := MOVE(1.25);
+        END_PROGRAM
+        ";
+
+    let mut maintype = MainType::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(10, maintype.sel_false);
+    assert_eq!(20, maintype.sel_true);
+    assert_eq!(30, maintype.mux_2);
+    assert_eq!(10, maintype.mux_out_of_range);
+    assert_eq!(12, maintype.max_int);
+    assert_almost_eq!(0.5, maintype.min_real, f32::EPSILON);
+    assert_eq!(2147483649, maintype.max_udint);
+    assert_eq!(0, maintype.limit_low);
+    assert_eq!(5, maintype.limit_in);
+    assert_eq!(10, maintype.limit_high);
+    assert_almost_eq!(1.25, maintype.move_lreal, f64::EPSILON);
+}
+
+#[test]
+fn a_declared_function_takes_precedence_over_the_builtin() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Type {
+        result: i16,
+    }
+
+    let function = r"
+        FUNCTION LIMIT : INT
+        VAR_INPUT
+            mn : INT;
+            in : INT;
+            mx : INT;
+        END_VAR
+            LIMIT := 42;
+        END_FUNCTION
+
+        PROGRAM main
+        VAR
+            result : INT;
+        END_VAR
+            result := LIMIT(0, 5, 10);
+        END_PROGRAM
+        ";
+
+    let mut maintype = Type::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(42, maintype.result);
+}
+
+#[test]
+fn max_min_and_limit_accept_all_elementary_types() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Type {
+        max_time: i64,
+        limit_time: i64,
+        min_date: i64,
+        first_date: i64,
+        max_byte: u8,
+        max_string: [u8; 6],
+        min_string: [u8; 6],
+    }
+
+    let function = r"
+        PROGRAM main
+        VAR
+            max_time : TIME;
+            limit_time : TIME;
+            min_date : DATE;
+            first_date : DATE;
+            max_byte : BYTE;
+            max_string : STRING[5];
+            min_string : STRING[5];
+            low_byte : BYTE;
+            high_byte : BYTE;
+            ab : STRING[5];
+            abc : STRING[5];
+        END_VAR
+            max_time := MAX(T#1s, T#3s, T#2s);
+            limit_time := LIMIT(T#1s, T#5s, T#2s);
+            min_date := MIN(D#2021-01-02, D#2021-01-01);
+            first_date := D#2021-01-01;
+            low_byte := 15;
+            high_byte := 240;
+            max_byte := MAX(high_byte, low_byte);
+            ab := 'ab';
+            abc := 'abc';
+            max_string := MAX(ab, abc);
+            min_string := MIN(abc, ab);
+        END_PROGRAM
+        ";
+
+    let mut maintype = Type::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(3_000_000_000, maintype.max_time);
+    assert_eq!(2_000_000_000, maintype.limit_time);
+    assert_eq!(maintype.first_date, maintype.min_date);
+    assert_eq!(240, maintype.max_byte);
+    assert_eq!(b"abc\0\0\0", &maintype.max_string);
+    assert_eq!(b"ab\0\0\0\0", &maintype.min_string);
+}
+
+#[test]
+fn strings_of_different_lengths_can_be_selected() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct Type {
+        max_string: [u8; 6],
+        limit_string: [u8; 6],
+        sel_string: [u8; 6],
+    }
+
+    let function = r"
+        PROGRAM main
+        VAR
+            max_string : STRING[5];
+            limit_string : STRING[5];
+            sel_string : STRING[5];
+            s : STRING[5];
+        END_VAR
+            s := 'hello';
+            max_string := MAX(s, 'abc');
+            limit_string := LIMIT('b', s, 'ca');
+            sel_string := SEL(TRUE, s, 'x');
+        END_PROGRAM
+        ";
+
+    let mut maintype = Type::default();
+
+    compile_and_run(function.to_string(), &mut maintype);
+    assert_eq!(b"hello\0", &maintype.max_string);
+    assert_eq!(b"ca\0\0\0\0", &maintype.limit_string);
+    assert_eq!(b"x\0\0\0\0\0", &maintype.sel_string);
+}
@@ -0,0 +1,370 @@
+// Copyright (c) 2021 Ghaith Hachem and Mathias Rieder
+use super::super::*;
+use inkwell::targets::{InitializationConfig, Target};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// the time returned by the external time source in nanoseconds
+static CLOCK_NS: AtomicI64 = AtomicI64::new(0);
+
+extern "C" fn get_time_ns(_: *const u8) -> i64 {
+    CLOCK_NS.load(Ordering::SeqCst)
+}
+
+const MILLISECONDS: i64 = 1_000_000;
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Default)]
+struct TimerType {
+    start: bool,
+    q: bool,
+    et: i64,
+}
+
+#[test]
+fn on_delay_timer_uses_the_external_time_source() {
+    let prog = "
+    VAR_GLOBAL
+        timer : TON;
+    END_VAR
+
+    PROGRAM main
+    VAR
+        start : BOOL;
+        q : BOOL;
+        et : TIME;
+    END_VAR
+        timer(IN := start, PT := T#100ms);
+        q := timer.Q;
+        et := timer.ET;
+    END_PROGRAM
+    ";
+
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+    let context: Context = Context::create();
+    let source = &SourceCode {
+        path: "external_test.st".to_string(),
+        source: prog.to_string(),
+    };
+    let code_gen = compile_module(&context, &[source.as_source_container()]).unwrap();
+    let exec_engine = code_gen
+        .module
+        .create_jit_execution_engine(inkwell::OptimizationLevel::None)
+        .unwrap();
+    let fn_value = code_gen.module.get_function("__get_time_ns").unwrap();
+    exec_engine.add_global_mapping(&fn_value, get_time_ns as usize);
+
+    let mut main = TimerType {
+        start: true,
+        ..TimerType::default()
+    };
+    CLOCK_NS.store(1000 * MILLISECONDS, Ordering::SeqCst);
+    run(&exec_engine, "main", &mut main);
+    assert_eq!((false, 0), (main.q, main.et));
+
+    CLOCK_NS.store(1050 * MILLISECONDS, Ordering::SeqCst);
+    run(&exec_engine, "main", &mut main);
+    assert_eq!((false, 50 * MILLISECONDS), (main.q, main.et));
+
+    CLOCK_NS.store(1110 * MILLISECONDS, Ordering::SeqCst);
+    run(&exec_engine, "main", &mut main);
+    assert_eq!((true, 100 * MILLISECONDS), (main.q, main.et));
+
+    main.start = false;
+    run(&exec_engine, "main", &mut main);
+    assert_eq!((false, 0), (main.q, main.et));
+}
+
+#[test]
+fn timers_can_be_initialized_with_a_preset_time() {
+    let prog = "
+    VAR_GLOBAL
+        clock : TIME;
+        timer : TON := (PT := T#30ms);
+    END_VAR
+
+    FUNCTION __get_time_ns : TIME
+        __get_time_ns := clock;
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        start : BOOL;
+        q : BOOL;
+        et : TIME;
+    END_VAR
+        clock := clock + T#10ms;
+        timer(IN := start);
+        q := timer.Q;
+        et := timer.ET;
+    END_PROGRAM
+    ";
+
+    let context: Context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main = TimerType {
+        start: true,
+        ..TimerType::default()
+    };
+
+    let mut results = vec![];
+    for _ in 0..4 {
+        run(&exec_engine, "main", &mut main);
+        results.push((main.q, main.et / MILLISECONDS));
+    }
+    assert_eq!(
+        vec![(false, 0), (false, 10), (false, 20), (true, 30)],
+        results
+    );
+}
+
+#[test]
+fn off_delay_and_pulse_timers_with_a_time_source_declared_in_st() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        signal: bool,
+        tof_q: bool,
+        tp_q: bool,
+    }
+
+    let prog = "
+    VAR_GLOBAL
+        clock : TIME;
+        off_delay : TOF;
+        pulse : TP;
+    END_VAR
+
+    FUNCTION __get_time_ns : TIME
+        __get_time_ns := clock;
+    END_FUNCTION
+
+    PROGRAM main
+    VAR
+        signal : BOOL;
+        tof_q : BOOL;
+        tp_q : BOOL;
+    END_VAR
+        clock := clock + T#10ms;
+        off_delay(IN := signal, PT := T#30ms);
+        pulse(IN := signal, PT := T#30ms);
+        tof_q := off_delay.Q;
+        tp_q := pulse.Q;
+    END_PROGRAM
+    ";
+
+    let context: Context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main = MainType::default();
+
+    let mut results = vec![];
+    for signal in &[true, false, false, false, false] {
+        main.signal = *signal;
+        run(&exec_engine, "main", &mut main);
+        results.push((main.tof_q, main.tp_q));
+    }
+    assert_eq!(
+        vec![
+            (true, true),
+            (true, true),
+            (true, true),
+            (true, false),
+            (false, false)
+        ],
+        results
+    );
+}
+
+#[test]
+fn edge_detection_function_blocks() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        clk: bool,
+        rising_q: bool,
+        falling_q: bool,
+    }
+
+    let prog = "
+    PROGRAM main
+    VAR
+        clk : BOOL;
+        rising_q : BOOL;
+        falling_q : BOOL;
+        rising : R_TRIG;
+        falling : F_TRIG;
+    END_VAR
+        rising(CLK := clk);
+        falling(CLK := clk);
+        rising_q := rising.Q;
+        falling_q := falling.Q;
+    END_PROGRAM
+    ";
+
+    let context: Context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main = MainType::default();
+
+    let mut results = vec![];
+    for clk in &[false, true, true, false, false] {
+        main.clk = *clk;
+        run(&exec_engine, "main", &mut main);
+        results.push((main.rising_q, main.falling_q));
+    }
+    assert_eq!(
+        vec![
+            (false, false),
+            (true, false),
+            (false, false),
+            (false, true),
+            (false, false)
+        ],
+        results
+    );
+}
+
+#[test]
+fn counter_function_blocks() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        cu: bool,
+        cd: bool,
+        reset: bool,
+        load: bool,
+        up_q: bool,
+        up_cv: i16,
+        down_q: bool,
+        down_cv: i16,
+        up_down_qu: bool,
+        up_down_qd: bool,
+        up_down_cv: i16,
+    }
+
+    let prog = "
+    VAR_GLOBAL
+        up : CTU;
+        down : CTD;
+        up_down : CTUD;
+    END_VAR
+
+    PROGRAM main
+    VAR
+        cu : BOOL;
+        cd : BOOL;
+        reset : BOOL;
+        load : BOOL;
+        up_q : BOOL;
+        up_cv : INT;
+        down_q : BOOL;
+        down_cv : INT;
+        up_down_qu : BOOL;
+        up_down_qd : BOOL;
+        up_down_cv : INT;
+    END_VAR
+        up(CU := cu, R := reset, PV := 2);
+        up_q := up.Q;
+        up_cv := up.CV;
+
+        down(CD := cd, LD := load, PV := 2);
+        down_q := down.Q;
+        down_cv := down.CV;
+
+        up_down(CU := cu, CD := cd, R := reset, LD := load, PV := 2);
+        up_down_qu := up_down.QU;
+        up_down_qd := up_down.QD;
+        up_down_cv := up_down.CV;
+    END_PROGRAM
+    ";
+
+    let context: Context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main = MainType::default();
+
+    //(cu, cd, reset, load)
+    let inputs = [
+        (false, false, false, true),
+        (true, false, false, false),
+        (false, false, false, false),
+        (true, false, false, false),
+        (false, true, false, false),
+        (false, false, false, false),
+        (false, true, false, false),
+        (false, false, true, false),
+    ];
+    let mut results = vec![];
+    for (cu, cd, reset, load) in &inputs {
+        main.cu = *cu;
+        main.cd = *cd;
+        main.reset = *reset;
+        main.load = *load;
+        run(&exec_engine, "main", &mut main);
+        results.push((
+            (main.up_q, main.up_cv),
+            (main.down_q, main.down_cv),
+            (main.up_down_qu, main.up_down_qd, main.up_down_cv),
+        ));
+    }
+    assert_eq!(
+        vec![
+            ((false, 0), (false, 2), (true, false, 2)),
+            ((false, 1), (false, 2), (true, false, 3)),
+            ((false, 1), (false, 2), (true, false, 3)),
+            ((true, 2), (false, 2), (true, false, 4)),
+            ((true, 2), (false, 1), (true, false, 3)),
+            ((true, 2), (false, 1), (true, false, 3)),
+            ((true, 2), (true, 0), (true, false, 2)),
+            ((false, 0), (true, 0), (false, true, 0)),
+        ],
+        results
+    );
+}
+
+#[test]
+fn bistable_function_blocks() {
+    #[allow(dead_code)]
+    #[repr(C)]
+    #[derive(Default)]
+    struct MainType {
+        s: bool,
+        r: bool,
+        sr_q: bool,
+        rs_q: bool,
+    }
+
+    let prog = "
+    PROGRAM main
+    VAR
+        s : BOOL;
+        r : BOOL;
+        sr_q : BOOL;
+        rs_q : BOOL;
+        set_dominant : SR;
+        reset_dominant : RS;
+    END_VAR
+        set_dominant(S1 := s, R := r);
+        reset_dominant(S := s, R1 := r);
+        sr_q := set_dominant.Q1;
+        rs_q := reset_dominant.Q1;
+    END_PROGRAM
+    ";
+
+    let context: Context = Context::create();
+    let exec_engine = compile(&context, prog.to_string());
+    let mut main = MainType::default();
+
+    let mut results = vec![];
+    for (s, r) in &[(true, false), (false, false), (true, true), (false, true)] {
+        main.s = *s;
+        main.r = *r;
+        run(&exec_engine, "main", &mut main);
+        results.push((main.sr_q, main.rs_q));
+    }
+    assert_eq!(
+        vec![(true, true), (true, true), (true, false), (false, false)],
+        results
+    );
+}
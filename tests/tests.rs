@@ -7,13 +7,22 @@ type MainFunction<T> = unsafe extern "C" fn(*mut T) -> i32;
 
 mod correctness {
     mod arrays;
+    mod bitwise_operations;
+    mod case_insensitivity;
+    mod conditional_compilation;
     mod control_flow;
     mod custom_datatypes;
+    mod date_and_time_functions;
     mod datatypes;
+    mod division_checks;
     mod external_functions;
     mod functions;
+    mod generic_functions;
     mod global_variables;
     mod initial_values;
+    mod pointers;
+    mod selection_functions;
+    mod standard_function_blocks;
     mod sub_range_types;
     mod sums;
 }